@@ -0,0 +1,90 @@
+//! Generates lookup tables that cannot be computed in const context.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Smallest decade of any nonzero f64
+const POW10_MIN_EXP: i32 = -324;
+/// One past the largest decade of any finite f64
+const POW10_MAX_EXP: i32 = 309;
+
+fn main() {
+    let mut table = String::new();
+    writeln!(
+        table,
+        "/// Smallest f64 greater than or equal to `10^k`, indexed by `k - POW10_MIN_EXP`\n\
+         pub(crate) const POW10_CEIL: [f64; {}] = [",
+        POW10_MAX_EXP - POW10_MIN_EXP + 1
+    )
+    .unwrap();
+    for k in POW10_MIN_EXP..=POW10_MAX_EXP {
+        writeln!(
+            table,
+            "    f64::from_bits({:#018x}),",
+            pow10_ceil(k).to_bits()
+        )
+        .unwrap();
+    }
+    table.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("pow10_ceil.rs"), table).unwrap();
+
+    let mut table = String::from(
+        "/// `2^n` in engineering notation at 3 significant figures, indexed by `n`, e.g.\n\
+         /// `POW2_ENG_3SF[10] == \"1.02e3\"`\n\
+         pub const POW2_ENG_3SF: [&str; 64] = [\n",
+    );
+    for n in 0..64 {
+        writeln!(table, "    {:?},", pow2_eng_3sf(n)).unwrap();
+    }
+    table.push_str("];\n");
+    fs::write(Path::new(&out_dir).join("pow2_eng_3sf.rs"), table).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+/// Returns the smallest f64 greater than or equal to `10^k`
+fn pow10_ceil(k: i32) -> f64 {
+    // correctly rounded to nearest, so at most one step below `10^k`
+    let nearest: f64 = format!("1e{k}").parse().unwrap();
+    if nearest.is_infinite() {
+        return nearest;
+    }
+    // the exact expansion of a value below `10^k` starts with 9s at decade `k - 1`
+    let exact = format!("{nearest:.767e}");
+    let decade: i32 = exact.split_once('e').unwrap().1.parse().unwrap();
+    if nearest == 0. || decade < k {
+        nearest.next_up()
+    } else {
+        nearest
+    }
+}
+
+/// Returns `2^n` in engineering notation at 3 significant figures, rounded half up
+fn pow2_eng_3sf(n: u32) -> String {
+    let digits = (1_u64 << n).to_string();
+    let mut exp_sci = digits.len() as i32 - 1;
+    // first three digits, padded with zeros, rounded on the fourth
+    let padded = format!("{digits:0<4}");
+    let mut kept: u32 = padded[..3].parse().unwrap();
+    if padded.as_bytes()[3] >= b'5' {
+        kept += 1;
+    }
+    if kept == 1000 {
+        kept = 100;
+        exp_sci += 1;
+    }
+    let exp_eng = exp_sci - exp_sci.rem_euclid(3);
+    let n_left_of_dec = (exp_sci - exp_eng + 1) as usize;
+    let kept = kept.to_string();
+    let mut formatted = kept[..n_left_of_dec].to_string();
+    if n_left_of_dec < 3 {
+        formatted = format!("{formatted}.{}", &kept[n_left_of_dec..]);
+    }
+    if exp_eng != 0 {
+        formatted = format!("{formatted}e{exp_eng}");
+    }
+    formatted
+}
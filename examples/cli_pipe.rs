@@ -0,0 +1,48 @@
+//! Normalizes every measurement in text piped through stdin to engineering notation, leaving
+//! version numbers, dates, and other non-measurements alone, run with e.g.
+//! `echo "Drift was 0.012345 V after 2.2kΩ" | cargo run --example cli_pipe`.
+
+use std::io::{self, BufRead, Write};
+
+use eng_fmt::reformat_prose;
+
+/// Units that may directly follow a number
+const UNITS: [&str; 5] = ["V", "A", "Ω", "Hz", "s"];
+
+/// Copies `input` to `output` line by line, reformatting the numbers in each line
+fn normalize<R: BufRead, W: Write>(input: R, mut output: W, sf: Option<usize>) -> io::Result<()> {
+    for line in input.lines() {
+        writeln!(output, "{}", reformat_prose(&line?, sf, &UNITS))?;
+    }
+    output.flush()
+}
+
+fn main() -> io::Result<()> {
+    normalize(io::stdin().lock(), io::stdout().lock(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let input = "\
+Run 42 on 2024-01-15 with firmware v2.0.1:
+  offset 0.0012345 V, gain 1234.5, clock 32.768kHz
+  settling (0.000047s) within spec
+";
+        let expected = "\
+Run 42 on 2024-01-15 with firmware v2.0.1:
+  offset 1.23e-3 V, gain 1.23e3, clock 32.8e3Hz
+  settling (47.0e-6s) within spec
+";
+        let mut output = Vec::new();
+        normalize(input.as_bytes(), &mut output, None).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+
+        let mut output = Vec::new();
+        normalize("gain 1234.5".as_bytes(), &mut output, Some(5)).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "gain 1.2345e3\n");
+    }
+}
@@ -0,0 +1,88 @@
+//! Formats readings into a fixed-size stack buffer through [core::fmt::Write], as firmware
+//! that cannot hold on to heap strings would before sending a line over a serial port, run
+//! with `cargo run --example embedded`.
+
+use core::fmt::{self, Write};
+
+use eng_fmt::Eng;
+
+/// Line buffer of `N` bytes on the stack that reports an error rather than growing
+struct StackBuf<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> StackBuf<N> {
+    fn new() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // only whole `str`s are ever copied in
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Write for StackBuf<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > N {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Writes one telemetry line for a sensor reading into `buf`
+fn telemetry_line<const N: usize>(
+    buf: &mut StackBuf<N>,
+    sensor: &str,
+    reading: f64,
+    unit: &str,
+) -> fmt::Result {
+    buf.clear();
+    write!(buf, "{sensor:<6}{:>10.4}{unit}", Eng(reading))
+}
+
+fn main() {
+    let mut buf = StackBuf::<32>::new();
+    for (sensor, reading, unit) in [("vbat", 3.71234, "V"), ("ibat", -0.0482, "A")] {
+        telemetry_line(&mut buf, sensor, reading, unit).unwrap();
+        println!("{}", buf.as_str());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_telemetry_lines() {
+        let mut buf = StackBuf::<32>::new();
+        let expected = [
+            ("vbat", 3.71234, "V", "vbat       3.712V"),
+            ("ibat", -0.0482, "A", "ibat   -48.20e-3A"),
+            ("temp", 298.16, "K", "temp       298.2K"),
+            ("leak", 1.5e-12, "A", "leak   1.500e-12A"),
+        ];
+        for (sensor, reading, unit, line) in expected {
+            telemetry_line(&mut buf, sensor, reading, unit).unwrap();
+            assert_eq!(buf.as_str(), line);
+        }
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        let mut buf = StackBuf::<8>::new();
+        assert!(telemetry_line(&mut buf, "vbat", 3.71234, "V").is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! Generates tick labels for a linear axis, labeled on one shared exponent, and a logarithmic
+//! axis, labeled with SI prefixes, run with `cargo run --example plot_labels`.
+
+use eng_fmt::{common_eng_exp, format_eng_fixed, format_si};
+
+/// Returns about `n_max` evenly spaced tick values covering `min..=max`, stepping by 1, 2, or 5
+/// times a power of ten
+fn linear_ticks(min: f64, max: f64, n_max: usize) -> Vec<f64> {
+    let rough_step = (max - min) / n_max as f64;
+    let decade = 10_f64.powf(rough_step.log10().floor());
+    let step = [1., 2., 5., 10.]
+        .into_iter()
+        .map(|m| m * decade)
+        .find(|step| *step >= rough_step)
+        .unwrap();
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    // multiply rather than accumulate so the ticks do not drift
+    (first..=last).map(|i| i as f64 * step).collect()
+}
+
+/// Returns labels for `ticks` on their shared exponent, with the exponent returned separately
+/// for the axis title
+fn linear_labels(ticks: &[f64]) -> (Vec<String>, i32) {
+    let exp = common_eng_exp(ticks);
+    let labels = ticks
+        .iter()
+        .map(|tick| {
+            let label = format_eng_fixed(*tick, exp, Some(2)).unwrap();
+            String::from(label.split('e').next().unwrap())
+        })
+        .collect();
+    (labels, exp)
+}
+
+/// Returns SI-prefixed labels for the decades from `10^exp_min` to `10^exp_max` of a log axis
+fn log_labels(exp_min: i32, exp_max: i32, unit: &str) -> Vec<String> {
+    (exp_min..=exp_max)
+        .map(|exp| format!("{}{unit}", format_si(10_f64.powi(exp), Some(1))))
+        .collect()
+}
+
+fn main() {
+    let ticks = linear_ticks(-0.0012, 0.0031, 6);
+    let (labels, exp) = linear_labels(&ticks);
+    println!("current (e{exp} A): {}", labels.join("  "));
+    println!("frequency: {}", log_labels(1, 6, "Hz").join("  "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_labels() {
+        let ticks = linear_ticks(-0.0012, 0.0031, 6);
+        assert_eq!(
+            linear_labels(&ticks),
+            (
+                ["-1.0", "0.0", "1.0", "2.0", "3.0"]
+                    .map(String::from)
+                    .to_vec(),
+                -3
+            )
+        );
+        let ticks = linear_ticks(0., 250e3, 5);
+        assert_eq!(
+            linear_labels(&ticks),
+            (
+                ["0.0", "50", "100", "150", "200", "250"]
+                    .map(String::from)
+                    .to_vec(),
+                3
+            )
+        );
+    }
+
+    #[test]
+    fn test_log_labels() {
+        assert_eq!(
+            log_labels(-1, 4, "Hz"),
+            ["100mHz", "1Hz", "10Hz", "100Hz", "1kHz", "10kHz"]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Builds a Markdown report of bench measurements where each column shares one engineering
+//! exponent, run with `cargo run --example report`.
+
+use eng_fmt::{common_eng_exp, eng_decades, format_eng, format_eng_fixed};
+
+/// Bench measurements: channel, supply current in A, ripple in V
+const MEASUREMENTS: [(&str, f64, f64); 4] = [
+    ("ch0", 150e-6, 2.5e-3),
+    ("ch1", 12.34e-3, 812e-6),
+    ("ch2", 1.5, 31.7e-3),
+    ("ch3", 0., 1.04e-3),
+];
+
+/// Returns the Markdown report for [MEASUREMENTS]
+fn report() -> String {
+    let currents: Vec<f64> = MEASUREMENTS.iter().map(|row| row.1).collect();
+    let ripples: Vec<f64> = MEASUREMENTS.iter().map(|row| row.2).collect();
+    // current column scaled to its largest value, ripple column to the most compact exponent
+    let current_exp = common_eng_exp(&currents);
+    let ripple_exp = eng_decades(&ripples).recommended.unwrap_or(0);
+
+    let mut report = String::from("# Supply bench\n\n");
+    report.push_str(&format!(
+        "| channel | current (e{current_exp} A) | ripple (e{ripple_exp} V) |\n"
+    ));
+    report.push_str("|---|--:|--:|\n");
+    for (channel, current, ripple) in MEASUREMENTS {
+        let current = format_eng_fixed(current, current_exp, None).unwrap();
+        let ripple = format_eng_fixed(ripple, ripple_exp, Some(2)).unwrap();
+        // the shared exponent is in the header, so strip it from each cell
+        let current = current.split('e').next().unwrap();
+        let ripple = ripple.split('e').next().unwrap();
+        report.push_str(&format!("| {channel} | {current} | {ripple} |\n"));
+    }
+    let total: f64 = currents.iter().sum();
+    report.push_str(&format!(
+        "\nTotal current: {} A\n",
+        format_eng(total, Some(4))
+    ));
+    report
+}
+
+fn main() {
+    print!("{}", report());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let expected = "\
+# Supply bench
+
+| channel | current (e0 A) | ripple (e-3 V) |
+|---|--:|--:|
+| ch0 | 0.000150 | 2.5 |
+| ch1 | 0.0123 | 0.81 |
+| ch2 | 1.50 | 32 |
+| ch3 | 0.00 | 1.0 |
+
+Total current: 1.512 A
+";
+        assert_eq!(report(), expected);
+    }
+}
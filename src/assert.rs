@@ -0,0 +1,204 @@
+//! Module backing the assertion macros [eng_assert_close](crate::eng_assert_close) and
+//! [eng_assert_in_range](crate::eng_assert_in_range), whose failure messages show every operand
+//! in [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use crate::{format_eng, EngFormatter};
+
+/// Most significant figures shown in a failure message, enough to round trip any f64
+const MAX_MESSAGE_SF: usize = 17;
+
+/// Asserts that `a` is within relative tolerance `rel_tol` of `b`, i.e. `|a - b| <= rel_tol *
+/// |b|`, panicking with every operand in engineering notation otherwise.  Significant figures
+/// grow with the tolerance so the failure is visible in the digits, 5 for `rel_tol = 0.005`,
+/// and the message includes the relative and absolute differences.  Each operand is evaluated
+/// exactly once.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::eng_assert_close;
+/// eng_assert_close!(1.0021e3, 1e3, 0.005);
+/// let payload = std::panic::catch_unwind(|| eng_assert_close!(1.0213e3, 1e3, 0.005));
+/// assert_eq!(
+///     payload.unwrap_err().downcast_ref::<String>().unwrap(),
+///     "1.0213e3 = 1.0213e3 not within ±0.5 % of 1e3 = 1.0000e3 (Δ = +2.13 %, |Δ| = 21.3)"
+/// );
+/// ```
+#[macro_export]
+macro_rules! eng_assert_close {
+    ($a:expr, $b:expr, $rel_tol:expr $(,)?) => {
+        match (&$a, &$b, &$rel_tol) {
+            (a, b, rel_tol) => {
+                if let ::core::option::Option::Some(message) = $crate::__eng_close_failure(
+                    ::core::stringify!($a),
+                    *a,
+                    ::core::stringify!($b),
+                    *b,
+                    *rel_tol,
+                ) {
+                    ::core::panic!("{}", message);
+                }
+            }
+        }
+    };
+}
+
+/// Asserts that `lo <= x <= hi`, panicking with every operand in engineering notation
+/// otherwise.  Significant figures grow as the range narrows relative to its bounds so the
+/// failure is visible in the digits, and the message includes the distance past the violated
+/// bound.  Each operand is evaluated exactly once.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::eng_assert_in_range;
+/// let current = 4.7e-3;
+/// eng_assert_in_range!(current, 0., 5e-3);
+/// let payload = std::panic::catch_unwind(|| eng_assert_in_range!(current, 0., 1e-3));
+/// assert_eq!(
+///     payload.unwrap_err().downcast_ref::<String>().unwrap(),
+///     "current = 4.70e-3 not within [0.00, 1.00e-3] (above by 3.70e-3)"
+/// );
+/// ```
+#[macro_export]
+macro_rules! eng_assert_in_range {
+    ($x:expr, $lo:expr, $hi:expr $(,)?) => {
+        match (&$x, &$lo, &$hi) {
+            (x, lo, hi) => {
+                if let ::core::option::Option::Some(message) =
+                    $crate::__eng_range_failure(::core::stringify!($x), *x, *lo, *hi)
+                {
+                    ::core::panic!("{}", message);
+                }
+            }
+        }
+    };
+}
+
+/// Returns significant figures that resolve a relative difference of `rel`: 3, plus one for
+/// each decade below 1
+fn sf_for_rel(rel: f64) -> usize {
+    if rel.is_nan() || rel <= 0. {
+        return MAX_MESSAGE_SF;
+    }
+    let extra = (-rel.log10()).floor().max(0.);
+    (3 + extra.min(MAX_MESSAGE_SF as f64) as usize).min(MAX_MESSAGE_SF)
+}
+
+/// Returns the failure message of [eng_assert_close](crate::eng_assert_close), or `None` if
+/// `a` is within `rel_tol` of `b`
+#[doc(hidden)]
+pub fn __eng_close_failure(
+    a_expr: &str,
+    a: f64,
+    b_expr: &str,
+    b: f64,
+    rel_tol: f64,
+) -> Option<String> {
+    let diff = a - b;
+    if diff.abs() <= rel_tol * b.abs() {
+        return None;
+    }
+    let sf = sf_for_rel(rel_tol);
+    // tolerance as typed, without binary noise such as 0.30000000000000004
+    let tol_pct: f64 = format!("{:.14e}", rel_tol * 100.).parse().unwrap();
+    let rel_diff_pct = EngFormatter::new()
+        .mantissa_plus(true)
+        .format(diff / b.abs() * 100.);
+    Some(format!(
+        "{a_expr} = {} not within ±{tol_pct} % of {b_expr} = {} (Δ = {rel_diff_pct} %, |Δ| = {})",
+        format_eng(a, Some(sf)),
+        format_eng(b, Some(sf)),
+        format_eng(diff.abs(), None),
+    ))
+}
+
+/// Returns the failure message of [eng_assert_in_range](crate::eng_assert_in_range), or `None`
+/// if `lo <= x <= hi`
+#[doc(hidden)]
+pub fn __eng_range_failure(x_expr: &str, x: f64, lo: f64, hi: f64) -> Option<String> {
+    if lo <= x && x <= hi {
+        return None;
+    }
+    let sf = sf_for_rel((hi - lo) / lo.abs().max(hi.abs()));
+    let past = if x > hi {
+        format!(" (above by {})", format_eng(x - hi, None))
+    } else if x < lo {
+        format!(" (below by {})", format_eng(lo - x, None))
+    } else {
+        String::new()
+    };
+    Some(format!(
+        "{x_expr} = {} not within [{}, {}]{past}",
+        format_eng(x, Some(sf)),
+        format_eng(lo, Some(sf)),
+        format_eng(hi, Some(sf)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{catch_unwind, UnwindSafe};
+
+    /// Returns the text of the panic raised by `f`
+    fn panic_message(f: impl FnOnce() + UnwindSafe) -> String {
+        let payload = catch_unwind(f).unwrap_err();
+        payload.downcast_ref::<String>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_close_passes() {
+        eng_assert_close!(1.0049e3, 1e3, 0.005);
+        eng_assert_close!(-0.9951, -1., 0.005);
+        eng_assert_close!(0., 0., 0.);
+    }
+    #[test]
+    fn test_close_message() {
+        let x = 1.0213e3;
+        assert_eq!(
+            panic_message(|| eng_assert_close!(x, 1e3, 0.005)),
+            "x = 1.0213e3 not within ±0.5 % of 1e3 = 1.0000e3 (Δ = +2.13 %, |Δ| = 21.3)"
+        );
+        assert_eq!(
+            panic_message(|| eng_assert_close!(4.69e-6, 4.7e-6, 1e-3)),
+            "4.69e-6 = 4.69000e-6 not within ±0.1 % of 4.7e-6 = 4.70000e-6 \
+             (Δ = -213e-3 %, |Δ| = 10.0e-9)"
+        );
+        // precision grows with tightening tolerance
+        assert_eq!(
+            panic_message(|| eng_assert_close!(2., 1., 0.3)),
+            "2. = 2.00 not within ±30 % of 1. = 1.00 (Δ = +100 %, |Δ| = 1.00)"
+        );
+        assert_eq!(
+            panic_message(|| eng_assert_close!(f64::NAN, 1., 0.1)),
+            "f64::NAN = NaN not within ±10 % of 1. = 1.000 (Δ = NaN %, |Δ| = NaN)"
+        );
+    }
+    #[test]
+    fn test_in_range() {
+        eng_assert_in_range!(0., 0., 1.);
+        eng_assert_in_range!(1., 0., 1.);
+        assert_eq!(
+            panic_message(|| eng_assert_in_range!(-2e-9, 0., 1e-6)),
+            "-2e-9 = -2.00e-9 not within [0.00, 1.00e-6] (below by 2.00e-9)"
+        );
+        // a narrow range relative to its bounds shows more digits
+        assert_eq!(
+            panic_message(|| eng_assert_in_range!(50.07e6, 49.95e6, 50.05e6)),
+            "50.07e6 = 50.070e6 not within [49.950e6, 50.050e6] (above by 20.0e3)"
+        );
+        assert_eq!(
+            panic_message(|| eng_assert_in_range!(f64::NAN, 0., 1.)),
+            "f64::NAN = NaN not within [0.00, 1.00]"
+        );
+    }
+    #[test]
+    fn test_operands_evaluated_once() {
+        let mut n_evals = 0;
+        let mut next = |x: f64| {
+            n_evals += 1;
+            x
+        };
+        eng_assert_close!(next(1.), next(1.), next(0.1));
+        eng_assert_in_range!(next(1.), next(0.), next(2.));
+        assert_eq!(n_evals, 6);
+    }
+}
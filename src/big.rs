@@ -0,0 +1,189 @@
+//! Module for formatting decimal text of any precision in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation), which also serves as the
+//! reference the f64 paths are tested against.
+
+use crate::error::check_sf;
+use crate::exact::{format_rounded, try_round_digits, RoundingMode};
+use crate::math::mod_floor_i32;
+use crate::parse::{decimal_text, parse_eng, ParseEngError};
+
+/// Returns decimal or E-notation text of any length, e.g. 100 digits of π, in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation) at up to
+/// [MAX_SIG_FIGS](crate::MAX_SIG_FIGS) significant figures, rounded half away from zero from
+/// the exact digits without ever going through an f64.  So it agrees with
+/// [format_eng](crate::format_eng) given the exact decimal expansion of an f64.  Text is accepted as by [parse_eng_with_precision](crate::parse_eng_with_precision),
+/// including a trailing SI prefix.  The power of ten of every digit shown or kept, and the
+/// engineering exponent of the result, must fit in an `i32`, or else
+/// [ParseEngError::ExponentOutOfRange] is returned.  Zero keeps its sign, e.g. `"-0.00"`.
+///
+/// # Arguments
+/// - `digits` - number text to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_big;
+/// let pi = "3.14159265358979323846264338327950288419716939937510";
+/// assert_eq!(format_eng_big(pi, Some(30)).unwrap(), "3.14159265358979323846264338328");
+/// assert_eq!(format_eng_big("-1e-5000", None).unwrap(), "-10.0e-5001");
+/// ```
+pub fn format_eng_big(digits: &str, sf: Option<usize>) -> Result<String, ParseEngError> {
+    let sf = match check_sf(sf, f64::NAN) {
+        Ok(sf) => sf,
+        // name the value in the message as `format_eng` does
+        Err(_) => panic!(
+            "{}",
+            check_sf(sf, parse_eng(digits).unwrap_or(f64::NAN)).unwrap_err()
+        ),
+    };
+    let text = decimal_text(digits)?;
+    if text.digits.is_empty() {
+        let sign = if text.negative { "-" } else { "" };
+        return Ok(format!("{sign}{:.*}", sf - 1, 0.));
+    }
+    let all_digits: Vec<u8> = text.digits.bytes().map(|b| b - b'0').collect();
+    let exp_sci = text
+        .place
+        .checked_add(all_digits.len() as i32 - 1)
+        .ok_or(ParseEngError::ExponentOutOfRange)?;
+    let rounded = try_round_digits(
+        &all_digits,
+        exp_sci,
+        sf,
+        RoundingMode::HalfAwayFromZero,
+        text.negative,
+    )
+    .ok_or(ParseEngError::ExponentOutOfRange)?;
+    // the engineering exponent is the multiple of 3 at or below `exp_sci`
+    rounded
+        .exp_sci
+        .checked_sub(mod_floor_i32(rounded.exp_sci, 3))
+        .ok_or(ParseEngError::ExponentOutOfRange)?;
+    Ok(format_rounded(text.negative, &rounded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_eng;
+
+    /// π to 100 decimal places
+    const PI_100: &str = "3.\
+        1415926535897932384626433832795028841971693993751058209749445923078164062862089986280348253421170679";
+
+    #[test]
+    fn test_100_digits() {
+        assert_eq!(
+            format_eng_big(PI_100, Some(50)),
+            Ok(String::from(
+                "3.1415926535897932384626433832795028841971693993751"
+            ))
+        );
+        assert_eq!(
+            format_eng_big(&format!("-{PI_100}e-7"), Some(5)),
+            Ok(String::from("-314.16e-9"))
+        );
+        // 100 nines carry into a new leading digit
+        let nines = format!("0.{}", "9".repeat(100));
+        assert_eq!(format_eng_big(&nines, None), Ok(String::from("1.00")));
+        assert_eq!(
+            format_eng_big(&nines, Some(100)),
+            Ok(format!("999.{}e-3", "9".repeat(97)))
+        );
+        // more significant figures than digits pads with zeros
+        assert_eq!(
+            format_eng_big("1.5", Some(120)),
+            Ok(format!("1.5{}", "0".repeat(118)))
+        );
+    }
+    #[test]
+    fn test_exact_ties() {
+        assert_eq!(format_eng_big("2.5", Some(1)), Ok(String::from("3")));
+        assert_eq!(
+            format_eng_big("-0.125", Some(2)),
+            Ok(String::from("-130e-3"))
+        );
+        // the decimal 0.85, not the nearest f64, which is below it
+        assert_eq!(format_eng_big("0.85", Some(1)), Ok(String::from("900e-3")));
+    }
+    #[test]
+    fn test_forms() {
+        assert_eq!(
+            format_eng_big("  +1234.5E3 ", None),
+            Ok(String::from("1.23e6"))
+        );
+        assert_eq!(format_eng_big("2.50k", Some(2)), Ok(String::from("2.5e3")));
+        assert_eq!(format_eng_big("000.000", None), Ok(String::from("0.00")));
+        assert_eq!(format_eng_big("-0", Some(1)), Ok(String::from("-0")));
+        assert_eq!(
+            format_eng_big("1e999999", None),
+            Ok(String::from("1.00e999999"))
+        );
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(format_eng_big("", None), Err(ParseEngError::Empty));
+        assert_eq!(format_eng_big("1e", None), Err(ParseEngError::Incomplete));
+        assert_eq!(
+            format_eng_big("1x", None),
+            Err(ParseEngError::InvalidChar { at: 1, found: 'x' })
+        );
+        assert_eq!(
+            format_eng_big("1e99999999999", None),
+            Err(ParseEngError::ExponentOutOfRange)
+        );
+        assert_eq!(
+            format_eng_big("12e2147483647", None),
+            Err(ParseEngError::ExponentOutOfRange)
+        );
+    }
+    #[test]
+    #[should_panic(expected = "cannot format 0.015 with 768 significant figures, more than 767")]
+    fn test_sf_too_large() {
+        let _ = format_eng_big("15e-3", Some(768));
+    }
+    #[test]
+    fn test_exponent_limits() {
+        let table = [
+            ("9.9e2147483646", 1, Ok("10e2147483646")),
+            ("1e2147483647", 2, Ok("10e2147483646")),
+            ("9.9e2147483647", 1, Err(ParseEngError::ExponentOutOfRange)),
+            ("1e-2147483646", 1, Ok("1e-2147483646")),
+            ("1e-2147483646", 3, Ok("1.00e-2147483646")),
+            ("1e-2147483646", 4, Err(ParseEngError::ExponentOutOfRange)),
+            ("1e-2147483647", 1, Err(ParseEngError::ExponentOutOfRange)),
+            ("1e-2147483648", 1, Err(ParseEngError::ExponentOutOfRange)),
+        ];
+        for (digits, sf, expected) in table {
+            assert_eq!(
+                format_eng_big(digits, Some(sf)),
+                expected.map(String::from),
+                "{digits} {sf}"
+            );
+        }
+    }
+    #[test]
+    fn test_f64_paths_match_oracle() {
+        let mut state: u64 = 0xd1b5_4a32_d192_ed03;
+        for _ in 0..100_000 {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let x = f64::from_bits(state);
+            if !x.is_finite() {
+                continue;
+            }
+            let sf = 1 + (state >> 59) as usize % 17;
+            let exact = format!("{x:.767e}");
+            assert_eq!(
+                format_eng(x, Some(sf)),
+                format_eng_big(&exact, Some(sf)).unwrap(),
+                "x: {x:e}, sf: {sf}"
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Module for formatting values in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) with a guaranteed direction of
+//! rounding, for reports that must never under- or over-state a value.
+
+use crate::error::check_sf;
+use crate::exact::{format_eng_exact_rounded, RoundingMode};
+use crate::format_eng;
+
+/// Which side of the true value the displayed value is guaranteed to be on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    /// Displayed value is greater than or equal to the true value, e.g. for loads
+    Upper,
+    /// Displayed value is less than or equal to the true value, e.g. for margins
+    Lower,
+}
+
+/// Returns f64 as string in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation) with the last digit rounded
+/// toward positive infinity, so the displayed value is never less than `x`.  A carry
+/// renormalizes the exponent, e.g. 999.01 at 3 significant figures gives `"1.00e3"`.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_ceil;
+/// assert_eq!(format_eng_ceil(999.01, None), "1.00e3");
+/// assert_eq!(format_eng_ceil(-999.01, None), "-999");
+/// ```
+pub fn format_eng_ceil(x: f64, sf: Option<usize>) -> String {
+    format_eng_directed(x, sf, RoundingMode::Ceil)
+}
+
+/// Returns f64 as string in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation) with the last digit rounded
+/// toward negative infinity, so the displayed value is never greater than `x`.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_floor;
+/// assert_eq!(format_eng_floor(999.99, None), "999");
+/// assert_eq!(format_eng_floor(-999.01, None), "-1.00e3");
+/// ```
+pub fn format_eng_floor(x: f64, sf: Option<usize>) -> String {
+    format_eng_directed(x, sf, RoundingMode::Floor)
+}
+
+/// Returns f64 as a bound in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation): [format_eng_ceil] prefixed
+/// with `"≤"` for [Bound::Upper], or [format_eng_floor] prefixed with `"≥"` for [Bound::Lower],
+/// so the text reads as a true statement about `x`.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+/// - `bound` - side of `x` the displayed value must be on
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_eng_bound, Bound};
+/// assert_eq!(format_eng_bound(4_701., None, Bound::Upper), "≤4.71e3");
+/// assert_eq!(format_eng_bound(4_709., None, Bound::Lower), "≥4.70e3");
+/// ```
+pub fn format_eng_bound(x: f64, sf: Option<usize>, bound: Bound) -> String {
+    match bound {
+        Bound::Upper => format!("≤{}", format_eng_ceil(x, sf)),
+        Bound::Lower => format!("≥{}", format_eng_floor(x, sf)),
+    }
+}
+
+fn format_eng_directed(x: f64, sf: Option<usize>, rounding: RoundingMode) -> String {
+    let sf = check_sf(sf, x).unwrap_or_else(|err| panic!("{err}"));
+    if x == 0. || !x.is_finite() {
+        return format_eng(x, Some(sf));
+    }
+    format_eng_exact_rounded(x, sf, rounding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ceil_carry() {
+        assert_eq!(format_eng_ceil(999.01, None), String::from("1.00e3"));
+    }
+    #[test]
+    fn test_ceil_exact() {
+        // exactly representable, so nothing is discarded
+        assert_eq!(format_eng_ceil(0.25, Some(2)), String::from("250e-3"));
+        assert_eq!(format_eng_floor(-2.5e3, Some(2)), String::from("-2.5e3"));
+        assert_eq!(format_eng_ceil(0., None), String::from("0.00"));
+    }
+    #[test]
+    fn test_ceil_negative() {
+        assert_eq!(format_eng_ceil(-999.01, None), String::from("-999"));
+    }
+    #[test]
+    fn test_floor() {
+        assert_eq!(format_eng_floor(999.99, None), String::from("999"));
+        assert_eq!(format_eng_floor(0.0129, Some(2)), String::from("12e-3"));
+    }
+    #[test]
+    fn test_floor_negative_carry() {
+        assert_eq!(format_eng_floor(-999.01, None), String::from("-1.00e3"));
+    }
+    #[test]
+    fn test_bound() {
+        assert_eq!(
+            format_eng_bound(std::f64::consts::PI, None, Bound::Upper),
+            String::from("≤3.15")
+        );
+        assert_eq!(
+            format_eng_bound(std::f64::consts::PI, None, Bound::Lower),
+            String::from("≥3.14")
+        );
+    }
+    #[test]
+    #[should_panic(expected = "cannot format 1.5 with 2000 significant figures, more than 767")]
+    fn test_sf_too_large() {
+        format_eng_ceil(1.5, Some(2000));
+    }
+    #[test]
+    #[should_panic(expected = "cannot format 0 with 0 significant figures")]
+    fn test_sf_zero() {
+        format_eng_floor(0., Some(0));
+    }
+    #[test]
+    fn test_bounds_hold_random() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        for i in 0..100_000 {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let mantissa = (state >> 11) as f64 / (1_u64 << 53) as f64 * 20. - 10.;
+            let x = mantissa * 10_f64.powi((state % 61) as i32 - 30);
+            let sf = Some(i % 6 + 1);
+            let upper: f64 = format_eng_bound(x, sf, Bound::Upper)
+                .trim_start_matches('≤')
+                .parse()
+                .unwrap();
+            let lower: f64 = format_eng_bound(x, sf, Bound::Lower)
+                .trim_start_matches('≥')
+                .parse()
+                .unwrap();
+            assert!(lower <= x && x <= upper, "x: {x:e}, sf: {sf:?}");
+        }
+    }
+}
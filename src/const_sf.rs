@@ -0,0 +1,85 @@
+//! Module for formatting in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) with the number of significant
+//! figures fixed at compile time.
+
+use crate::error::MAX_SIG_FIGS;
+use crate::exact::write_eng_checked;
+
+/// Same as [format_eng](crate::format_eng) with `Some(SF)`, but with `SF` checked at compile
+/// time rather than at every call, and without the branches on an `Option` or on formatter
+/// options, for hot loops where the precision is a constant.  The output is identical.
+///
+/// # Compile-time errors
+/// If `SF` is 0 or more than [MAX_SIG_FIGS]:
+/// ```compile_fail
+/// eng_fmt::format_eng_const::<0>(1.5);
+/// ```
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_const;
+/// assert_eq!(format_eng_const::<4>(std::f64::consts::PI), "3.142");
+/// assert_eq!(format_eng_const::<3>(6.022e-23), "60.2e-24");
+/// ```
+#[inline]
+pub fn format_eng_const<const SF: usize>(x: f64) -> String {
+    const {
+        assert!(
+            SF >= 1 && SF <= MAX_SIG_FIGS,
+            "`format_eng_const` arg `SF` must be from 1 to `MAX_SIG_FIGS`."
+        )
+    };
+    let mut formatted = String::with_capacity(const { SF + 8 });
+    write_eng_checked(&mut formatted, x, SF).unwrap();
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_eng;
+
+    /// Asserts that [format_eng_const] matches [format_eng] for every `x` at each listed `SF`
+    macro_rules! assert_matches_format_eng {
+        ($values:expr, $($sf:literal),*) => {$(
+            for x in $values {
+                assert_eq!(format_eng_const::<$sf>(x), format_eng(x, Some($sf)), "{x:e}");
+            }
+        )*};
+    }
+
+    #[test]
+    fn test_matches_format_eng() {
+        // the values of the `format_eng` tests in lib.rs, plus special values
+        let values = [
+            2.,
+            std::f64::consts::PI,
+            std::f64::consts::PI / 10.,
+            std::f64::consts::PI / 5.,
+            -std::f64::consts::PI / 10.,
+            33.333,
+            66.666,
+            333.33,
+            666.66,
+            3.3333e3,
+            6.6666e3,
+            33.333e6,
+            66.666e6,
+            std::f64::consts::PI * 2.,
+            -std::f64::consts::PI * 2.,
+            -std::f64::consts::PI * 2e5,
+            std::f64::consts::PI * 2e5,
+            999.96,
+            6.022e-23,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+            0.,
+            -0.,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        assert_matches_format_eng!(values, 1, 2, 3, 4, 5, 7, 15, 16, 17, 20, 767);
+    }
+}
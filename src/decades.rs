@@ -0,0 +1,132 @@
+//! Module for summarizing which [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) exponents a dataset spans.
+
+use std::collections::BTreeSet;
+
+use crate::math::eng_exponent;
+
+/// Number of significant figures assumed when [eng_decades] counts mantissa digits
+const SUMMARY_SF: i32 = 3;
+
+/// Engineering exponents covered by a dataset, as returned by [eng_decades]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DecadeSummary {
+    /// Engineering exponents of the finite, nonzero values, in ascending order
+    pub exponents: BTreeSet<i32>,
+    /// Largest minus smallest of `exponents`, 0 if there are none
+    pub span: i32,
+    /// Exponent that minimizes the total mantissa digits if every value shares it, `None` if
+    /// there are no finite, nonzero values
+    pub recommended: Option<i32>,
+}
+
+/// Returns the engineering exponents that `values` cover, their span, and a recommended common
+/// exponent for displaying them all on one scale.  Zero and non-finite values are excluded.
+/// Exponents are taken before rounding, as from [eng_exponent].
+///
+/// The recommendation tries every multiple of 3 from the smallest to the largest exponent
+/// present and counts the mantissa digits each value would need at that exponent with 3
+/// significant figures: `max(3, d - e + 1)` for a value whose leading digit is at power of ten
+/// `d >= e`, and `e - d + 3` (a leading `0.`, padding zeros, and 3 digits) for `d < e`.  The
+/// exponent with the smallest total wins, and ties go to the smaller exponent.
+///
+/// # Arguments
+/// - `values` - dataset to be summarized
+///
+/// # Examples
+/// ```
+/// use eng_fmt::eng_decades;
+/// let summary = eng_decades(&[150e-6, 12e-3, 1.5, f64::NAN, 0.]);
+/// assert_eq!(summary.exponents.into_iter().collect::<Vec<_>>(), [-6, -3, 0]);
+/// assert_eq!(summary.span, 6);
+/// assert_eq!(summary.recommended, Some(-3));
+/// ```
+pub fn eng_decades(values: &[f64]) -> DecadeSummary {
+    let decades: Vec<i32> = values
+        .iter()
+        .filter(|x| x.is_finite() && **x != 0.)
+        .map(|x| crate::math::decade(*x))
+        .collect();
+    let exponents: BTreeSet<i32> = values
+        .iter()
+        .filter(|x| x.is_finite() && **x != 0.)
+        .map(|x| eng_exponent(*x, 3))
+        .collect();
+    let (Some(min), Some(max)) = (exponents.first(), exponents.last()) else {
+        return DecadeSummary::default();
+    };
+    let recommended = (*min..=*max)
+        .step_by(3)
+        .min_by_key(|e| total_digits(&decades, *e));
+    DecadeSummary {
+        span: max - min,
+        recommended,
+        exponents,
+    }
+}
+
+/// Returns the total mantissa digits needed to show values with leading digits at powers of ten
+/// `decades` at exponent `e`
+fn total_digits(decades: &[i32], e: i32) -> i64 {
+    decades
+        .iter()
+        .map(|d| match d - e + 1 {
+            n_left_of_dec if n_left_of_dec >= 1 => n_left_of_dec.max(SUMMARY_SF) as i64,
+            _ => (e - d + SUMMARY_SF) as i64,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_decade() {
+        let summary = eng_decades(&[1e3, 4.7e3, 999e3, -22e3]);
+        assert_eq!(summary.exponents, BTreeSet::from([3]));
+        assert_eq!(summary.span, 0);
+        assert_eq!(summary.recommended, Some(3));
+    }
+    #[test]
+    fn test_three_decades() {
+        let summary = eng_decades(&[150e-6, 12e-3, 1.5]);
+        assert_eq!(summary.exponents, BTreeSet::from([-6, -3, 0]));
+        assert_eq!(summary.span, 6);
+        // e-6: 150 + 12000 + 1500000 = 3 + 5 + 7 digits; e-3: 0.150 + 12.0 + 1500 = 4 + 3 + 4;
+        // e0: 0.000150 + 0.0120 + 1.50 = 7 + 5 + 3
+        assert_eq!(summary.recommended, Some(-3));
+    }
+    #[test]
+    fn test_recommendation_follows_the_majority() {
+        let summary = eng_decades(&[1e-3, 2e-3, 3e-3, 4e-3, 1e3]);
+        assert_eq!(summary.recommended, Some(-3));
+    }
+    #[test]
+    fn test_tie_goes_to_smaller_exponent() {
+        // e-3: 1.00 + 1000 = 7 digits; e0: 0.00100 + 1.00 = 9 digits; e3 is not a candidate
+        assert_eq!(eng_decades(&[1e-3, 1.]).recommended, Some(-3));
+        // e0: 100 + 1000 = 7 digits; e3: 0.100 + 1.00 = 7 digits
+        assert_eq!(eng_decades(&[100., 1e3]).recommended, Some(0));
+    }
+    #[test]
+    fn test_pathological_span() {
+        let values: Vec<f64> = (-30..=30)
+            .map(|k| format!("1e{k}").parse().unwrap())
+            .collect();
+        let summary = eng_decades(&values);
+        assert_eq!(summary.exponents.len(), 21);
+        assert_eq!(summary.exponents.first(), Some(&-30));
+        assert_eq!(summary.exponents.last(), Some(&30));
+        assert_eq!(summary.span, 60);
+        assert_eq!(summary.recommended, Some(0));
+    }
+    #[test]
+    fn test_excludes_zero_and_non_finite() {
+        let summary = eng_decades(&[0., f64::NAN, f64::INFINITY, 2e6, -0.]);
+        assert_eq!(summary.exponents, BTreeSet::from([6]));
+        assert_eq!(summary.span, 0);
+        assert_eq!(eng_decades(&[f64::NAN, 0.]), DecadeSummary::default());
+        assert_eq!(eng_decades(&[]), DecadeSummary::default());
+    }
+}
@@ -0,0 +1,319 @@
+//! Module for comparing two texts full of numbers, such as generated reports, value by value
+//! rather than character by character.
+
+use std::fmt;
+
+use crate::{format_eng, MAX_SIG_FIGS};
+
+/// One meaningful difference found by [eng_diff]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiffItem {
+    /// Number that differs at the compared significant figures
+    Value {
+        /// 1-based line number
+        line: usize,
+        /// 1-based character column of the number in the new text
+        column: usize,
+        /// Old value in engineering notation at the compared significant figures
+        old: String,
+        /// New value in engineering notation at the compared significant figures
+        new: String,
+        /// `(new - old) / |old|`, infinite if the old value is 0
+        rel_change: f64,
+    },
+    /// Non-numeric text that differs, with runs of whitespace treated as one space
+    Text {
+        /// 1-based line number
+        line: usize,
+        /// Old line with every number replaced by `#`
+        old: String,
+        /// New line with every number replaced by `#`
+        new: String,
+    },
+}
+
+/// Error returned by [eng_diff]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DiffError {
+    /// Texts differ in their number of lines, or `line` holds a different number of values in
+    /// each, so values cannot be paired by position
+    StructureMismatch { line: usize },
+    /// `tol_sf` is 0 or more than [MAX_SIG_FIGS]
+    TolSigFigsOutOfRange { tol_sf: usize },
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffError::StructureMismatch { line } => {
+                write!(f, "texts differ in structure at line {line}")
+            }
+            DiffError::TolSigFigsOutOfRange { tol_sf } => write!(
+                f,
+                "tolerance must be from 1 to {MAX_SIG_FIGS} significant figures, not {tol_sf}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DiffError {}
+
+/// Returns the meaningful differences between `old` and `new`, pairing their numbers by
+/// position line by line.  Numbers are compared after rounding to `tol_sf` significant figures
+/// as by [format_eng], so `"1.0e3"` and `"1000.2"` match at 3, and the surrounding text is
+/// compared with runs of whitespace collapsed, so changes in formatting alone give no items.
+/// A number is an optional sign, digits with an optional fraction, and an optional exponent,
+/// not directly preceded by a letter, digit, `_`, or `.`, so `v2` and `x86` are text.
+///
+/// # Arguments
+/// - `old` - text of the earlier file
+/// - `new` - text of the later file
+/// - `tol_sf` - significant figures at which values are compared
+///
+/// # Errors
+/// [DiffError::TolSigFigsOutOfRange], before anything is compared, if `tol_sf` is 0 or more
+/// than [MAX_SIG_FIGS], or [DiffError::StructureMismatch] at the first line where the texts
+/// have different numbers of lines or values.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{eng_diff, DiffItem};
+/// let old = "gain: 1.000e3  offset: 12.0e-3\n";
+/// let new = "gain: 1000.1 offset: 12.5e-3\n";
+/// assert_eq!(
+///     eng_diff(old, new, 3),
+///     Ok(vec![DiffItem::Value {
+///         line: 1,
+///         column: 22,
+///         old: String::from("12.0e-3"),
+///         new: String::from("12.5e-3"),
+///         rel_change: (12.5e-3 - 12.0e-3) / 12.0e-3,
+///     }])
+/// );
+/// ```
+pub fn eng_diff(old: &str, new: &str, tol_sf: usize) -> Result<Vec<DiffItem>, DiffError> {
+    if !(1..=MAX_SIG_FIGS).contains(&tol_sf) {
+        return Err(DiffError::TolSigFigsOutOfRange { tol_sf });
+    }
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    if old_lines.len() != new_lines.len() {
+        return Err(DiffError::StructureMismatch {
+            line: old_lines.len().min(new_lines.len()) + 1,
+        });
+    }
+    let mut items = Vec::new();
+    for (i, (old_line, new_line)) in old_lines.iter().zip(&new_lines).enumerate() {
+        let line = i + 1;
+        let old_tokens = tokenize(old_line);
+        let new_tokens = tokenize(new_line);
+        if old_tokens.numbers.len() != new_tokens.numbers.len() {
+            return Err(DiffError::StructureMismatch { line });
+        }
+        if old_tokens.text != new_tokens.text {
+            items.push(DiffItem::Text {
+                line,
+                old: old_tokens.text,
+                new: new_tokens.text,
+            });
+        }
+        for (old_number, new_number) in old_tokens.numbers.iter().zip(&new_tokens.numbers) {
+            let old_formatted = format_eng(old_number.value, Some(tol_sf));
+            let new_formatted = format_eng(new_number.value, Some(tol_sf));
+            if old_formatted != new_formatted {
+                items.push(DiffItem::Value {
+                    line,
+                    column: new_number.column,
+                    old: old_formatted,
+                    new: new_formatted,
+                    rel_change: (new_number.value - old_number.value) / old_number.value.abs(),
+                });
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// Number found by [tokenize]
+struct Number {
+    /// 1-based character column of the first character
+    column: usize,
+    value: f64,
+}
+
+/// Line split into its numbers and the text around them
+struct Tokens {
+    numbers: Vec<Number>,
+    /// Line with every number replaced by `#` and whitespace collapsed
+    text: String,
+}
+
+/// Splits `line` into numbers as described for [eng_diff] and the text around them
+fn tokenize(line: &str) -> Tokens {
+    let chars: Vec<char> = line.chars().collect();
+    let mut numbers = Vec::new();
+    let mut text = String::with_capacity(line.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let boundary =
+            i == 0 || !(chars[i - 1].is_alphanumeric() || matches!(chars[i - 1], '_' | '.'));
+        match boundary.then(|| number_len(&chars[i..])).flatten() {
+            Some(n) => {
+                let number: String = chars[i..i + n].iter().collect();
+                numbers.push(Number {
+                    column: i + 1,
+                    value: number.parse().unwrap(),
+                });
+                text.push('#');
+                i += n;
+            }
+            None => {
+                if chars[i].is_whitespace() {
+                    if !text.ends_with(' ') {
+                        text.push(' ');
+                    }
+                } else {
+                    text.push(chars[i]);
+                }
+                i += 1;
+            }
+        }
+    }
+    Tokens {
+        numbers,
+        text: String::from(text.trim()),
+    }
+}
+
+/// Returns the number of characters in the number at the start of `chars`, if there is one
+fn number_len(chars: &[char]) -> Option<usize> {
+    let count_digits = |from: usize| {
+        chars.get(from..).map_or(0, |rest| {
+            rest.iter().take_while(|c| c.is_ascii_digit()).count()
+        })
+    };
+    let mut i = usize::from(matches!(chars.first(), Some('-' | '+')));
+    let n_int = count_digits(i);
+    i += n_int;
+    let mut n_frac = 0;
+    if chars.get(i) == Some(&'.') {
+        n_frac = count_digits(i + 1);
+        if n_int + n_frac > 0 {
+            i += 1 + n_frac;
+        }
+    }
+    if n_int + n_frac == 0 {
+        return None;
+    }
+    if matches!(chars.get(i), Some('e' | 'E')) {
+        let n_sign = usize::from(matches!(chars.get(i + 1), Some('-' | '+')));
+        let n_exp = count_digits(i + 1 + n_sign);
+        if n_exp > 0 {
+            i += 1 + n_sign + n_exp;
+        }
+    }
+    Some(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "\
+# Supply bench, release 2.1
+channel  current (A)  ripple (V)
+ch0      150.0e-6     2.5e-3
+ch1      0.012341     812e-6
+total: 12.49e-3 A over 2 channels
+";
+
+    #[test]
+    fn test_formatting_only() {
+        let new = "\
+# Supply bench, release 2.1
+channel current (A) ripple (V)
+ch0   0.00015000    0.0025
+ch1   12.34e-3      0.812e-3
+total:   0.012490 A over 2. channels
+";
+        assert_eq!(eng_diff(OLD, new, 3), Ok(vec![]));
+        assert_eq!(eng_diff(OLD, OLD, 17), Ok(vec![]));
+    }
+    #[test]
+    fn test_one_value() {
+        let new = OLD.replace("812e-6", "815e-6");
+        assert_eq!(
+            eng_diff(OLD, &new, 3),
+            Ok(vec![DiffItem::Value {
+                line: 4,
+                column: 23,
+                old: String::from("812e-6"),
+                new: String::from("815e-6"),
+                rel_change: (815e-6 - 812e-6) / 812e-6,
+            }])
+        );
+        // below the compared significant figures
+        assert_eq!(eng_diff(OLD, &new, 2), Ok(vec![]));
+    }
+    #[test]
+    fn test_text_change() {
+        let new = OLD
+            .replace("release 2.1", "release 2.2")
+            .replace("over", "across");
+        assert_eq!(
+            eng_diff(OLD, &new, 3),
+            Ok(vec![
+                DiffItem::Value {
+                    line: 1,
+                    column: 25,
+                    old: String::from("2.10"),
+                    new: String::from("2.20"),
+                    rel_change: (2.2 - 2.1) / 2.1,
+                },
+                DiffItem::Text {
+                    line: 5,
+                    old: String::from("total: # A over # channels"),
+                    new: String::from("total: # A across # channels"),
+                },
+            ])
+        );
+    }
+    #[test]
+    fn test_structure_mismatch() {
+        let new = OLD.replace("ch1      0.012341     812e-6", "ch1      0.012341");
+        assert_eq!(
+            eng_diff(OLD, &new, 3),
+            Err(DiffError::StructureMismatch { line: 4 })
+        );
+        let new = format!("{OLD}extra line\n");
+        assert_eq!(
+            eng_diff(OLD, &new, 3).map_err(|err| err.to_string()),
+            Err(String::from("texts differ in structure at line 6"))
+        );
+    }
+    #[test]
+    fn test_tol_sf_out_of_range() {
+        for tol_sf in [0, MAX_SIG_FIGS + 1] {
+            assert_eq!(
+                eng_diff("1.5", "1.6", tol_sf),
+                Err(DiffError::TolSigFigsOutOfRange { tol_sf })
+            );
+        }
+        // checked before the structure
+        assert_eq!(
+            eng_diff("1.5", "", 0).map_err(|err| err.to_string()),
+            Err(String::from(
+                "tolerance must be from 1 to 767 significant figures, not 0"
+            ))
+        );
+        assert_eq!(eng_diff("1.5", "1.6", MAX_SIG_FIGS).unwrap().len(), 1);
+    }
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("v2 x86 a-1 (-.5e3) 1e 3rd 1.2.3 +4E-2");
+        let values: Vec<f64> = tokens.numbers.iter().map(|n| n.value).collect();
+        assert_eq!(values, [1., -0.5e3, 1., 3., 1.2, 4e-2]);
+        assert_eq!(tokens.text, "v2 x86 a-# (#) #e #rd #.3 #");
+    }
+}
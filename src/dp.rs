@@ -0,0 +1,126 @@
+//! Module for formatting in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) with a fixed number of decimal
+//! places in the mantissa rather than a fixed number of significant figures.
+
+use crate::exact::{expansion_digits, round_digits, write_eng, RoundingMode, EXACT_PRECISION};
+use crate::format_eng;
+use crate::math::eng_exponent_from_sci;
+use crate::parse::decimal_text;
+
+/// Returns `x` in [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation)
+/// with exactly `decimal_places` digits after the decimal point of the mantissa, whatever its
+/// magnitude, e.g. `1.23e6`, `12.35e6`, and `123.46e6` at 2.  Digits are rounded half away
+/// from zero from the exact binary value of `x`, like [format_eng], and a carry past 999 moves
+/// to the next exponent, so 999.996 at 2 is `"1.00e3"` rather than `"1000.00"`.  Zero keeps its
+/// sign, and NaN and infinities are formatted as by [format_eng].
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `decimal_places` - number of digits after the decimal point, 0 for an integer mantissa
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_dp;
+/// assert_eq!(format_eng_dp(12.345_678e6, 2), "12.35e6");
+/// assert_eq!(format_eng_dp(123.456e6, 0), "123e6");
+/// assert_eq!(format_eng_dp(999.996, 2), "1.00e3");
+/// ```
+pub fn format_eng_dp(x: f64, decimal_places: usize) -> String {
+    if x == 0. {
+        return format!("{x:.decimal_places$}");
+    }
+    if !x.is_finite() {
+        return format_eng(x, None);
+    }
+    let (all_digits, exp_sci) = expansion_digits(x, EXACT_PRECISION);
+    format_digits_dp(x < 0., &all_digits, exp_sci, decimal_places)
+}
+
+/// Same as [format_eng_dp] for the exact decimal text that
+/// [FormatEng::format_eng](crate::FormatEng::format_eng) gives at
+/// [MAX_SIG_FIGS](crate::MAX_SIG_FIGS), which is returned unchanged if it is not a number
+pub(crate) fn format_text_dp(exact: String, decimal_places: usize) -> String {
+    let Ok(text) = decimal_text(&exact) else {
+        return exact;
+    };
+    if text.digits.is_empty() {
+        let sign = if text.negative { "-" } else { "" };
+        return format!("{sign}{:.*}", decimal_places, 0.);
+    }
+    let all_digits: Vec<u8> = text.digits.bytes().map(|b| b - b'0').collect();
+    let exp_sci = text.place + all_digits.len() as i32 - 1;
+    format_digits_dp(text.negative, &all_digits, exp_sci, decimal_places)
+}
+
+/// Formats the nonzero magnitude with decimal digits `all_digits` (most significant first) and
+/// leading digit at power of ten `exp_sci` as described for [format_eng_dp]
+fn format_digits_dp(
+    negative: bool,
+    all_digits: &[u8],
+    exp_sci: i32,
+    decimal_places: usize,
+) -> String {
+    let n_left_of_dec = |exp_sci| (exp_sci - eng_exponent_from_sci(exp_sci, 3) + 1) as usize;
+    let sf = n_left_of_dec(exp_sci) + decimal_places;
+    let rounded = round_digits(
+        all_digits,
+        exp_sci,
+        sf,
+        RoundingMode::HalfAwayFromZero,
+        negative,
+    );
+    // a carry leaves a 1 and zeros, which fill the new number of digits exactly
+    let mut digits: Vec<u8> = rounded.digits.iter().map(|d| b'0' + d).collect();
+    digits.resize(n_left_of_dec(rounded.exp_sci) + decimal_places, b'0');
+    write_eng(negative, &digits, rounded.exp_sci)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_eng_dp() {
+        let table = [
+            (1.234_56e6, 2, "1.23e6"),
+            (12.345_678e6, 2, "12.35e6"),
+            (123.456e6, 2, "123.46e6"),
+            (123.456e6, 0, "123e6"),
+            (-4.5, 0, "-5"),
+            (0.1, 4, "100.0000e-3"),
+            (2.5, 0, "3"),
+            // 0.125 is exact, so it is a tie, while 1.005 is stored below its tie
+            (0.125, 1, "125.0e-3"),
+            (1.005, 2, "1.00"),
+            (1.234e-20, 1, "12.3e-21"),
+        ];
+        for (x, dp, expected) in table {
+            assert_eq!(format_eng_dp(x, dp), expected, "x: {x:e}, dp: {dp}");
+        }
+    }
+    #[test]
+    fn test_carries() {
+        assert_eq!(format_eng_dp(999.996, 2), String::from("1.00e3"));
+        assert_eq!(format_eng_dp(-999.5, 0), String::from("-1e3"));
+        assert_eq!(format_eng_dp(99.996, 2), String::from("100.00"));
+        assert_eq!(format_eng_dp(9.9996e3, 3), String::from("10.000e3"));
+        assert_eq!(format_eng_dp(999_999.7, 0), String::from("1e6"));
+    }
+    #[test]
+    fn test_special_values() {
+        assert_eq!(format_eng_dp(0., 2), String::from("0.00"));
+        assert_eq!(format_eng_dp(-0., 0), String::from("-0"));
+        assert_eq!(format_eng_dp(f64::NAN, 2), String::from("NaN"));
+        assert_eq!(format_eng_dp(f64::NEG_INFINITY, 2), String::from("-inf"));
+        assert_eq!(format_eng_dp(5e-324, 1), String::from("4.9e-324"));
+    }
+    #[test]
+    fn test_matches_text_path() {
+        for x in [1.234_56e6, -999.996, 0.1, 5e-324, f64::MAX, -0., f64::NAN] {
+            for dp in 0..=4 {
+                let exact = format_eng(x, Some(crate::MAX_SIG_FIGS));
+                assert_eq!(format_text_dp(exact, dp), format_eng_dp(x, dp), "x: {x:e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Module implementing [FormatEng] for [Duration] and formatting durations in time units, e.g.
+//! `"1.50 ms"`.
+
+use std::time::Duration;
+
+use crate::error::check_sf;
+use crate::exact::{format_rounded, round_digits, RoundingMode};
+use crate::{try_format_eng, EngFmtError, FormatEng};
+
+impl FormatEng for Duration {
+    /// Returns the duration in seconds as string in [engineering
+    /// notation](https://en.wikipedia.org/wiki/Engineering_notation), rounded half away from
+    /// zero from its exact count of nanoseconds rather than from [Duration::as_secs_f64], so
+    /// that long durations keep their nanoseconds, e.g. 10⁷ s + 1 ns at 17 significant figures
+    /// is `"10.000000000000001e6"`.
+    ///
+    /// # Arguments
+    /// - `sf` - Number of significant figures, defaults to 3
+    ///
+    /// # Examples
+    /// ```
+    /// use eng_fmt::FormatEng;
+    /// use std::time::Duration;
+    /// assert_eq!(Duration::from_micros(1500).format_eng(None), "1.50e-3");
+    /// ```
+    fn try_format_eng(&self, sf: Option<usize>) -> Result<String, EngFmtError> {
+        let sf = check_sf(sf, self.as_secs_f64())?;
+        let nanos = self.as_nanos();
+        if nanos == 0 {
+            return try_format_eng(0., Some(sf));
+        }
+        let digits: Vec<u8> = nanos.to_string().bytes().map(|b| b - b'0').collect();
+        let exp_sci = digits.len() as i32 - 1 - 9;
+        Ok(format_rounded(
+            false,
+            &round_digits(&digits, exp_sci, sf, RoundingMode::HalfAwayFromZero, false),
+        ))
+    }
+}
+
+/// Returns `d` in the time unit of its engineering exponent, i.e. `ns`, `µs` (U+00B5 MICRO
+/// SIGN), `ms`, or `s`, e.g. `"1.50 ms"` rather than `"1.50e-3"`.  The mantissa is exactly that
+/// of [format_eng](FormatEng::format_eng) for `d`, including carries such as 999.96 µs becoming
+/// `"1.00 ms"`.  Durations of 1000 s or more are left in seconds with their `e` exponent, e.g.
+/// `"5.40e3 s"` for 1.5 h, and zero is `"0.00 s"`.
+///
+/// # Arguments
+/// - `d` - duration to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_duration;
+/// use std::time::Duration;
+/// assert_eq!(format_duration(Duration::from_micros(1500), None), "1.50 ms");
+/// assert_eq!(format_duration(Duration::from_micros(250), None), "250 µs");
+/// assert_eq!(format_duration(Duration::from_millis(3200), None), "3.20 s");
+/// assert_eq!(format_duration(Duration::from_nanos(45), None), "45.0 ns");
+/// assert_eq!(format_duration(Duration::from_secs(5400), None), "5.40e3 s");
+/// ```
+pub fn format_duration(d: Duration, sf: Option<usize>) -> String {
+    let formatted = d.format_eng(sf);
+    let Some((mantissa, exp)) = formatted.split_once('e') else {
+        return format!("{formatted} s");
+    };
+    let unit = match exp {
+        "-9" => "ns",
+        "-6" => "µs",
+        "-3" => "ms",
+        _ => return format!("{formatted} s"),
+    };
+    format!("{mantissa} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{format_eng, MAX_SIG_FIGS};
+
+    #[test]
+    fn test_units() {
+        let table = [
+            (Duration::from_nanos(1), "1.00 ns"),
+            (Duration::from_nanos(45), "45.0 ns"),
+            (Duration::from_nanos(999), "999 ns"),
+            (Duration::from_nanos(1_234), "1.23 µs"),
+            (Duration::from_micros(250), "250 µs"),
+            (Duration::from_micros(1500), "1.50 ms"),
+            (Duration::from_millis(999), "999 ms"),
+            (Duration::from_millis(3200), "3.20 s"),
+            (Duration::from_secs(999), "999 s"),
+            (Duration::from_secs(1000), "1.00e3 s"),
+            (Duration::from_secs(5400), "5.40e3 s"),
+            (Duration::from_secs(86_400 * 365), "31.5e6 s"),
+            (Duration::MAX, "18.4e18 s"),
+        ];
+        for (d, expected) in table {
+            assert_eq!(format_duration(d, None), expected, "{d:?}");
+        }
+    }
+    #[test]
+    fn test_carry() {
+        assert_eq!(
+            format_duration(Duration::from_nanos(999_960), None),
+            "1.00 ms"
+        );
+        assert_eq!(
+            format_duration(Duration::from_nanos(999_500), None),
+            "1.00 ms"
+        );
+        assert_eq!(
+            format_duration(Duration::from_nanos(999_499), None),
+            "999 µs"
+        );
+        assert_eq!(
+            format_duration(Duration::from_millis(999_600), None),
+            "1.00e3 s"
+        );
+    }
+    #[test]
+    fn test_zero() {
+        assert_eq!(format_duration(Duration::ZERO, None), "0.00 s");
+        assert_eq!(format_duration(Duration::ZERO, Some(1)), "0 s");
+        assert_eq!(Duration::ZERO.format_eng(Some(4)), "0.000");
+    }
+    #[test]
+    fn test_sub_nanosecond() {
+        // durations hold whole nanoseconds, so digits past them are exact zeros
+        assert_eq!(
+            format_duration(Duration::from_nanos(1_234), Some(6)),
+            "1.23400 µs"
+        );
+        // sub-nanosecond parts are lost on conversion, not by formatting
+        assert_eq!(
+            format_duration(Duration::from_secs_f64(0.4e-9), None),
+            "0.00 s"
+        );
+        assert_eq!(
+            format_duration(Duration::from_secs_f64(1.25e-6), Some(4)),
+            "1.250 µs"
+        );
+    }
+    #[test]
+    fn test_exact_nanos() {
+        let d = Duration::new(10_000_000, 1);
+        assert_eq!(d.format_eng(Some(17)), "10.000000000000001e6");
+        assert_eq!(
+            format_eng(d.as_secs_f64(), Some(17)),
+            "10.000000000000002e6"
+        );
+        // ties are exact in nanoseconds but not in seconds as f64
+        assert_eq!(Duration::from_nanos(45).format_eng(Some(1)), "50e-9");
+        assert_eq!(format_eng(45e-9, Some(1)), "40e-9");
+        assert_eq!(
+            Duration::MAX.format_eng(Some(30)),
+            "18.4467440737095516159999999990e18"
+        );
+    }
+    #[test]
+    fn test_matches_secs_f64() {
+        for nanos in [
+            1,
+            7,
+            999,
+            1_234,
+            31_416,
+            271_828_183,
+            3_200_000_000,
+            5_400_000_000_000,
+        ] {
+            let d = Duration::from_nanos(nanos);
+            for sf in [1, 2, 3, 5] {
+                assert_eq!(
+                    d.format_eng(Some(sf)),
+                    format_eng(d.as_secs_f64(), Some(sf)),
+                    "{d:?} {sf}"
+                );
+            }
+        }
+    }
+    #[test]
+    fn test_sf_errors() {
+        let d = Duration::from_millis(1500);
+        assert_eq!(
+            d.try_format_eng(Some(0)),
+            Err(EngFmtError::SigFigsZero { value: 1.5 })
+        );
+        assert_eq!(
+            d.try_format_eng(Some(MAX_SIG_FIGS + 1)),
+            Err(EngFmtError::SigFigsTooLarge {
+                sf: MAX_SIG_FIGS + 1,
+                value: 1.5
+            })
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Module providing [Eng], which formats an f64 in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) through `format!` without
+//! allocating.
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use crate::error::check_sf;
+use crate::exact::{with_eng_parts, DigitBuf};
+use crate::parse::{parse_eng, ParseEngError};
+use crate::parts::Sign;
+use crate::sci::pad_nan;
+
+/// f64 newtype parsed from engineering notation with [parse_eng](crate::parse_eng) and
+/// formatted in engineering notation with [Display](fmt::Display), so it works with `format!`
+/// width, fill, and alignment like any other number.  The precision gives the significant
+/// figures, defaulting to 3, and digits are rounded as by [format_eng](crate::format_eng).
+/// [LowerExp](fmt::LowerExp) is the same as [Display](fmt::Display) and
+/// [UpperExp](fmt::UpperExp) writes the exponent as `E`.  The `+` and `0` flags behave as for
+/// numbers.
+///
+/// # Panics
+/// When formatted with precision 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::Eng;
+/// let x: Eng = "-314e-3".parse().unwrap();
+/// assert_eq!(x, Eng(-0.314));
+/// assert_eq!(format!("{x}"), "-314e-3");
+/// assert_eq!(format!("{:>10.4}", Eng(std::f64::consts::PI * 1e5)), "   314.2e3");
+/// assert_eq!(format!("{:<8.2E}|", Eng(4.7e-6)), "4.7E-6  |");
+/// assert_eq!(format!("{:+09}", Eng(12e3)), "+0012.0e3");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Eng(pub f64);
+
+impl FromStr for Eng {
+    type Err = ParseEngError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_eng(s).map(Eng)
+    }
+}
+
+/// Writes `x` at `sf` significant figures with exponent marker `exp_char`, padding per the
+/// formatter's flags, through the same allocation-free path as
+/// [format_eng_into](crate::format_eng_into)
+pub(crate) fn pad_eng(
+    f: &mut fmt::Formatter<'_>,
+    x: f64,
+    sf: Option<usize>,
+    exp_char: char,
+) -> fmt::Result {
+    if x.is_nan() {
+        return pad_nan(f);
+    }
+    if x.is_infinite() {
+        return f.pad_integral(!x.is_sign_negative(), "", "inf");
+    }
+    let sf = check_sf(sf, x).unwrap_or_else(|err| panic!("{err}"));
+    with_eng_parts(x, sf, |sign, mantissa, exponent| {
+        let mut magnitude = DigitBuf::new();
+        magnitude.write_str(mantissa)?;
+        if exponent != 0 {
+            write!(magnitude, "{exp_char}{exponent}")?;
+        }
+        f.pad_integral(sign == Sign::Positive, "", magnitude.as_str())
+    })
+}
+
+impl fmt::Display for Eng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad_eng(f, self.0, f.precision(), 'e')
+    }
+}
+
+impl fmt::LowerExp for Eng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad_eng(f, self.0, f.precision(), 'e')
+    }
+}
+
+impl fmt::UpperExp for Eng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad_eng(f, self.0, f.precision(), 'E')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_eng;
+
+    #[test]
+    fn test_eng_display_matches_format_eng() {
+        let values = [1., -1., 0., -0., 999.96, -1.602176634e-19, f64::MAX, 5e-324];
+        for x in values {
+            assert_eq!(format!("{}", Eng(x)), format_eng(x, None));
+            for sf in (1..=8).chain([17, 100, crate::MAX_SIG_FIGS]) {
+                assert_eq!(format!("{:.*}", sf, Eng(x)), format_eng(x, Some(sf)));
+                assert_eq!(format!("{:.*e}", sf, Eng(x)), format_eng(x, Some(sf)));
+            }
+        }
+        for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(format!("{}", Eng(x)), format_eng(x, None));
+        }
+    }
+    #[test]
+    fn test_eng_display_flags() {
+        let x = Eng(std::f64::consts::PI * 1e5);
+        assert_eq!(format!("{x:>10.4}"), String::from("   314.2e3"));
+        assert_eq!(format!("{x:*<10.4}"), String::from("314.2e3***"));
+        assert_eq!(format!("{x:^11.4E}"), String::from("  314.2E3  "));
+        assert_eq!(format!("{x:+}"), String::from("+314e3"));
+        assert_eq!(format!("{:08.2}", Eng(-4.7e-6)), String::from("-04.7e-6"));
+        assert_eq!(format!("{:3}", Eng(1234.)), String::from("1.23e3"));
+        assert_eq!(format!("{:>5}", Eng(f64::NAN)), String::from("  NaN"));
+        assert_eq!(format!("{:+}", Eng(f64::NAN)), String::from("NaN"));
+        assert_eq!(
+            format!("{:>6}", Eng(f64::NEG_INFINITY)),
+            String::from("  -inf")
+        );
+    }
+    #[test]
+    #[should_panic(expected = "0 significant figures")]
+    fn test_eng_display_zero_precision_panics() {
+        let _ = format!("{:.0}", Eng(1.));
+    }
+}
@@ -0,0 +1,109 @@
+//! Module containing the error returned by the fallible formatting functions.
+
+use std::fmt;
+
+/// Largest number of significant figures accepted by [try_format_eng](crate::try_format_eng),
+/// since the exact decimal expansion of an f64 has at most 767 significant digits and more can
+/// only add zeros
+pub const MAX_SIG_FIGS: usize = 767;
+
+/// Error returned by [try_format_eng](crate::try_format_eng)
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum EngFmtError {
+    /// Number of significant figures is 0, e.g. from a subtraction that went to zero
+    SigFigsZero {
+        /// Value that was being formatted
+        value: f64,
+    },
+    /// Number of significant figures is more than [MAX_SIG_FIGS], e.g. from a subtraction that
+    /// wrapped around
+    SigFigsTooLarge {
+        /// Requested number of significant figures
+        sf: usize,
+        /// Value that was being formatted
+        value: f64,
+    },
+    /// Number of significant figures set on a formatter, e.g. by
+    /// [try_sig_figs](crate::EngFormatter::try_sig_figs), is 0 or more than [MAX_SIG_FIGS]
+    SigFigsOutOfRange {
+        /// Requested number of significant figures
+        sf: usize,
+    },
+    /// Digit-group separator is the same character as the decimal mark, e.g. `','` with
+    /// [DecimalMark::Comma](crate::DecimalMark::Comma), so the output could not be read back
+    AmbiguousSeparator {
+        /// Character used for both
+        separator: char,
+    },
+}
+
+impl fmt::Display for EngFmtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngFmtError::SigFigsZero { value } => {
+                write!(f, "cannot format {value} with 0 significant figures")
+            }
+            EngFmtError::SigFigsTooLarge { sf, value } => write!(
+                f,
+                "cannot format {value} with {sf} significant figures, more than {MAX_SIG_FIGS}"
+            ),
+            EngFmtError::SigFigsOutOfRange { sf } => write!(
+                f,
+                "number of significant figures must be from 1 to {MAX_SIG_FIGS}, not {sf}"
+            ),
+            EngFmtError::AmbiguousSeparator { separator } => {
+                write!(f, "digit separator {separator:?} is also the decimal mark")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EngFmtError {}
+
+/// Returns `sf` or its default of 3, or an error naming `sf` and `value` if it is out of range
+pub(crate) fn check_sf(sf: Option<usize>, value: f64) -> Result<usize, EngFmtError> {
+    match sf.unwrap_or(3) {
+        0 => Err(EngFmtError::SigFigsZero { value }),
+        sf @ 1..=MAX_SIG_FIGS => Ok(sf),
+        sf => Err(EngFmtError::SigFigsTooLarge { sf, value }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_sf() {
+        assert_eq!(check_sf(None, 1.5), Ok(3));
+        assert_eq!(check_sf(Some(1), 1.5), Ok(1));
+        assert_eq!(check_sf(Some(MAX_SIG_FIGS), 1.5), Ok(MAX_SIG_FIGS));
+        assert_eq!(
+            check_sf(Some(0), 1.5),
+            Err(EngFmtError::SigFigsZero { value: 1.5 })
+        );
+        assert_eq!(
+            check_sf(Some(usize::MAX), -2.5),
+            Err(EngFmtError::SigFigsTooLarge {
+                sf: usize::MAX,
+                value: -2.5
+            })
+        );
+    }
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            EngFmtError::SigFigsZero { value: 1.5 }.to_string(),
+            "cannot format 1.5 with 0 significant figures"
+        );
+        assert_eq!(
+            EngFmtError::SigFigsTooLarge {
+                sf: 768,
+                value: -2.5e-9
+            }
+            .to_string(),
+            "cannot format -0.0000000025 with 768 significant figures, more than 767"
+        );
+    }
+}
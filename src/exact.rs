@@ -0,0 +1,655 @@
+//! Module containing the correctly rounded digit paths behind [format_eng](crate::format_eng),
+//! from fastest to slowest: f64 scaling when it is provably accurate enough, digits with guard
+//! digits from `{:e}`, and rounding of the full exact decimal expansion.  The digits are
+//! produced in a stack buffer, so writing into a `fmt::Write` never allocates.
+
+use std::fmt::{self, Write};
+
+use crate::parts::{write_parts, Sign};
+
+/// Number of digits after the decimal point needed for `{:e}` to print the exact decimal
+/// expansion of any f64
+pub(crate) const EXACT_PRECISION: usize = 767;
+
+#[cfg(test)]
+thread_local! {
+    static FORCE_FALLBACK: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Test hook that forces [format_eng](crate::format_eng) onto [digits_exact] for the
+/// current thread
+#[cfg(test)]
+pub(crate) fn force_fallback(on: bool) {
+    FORCE_FALLBACK.with(|f| f.set(on));
+}
+
+/// Returns true if [force_fallback] is active for the current thread
+pub(crate) fn fallback_forced() -> bool {
+    #[cfg(test)]
+    return FORCE_FALLBACK.with(|f| f.get());
+    #[cfg(not(test))]
+    false
+}
+
+/// Rule for rounding away the digits past the last significant figure, applied to the exact
+/// decimal value of a number, e.g. for 0.125 at 2 significant figures
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to nearest, with ties away from zero: `0.13`, the default everywhere in this crate
+    #[default]
+    HalfAwayFromZero,
+    /// Round to nearest, with ties to an even last digit, i.e. banker's rounding: `0.12`
+    HalfEven,
+    /// Drop the discarded digits: `0.12`, and `-0.12` for -0.125
+    TruncateTowardZero,
+    /// Round toward positive infinity: `0.13`, and `-0.12` for -0.125
+    Ceil,
+    /// Round toward negative infinity: `0.12`, and `-0.13` for -0.125
+    Floor,
+}
+
+/// Formats `x` in engineering notation at `1 <= sf <= MAX_SIG_FIGS` significant figures through
+/// the fastest correctly rounded path, with the special values of
+/// [format_eng](crate::format_eng)
+pub(crate) fn format_eng_checked(x: f64, sf: usize) -> String {
+    let mut formatted = String::with_capacity(sf + 8);
+    write_eng_checked(&mut formatted, x, sf).unwrap();
+    formatted
+}
+
+/// Same as [format_eng_checked] but writes into `out` without allocating
+pub(crate) fn write_eng_checked<W: fmt::Write>(out: &mut W, x: f64, sf: usize) -> fmt::Result {
+    with_eng_parts(x, sf, |sign, mantissa, exponent| {
+        write_parts(out, sign, mantissa, exponent)
+    })
+}
+
+/// Calls `f` with the sign, mantissa, and engineering exponent of `x` at `1 <= sf <=
+/// MAX_SIG_FIGS` significant figures, as in [EngParts](crate::EngParts), through the fastest
+/// correctly rounded path, without allocating
+pub(crate) fn with_eng_parts<R>(x: f64, sf: usize, f: impl FnOnce(Sign, &str, i32) -> R) -> R {
+    let sign = match x.is_sign_negative() && !x.is_nan() {
+        true => Sign::Negative,
+        false => Sign::Positive,
+    };
+    if x == 0. {
+        let mut mantissa = DigitBuf::new();
+        write!(mantissa, "{:.*}", sf - 1, 0.).unwrap();
+        return f(sign, mantissa.as_str(), 0);
+    }
+    if x.is_nan() {
+        return f(sign, "NaN", 0);
+    }
+    if x.is_infinite() {
+        return f(sign, "inf", 0);
+    }
+    let mut buf = DigitBuf::new();
+    let exp_sci = if fallback_forced() {
+        digits_exact(x, sf, &mut buf)
+    } else {
+        digits_fast(x, sf, &mut buf)
+            .or_else(|| digits_guarded(x, sf, &mut buf))
+            .unwrap_or_else(|| digits_exact(x, sf, &mut buf))
+    };
+    let mut mantissa = DigitBuf::new();
+    let exp_eng = write_mantissa_into(&mut mantissa, &buf.as_bytes()[..sf], exp_sci).unwrap();
+    f(sign, mantissa.as_str(), exp_eng)
+}
+
+/// Capacity of [DigitBuf], enough for `{:.767e}` of any f64
+const DIGIT_BUF_BYTES: usize = EXACT_PRECISION + 16;
+
+/// Stack buffer that the digit paths format into, so formatting never allocates
+pub(crate) struct DigitBuf {
+    bytes: [u8; DIGIT_BUF_BYTES],
+    len: usize,
+}
+
+impl DigitBuf {
+    pub(crate) fn new() -> Self {
+        Self {
+            bytes: [0; DIGIT_BUF_BYTES],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // only ever written with ASCII
+        std::str::from_utf8(self.as_bytes()).unwrap()
+    }
+}
+
+impl fmt::Write for DigitBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > DIGIT_BUF_BYTES {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Largest `sf` for which [digits_fast] can hold the rounded digits exactly in an f64
+const FAST_MAX_SF: usize = 15;
+
+/// Bound on the relative error of scaling by `10_f64.powi(k)` for `|k| <= 300`, far above the
+/// few ULPs it can actually accumulate
+const FAST_TOLERANCE: f64 = 1. / (1_u64 << 40) as f64;
+
+/// Fills `buf` with the `sf` ASCII digits of finite, nonzero `x` rounded like [digits_exact] by
+/// scaling it to an integer with f64 arithmetic and returns the power of ten of the leading
+/// digit, or returns `None` if the scaled value is too close to a tie for its rounding error,
+/// or if `sf` or the exponent is too large for the scaling to be accurate
+pub(crate) fn digits_fast(x: f64, sf: usize, buf: &mut DigitBuf) -> Option<i32> {
+    if sf > FAST_MAX_SF {
+        return None;
+    }
+    let mut exp_sci = crate::math::decade(x);
+    let k = sf as i32 - 1 - exp_sci;
+    if k.abs() > 300 {
+        return None;
+    }
+    let scaled = match k {
+        k if k >= 0 => x.abs() * 10_f64.powi(k),
+        k => x.abs() / 10_f64.powi(-k),
+    };
+    if (scaled - scaled.floor() - 0.5).abs() <= scaled * FAST_TOLERANCE {
+        return None;
+    }
+    let mut kept = scaled.round() as u64;
+    if kept == 10_u64.pow(sf as u32) {
+        // rounding carried into a new leading digit
+        kept /= 10;
+        exp_sci += 1;
+    }
+    buf.len = 0;
+    write!(buf, "{kept}").unwrap();
+    Some(exp_sci)
+}
+
+/// Fills `buf` like [digits_fast] from the digits of `x` correctly rounded to three guard
+/// digits past `sf`, or returns `None` if the guard digits are exactly 500 and may hide a tie,
+/// since `{:e}` rounds ties to even
+pub(crate) fn digits_guarded(x: f64, sf: usize, buf: &mut DigitBuf) -> Option<i32> {
+    let exp_sci = expansion_into(x, sf + 2, buf);
+    if buf.as_bytes()[sf..] == *b"500" {
+        return None;
+    }
+    Some(round_ascii_in_place(&mut buf.bytes[..buf.len], sf, exp_sci))
+}
+
+/// Fills `buf` like [digits_fast] by rounding the full exact decimal expansion of `x` half away
+/// from zero
+pub(crate) fn digits_exact(x: f64, sf: usize, buf: &mut DigitBuf) -> i32 {
+    let exp_sci = expansion_into(x, EXACT_PRECISION, buf);
+    round_ascii_in_place(&mut buf.bytes[..buf.len], sf, exp_sci)
+}
+
+/// Fills `buf` with the ASCII digits of `|x|` correctly rounded to `1 <= precision` digits
+/// after the leading digit, without the decimal point, and returns the power of ten of the
+/// leading digit
+fn expansion_into(x: f64, precision: usize, buf: &mut DigitBuf) -> i32 {
+    buf.len = 0;
+    // expansion formatted as `d.ddd...e<exp>`
+    write!(buf, "{:.*e}", precision, x.abs()).unwrap();
+    let n_mantissa = precision + 2;
+    let exp_sci = std::str::from_utf8(&buf.bytes[n_mantissa + 1..buf.len])
+        .unwrap()
+        .parse()
+        .unwrap();
+    buf.bytes.copy_within(2..n_mantissa, 1);
+    buf.len = n_mantissa - 1;
+    exp_sci
+}
+
+/// Rounds the ASCII `digits` (leading digit at power of ten `exp_sci`) half away from zero to
+/// their first `sf`, where the digits past `sf` are exact or already rounded, and returns the
+/// power of ten of the leading digit after any carry
+fn round_ascii_in_place(digits: &mut [u8], sf: usize, mut exp_sci: i32) -> i32 {
+    if digits.get(sf).is_some_and(|d| *d >= b'5') {
+        // increment the last kept digit, propagating the carry
+        match digits[..sf].iter().rposition(|d| *d != b'9') {
+            Some(i) => {
+                digits[i] += 1;
+                digits[i + 1..sf].fill(b'0');
+            }
+            None => {
+                digits[..sf].fill(b'0');
+                digits[0] = b'1';
+                exp_sci += 1;
+            }
+        }
+    }
+    exp_sci
+}
+
+/// Formats ASCII significant `digits` whose leading digit is at power of ten `exp_sci` in
+/// engineering notation, with a leading `-` if `negative`
+pub(crate) fn write_eng(negative: bool, digits: &[u8], exp_sci: i32) -> String {
+    let mut formatted = String::with_capacity(digits.len() + 8);
+    write_eng_into(&mut formatted, negative, digits, exp_sci, 'e').unwrap();
+    formatted
+}
+
+/// Same as [write_eng] but writes into `out` with exponent marker `exp_char`
+pub(crate) fn write_eng_into<W: fmt::Write>(
+    out: &mut W,
+    negative: bool,
+    digits: &[u8],
+    exp_sci: i32,
+    exp_char: char,
+) -> fmt::Result {
+    if negative {
+        out.write_char('-')?;
+    }
+    let exp_eng = write_mantissa_into(out, digits, exp_sci)?;
+    if exp_eng != 0 {
+        write!(out, "{exp_char}{exp_eng}")?;
+    }
+    Ok(())
+}
+
+/// Writes the unsigned engineering notation mantissa of ASCII significant `digits` whose
+/// leading digit is at power of ten `exp_sci` into `out` and returns the engineering exponent
+fn write_mantissa_into<W: fmt::Write>(
+    out: &mut W,
+    digits: &[u8],
+    exp_sci: i32,
+) -> Result<i32, fmt::Error> {
+    let exp_eng = crate::math::eng_exponent_from_sci(exp_sci, 3);
+    let n_left_of_dec = (exp_sci - exp_eng + 1) as usize;
+    for i in 0..n_left_of_dec.max(digits.len()) {
+        if i == n_left_of_dec {
+            out.write_char('.')?;
+        }
+        out.write_char(char::from(digits.get(i).copied().unwrap_or(b'0')))?;
+    }
+    Ok(exp_eng)
+}
+
+/// Formats finite, nonzero `x` in engineering notation at `sf` significant figures by rounding
+/// the full exact decimal expansion of `x` half away from zero
+#[cfg(test)]
+pub(crate) fn format_eng_exact(x: f64, sf: usize) -> String {
+    format_eng_exact_rounded(x, sf, RoundingMode::HalfAwayFromZero)
+}
+
+/// Significant digits of `|x|` rounded per a [RoundingMode], as produced by [round_exact]
+pub(crate) struct Rounded {
+    /// Kept digits, most significant first
+    pub(crate) digits: Vec<u8>,
+    /// Power of ten of the first kept digit after rounding
+    pub(crate) exp_sci: i32,
+    /// True if rounding carried into a new leading digit, e.g. 999.7 → 1.00e3
+    pub(crate) carried: bool,
+    /// True if the magnitude was rounded up
+    round_up: bool,
+    /// Discarded digits of the exact expansion, with trailing zeros removed
+    discarded: Vec<u8>,
+    /// Power of ten of the last kept digit before any carry
+    exp_last: i32,
+}
+
+impl Rounded {
+    /// Returns true if no nonzero digits were discarded
+    pub(crate) fn is_exact(&self) -> bool {
+        self.discarded.is_empty()
+    }
+
+    /// Returns `|rounded| - |x|` correctly rounded to f64
+    pub(crate) fn magnitude_error(&self) -> f64 {
+        let Some((last, init)) = self.discarded.split_last() else {
+            return 0.;
+        };
+        // rounding up adds the ten's complement of the discarded digits
+        let fraction: String = if self.round_up {
+            init.iter()
+                .map(|d| char::from(b'0' + 9 - d))
+                .chain([char::from(b'0' + 10 - last)])
+                .collect()
+        } else {
+            self.discarded
+                .iter()
+                .map(|d| char::from(b'0' + d))
+                .collect()
+        };
+        let magnitude: f64 = format!("0.{fraction}e{}", self.exp_last).parse().unwrap();
+        if self.round_up {
+            magnitude
+        } else {
+            -magnitude
+        }
+    }
+}
+
+/// Rounds the exact decimal expansion of finite, nonzero `x` to `sf` significant digits per
+/// `rounding`
+pub(crate) fn round_exact(x: f64, sf: usize, rounding: RoundingMode) -> Rounded {
+    debug_assert!(x.is_finite() && x != 0.);
+    let (all_digits, exp_sci) = expansion_digits(x, EXACT_PRECISION);
+    round_digits(&all_digits, exp_sci, sf, rounding, x < 0.)
+}
+
+/// Returns the digits of `|x|` correctly rounded to `precision` digits after the leading digit,
+/// most significant first, and the power of ten of the leading digit
+pub(crate) fn expansion_digits(x: f64, precision: usize) -> (Vec<u8>, i32) {
+    // expansion formatted as `d.ddd...e<exp>`
+    let expansion = format!("{:.*e}", precision, x.abs());
+    let (mantissa, exp) = expansion.split_once('e').unwrap();
+    let digits = mantissa
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .collect();
+    (digits, exp.parse().unwrap())
+}
+
+/// Rounds the magnitude with decimal digits `all_digits` (most significant first, nonzero
+/// leading digit) and leading digit at power of ten `exp_sci` to `sf` significant digits per
+/// `rounding`, where `negative` gives the sign for [RoundingMode::Ceil] and [RoundingMode::Floor]
+pub(crate) fn round_digits(
+    all_digits: &[u8],
+    exp_sci: i32,
+    sf: usize,
+    rounding: RoundingMode,
+    negative: bool,
+) -> Rounded {
+    try_round_digits(all_digits, exp_sci, sf, rounding, negative)
+        .expect("exponents of f64 and integer digits are far from the i32 limits")
+}
+
+/// Same as [round_digits] but returns `None` if the power of ten of the last kept digit, or of
+/// the leading digit after a carry, does not fit in an `i32`, as for digits parsed from text
+pub(crate) fn try_round_digits(
+    all_digits: &[u8],
+    mut exp_sci: i32,
+    sf: usize,
+    rounding: RoundingMode,
+    negative: bool,
+) -> Option<Rounded> {
+    let exp_last = i32::try_from(i64::from(exp_sci) - i64::try_from(sf).ok()? + 1).ok()?;
+    let mut discarded = all_digits.get(sf..).unwrap_or_default().to_vec();
+    let n_discarded = discarded.iter().rposition(|d| *d != 0).map_or(0, |i| i + 1);
+    discarded.truncate(n_discarded);
+    let inexact = !discarded.is_empty();
+    let round_up = match rounding {
+        RoundingMode::HalfAwayFromZero => discarded.first().is_some_and(|d| *d >= 5),
+        RoundingMode::HalfEven => match discarded.as_slice() {
+            // exact tie, so round up only from an odd last kept digit
+            [5] => all_digits.get(sf - 1).is_some_and(|d| d % 2 == 1),
+            discarded => discarded.first().is_some_and(|d| *d >= 5),
+        },
+        RoundingMode::TruncateTowardZero => false,
+        RoundingMode::Ceil => inexact && !negative,
+        RoundingMode::Floor => inexact && negative,
+    };
+    let mut digits: Vec<u8> = (0..sf)
+        .map(|i| all_digits.get(i).copied().unwrap_or(0))
+        .collect();
+    let mut carried = false;
+    if round_up {
+        // increment the last kept digit, propagating the carry
+        match digits.iter().rposition(|d| *d != 9) {
+            Some(i) => {
+                digits[i] += 1;
+                digits[i + 1..].iter_mut().for_each(|d| *d = 0);
+            }
+            None => {
+                digits.iter_mut().for_each(|d| *d = 0);
+                digits[0] = 1;
+                exp_sci = exp_sci.checked_add(1)?;
+                carried = true;
+            }
+        }
+    }
+    Some(Rounded {
+        digits,
+        exp_sci,
+        carried,
+        round_up,
+        discarded,
+        exp_last,
+    })
+}
+
+/// Returns the decimal digits of the integer `n` such that `n * 10^place` is finite `|x|`
+/// rounded half away from zero to a multiple of `10^place`, without leading zeros (`"0"` for 0)
+pub(crate) fn round_at_place(x: f64, place: i32) -> String {
+    if x == 0. {
+        return String::from("0");
+    }
+    let (all_digits, exp_sci) = expansion_digits(x, EXACT_PRECISION);
+    let n_kept = exp_sci - place + 1;
+    if n_kept < 0 || (n_kept == 0 && all_digits[0] < 5) {
+        return String::from("0");
+    }
+    if n_kept == 0 {
+        return String::from("1");
+    }
+    let rounded = round_digits(
+        &all_digits,
+        exp_sci,
+        n_kept as usize,
+        RoundingMode::HalfAwayFromZero,
+        false,
+    );
+    let mut n: String = rounded
+        .digits
+        .iter()
+        .map(|d| char::from(b'0' + d))
+        .collect();
+    if rounded.carried {
+        n.push('0');
+    }
+    n
+}
+
+/// Compares finite `|x|` with the decimal `n * 10^place`, where `n` has ASCII digits `digits`,
+/// exactly
+pub(crate) fn cmp_abs_decimal(x: f64, digits: &str, place: i32) -> std::cmp::Ordering {
+    let digits = digits.trim_start_matches('0');
+    match (x == 0., digits.is_empty()) {
+        (true, true) => return std::cmp::Ordering::Equal,
+        (true, false) => return std::cmp::Ordering::Less,
+        (false, true) => return std::cmp::Ordering::Greater,
+        (false, false) => {}
+    }
+    let (x_digits, x_exp_sci) = expansion_digits(x, EXACT_PRECISION);
+    let exp_sci = place + digits.len() as i32 - 1;
+    x_exp_sci.cmp(&exp_sci).then_with(|| {
+        // same leading power of ten, so compare digit by digit, padding with zeros
+        let x_digits = x_digits.iter().map(|d| b'0' + d);
+        let n_digits = x_digits.len().max(digits.len());
+        x_digits
+            .chain(std::iter::repeat(b'0'))
+            .take(n_digits)
+            .cmp(digits.bytes().chain(std::iter::repeat(b'0')).take(n_digits))
+    })
+}
+
+/// Formats finite, nonzero `x` in engineering notation by rounding the exact decimal expansion
+/// of `x` per `rounding`
+pub(crate) fn format_eng_exact_rounded(x: f64, sf: usize, rounding: RoundingMode) -> String {
+    format_rounded(x < 0., &round_exact(x, sf, rounding))
+}
+
+/// Formats `rounded` in engineering notation with a leading `-` if `negative`
+pub(crate) fn format_rounded(negative: bool, rounded: &Rounded) -> String {
+    let digits: Vec<u8> = rounded.digits.iter().map(|d| b'0' + d).collect();
+    write_eng(negative, &digits, rounded.exp_sci)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_eng;
+
+    /// Formats `x` through [format_eng] with the fallback path forced on
+    fn format_fallback(x: f64, sf: Option<usize>) -> String {
+        force_fallback(true);
+        let formatted = format_eng(x, sf);
+        force_fallback(false);
+        formatted
+    }
+
+    #[test]
+    fn test_fallback_matches_fast_path() {
+        for x in [
+            2.,
+            std::f64::consts::PI,
+            -std::f64::consts::PI / 10.,
+            33.333,
+            666.66,
+            3.3333e3,
+            66.666e6,
+            -std::f64::consts::PI * 2e5,
+            1e3,
+            1e-6,
+            1e-4,
+            0.001,
+            0.010,
+            0.100,
+            1.,
+            10.,
+            6.022e-23,
+        ] {
+            for sf in 1..=7 {
+                assert_eq!(
+                    format_fallback(x, Some(sf)),
+                    format_eng(x, Some(sf)),
+                    "x: {x}, sf: {sf}"
+                );
+            }
+        }
+    }
+    #[test]
+    fn test_fast_and_guarded_match_exact() {
+        let mut values = vec![0.85, 2.5, 0.125, 1.5e-10, 9.9994e-6, 999.5, 1e300, 1e-300];
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..2_000 {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let x = f64::from_bits(state);
+            if x.is_finite() && x != 0. {
+                values.push(x);
+            }
+            // near-ties like 12.35e-7 and their neighbors
+            let tie: f64 = format!("{}5e{}", state % 1000, (state >> 20) as i32 % 40)
+                .parse()
+                .unwrap();
+            values.extend([tie.next_down(), tie, tie.next_up()]);
+        }
+        for x in values {
+            for sf in 1..=17 {
+                let exact = format_eng_exact(x, sf);
+                let mut buf = DigitBuf::new();
+                let exp_sci = digits_exact(x, sf, &mut buf);
+                assert_eq!(
+                    write_eng(x < 0., &buf.as_bytes()[..sf], exp_sci),
+                    exact,
+                    "stack x: {x:e}, sf: {sf}"
+                );
+                if let Some(exp_sci) = digits_fast(x, sf, &mut buf) {
+                    let fast = write_eng(x < 0., &buf.as_bytes()[..sf], exp_sci);
+                    assert_eq!(fast, exact, "fast x: {x:e}, sf: {sf}");
+                }
+                if let Some(exp_sci) = digits_guarded(x, sf, &mut buf) {
+                    let guarded = write_eng(x < 0., &buf.as_bytes()[..sf], exp_sci);
+                    assert_eq!(guarded, exact, "guarded x: {x:e}, sf: {sf}");
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_round_at_place() {
+        let table = [
+            (0., 0, "0"),
+            (2.5, 0, "3"),
+            (0.125, -2, "13"),
+            (0.0049, -2, "0"),
+            (0.005, -2, "1"),
+            (0.004, -2, "0"),
+            (1e-9, -2, "0"),
+            (9.96, -1, "100"),
+            (-1234.5, 2, "12"),
+            (99.5, 0, "100"),
+            (1.2e-5, -6, "12"),
+        ];
+        for (x, place, expected) in table {
+            assert_eq!(round_at_place(x, place), expected, "x: {x}, place: {place}");
+        }
+    }
+    #[test]
+    fn test_cmp_abs_decimal() {
+        use std::cmp::Ordering::*;
+        let table = [
+            (0.35, "35", -2, Less),
+            (0.375, "375", -3, Equal),
+            (-0.375, "375", -3, Equal),
+            (0.1, "1", -1, Greater),
+            (1250., "125", 1, Equal),
+            (1250., "0125", 1, Equal),
+            (1e3, "999", 0, Greater),
+            (0., "", 5, Equal),
+            (0., "1", -300, Less),
+            (5e-324, "0", 0, Greater),
+        ];
+        for (x, digits, place, expected) in table {
+            assert_eq!(cmp_abs_decimal(x, digits, place), expected, "x: {x:e}");
+        }
+    }
+    #[test]
+    fn test_fallback_zero() {
+        assert_eq!(format_fallback(0., None), String::from("0.00"));
+    }
+    #[test]
+    fn test_fallback_999p999() {
+        assert_eq!(format_fallback(999.999, None), String::from("1.00e3"));
+    }
+    #[test]
+    fn test_fallback_999p5() {
+        assert_eq!(format_fallback(999.5, None), String::from("1.00e3"));
+    }
+    #[test]
+    fn test_fallback_n999_950_4sf() {
+        assert_eq!(
+            format_fallback(-999_950., Some(4)),
+            String::from("-1.000e6")
+        );
+    }
+    #[test]
+    fn test_fallback_0p99999() {
+        assert_eq!(format_fallback(0.99999, None), String::from("1.00"));
+    }
+    #[test]
+    fn test_fallback_0p0009995() {
+        // exact binary value is just below the tie
+        assert_eq!(format_fallback(0.0009995, None), String::from("999e-6"));
+    }
+    #[test]
+    fn test_fallback_2pi_e5_1d() {
+        assert_eq!(
+            format_fallback(std::f64::consts::PI * 2e5, Some(1)),
+            String::from("600e3")
+        );
+    }
+    #[test]
+    fn test_fallback_subnormal() {
+        assert_eq!(format_fallback(5e-320, None), String::from("50.0e-321"));
+    }
+    #[test]
+    fn test_fallback_max() {
+        assert_eq!(
+            format_fallback(f64::MAX, Some(4)),
+            String::from("179.8e306")
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Module for auditing that formatted text is the correctly rounded representation of a value.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::error::MAX_SIG_FIGS;
+use crate::exact::{cmp_abs_decimal, round_at_place};
+use crate::format_eng;
+use crate::parse::{decimal_text, parse_eng_with_precision, ParseEngError};
+
+/// Power of ten of the last digit of the exact decimal expansion of the smallest subnormal, so
+/// no f64 has a nonzero digit below it
+const EXPANSION_END: i32 = -1074;
+
+/// Error returned by [verify_faithful]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FaithfulnessError {
+    /// Text is not a number
+    Unparsable(ParseEngError),
+    /// Text is not `x` rounded half away from zero to the precision the text shows; `expected`
+    /// is what [format_eng] gives at that many significant figures
+    NotClosest { found: String, expected: String },
+}
+
+impl fmt::Display for FaithfulnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FaithfulnessError::Unparsable(err) => write!(f, "cannot audit unparsable text: {err}"),
+            FaithfulnessError::NotClosest { found, expected } => {
+                write!(
+                    f,
+                    "{found:?} is not correctly rounded, expected {expected:?}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for FaithfulnessError {}
+
+/// Checks that `s` is `x` rounded half away from zero to the place of the last digit `s` shows,
+/// which makes `s` the closest number to `x` at that precision, with ties away from zero.  The
+/// value is compared rather than the text, so plain, scientific, and SI prefixed text from
+/// other formatters can be audited too.  Trailing zeros of a number without a decimal point may
+/// be padding, as in `"30e-3"` from [format_eng] at 1 significant figure, so `s` passes if it is
+/// correctly rounded at any of the precisions it could be showing.  Non-finite `x` must match
+/// [format_eng] exactly.
+///
+/// # Arguments
+/// - `x` - value that `s` claims to represent
+/// - `s` - formatted text to be audited
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{verify_faithful, FaithfulnessError};
+/// assert!(verify_faithful(0.010, "10.0e-3").is_ok());
+/// assert!(verify_faithful(2.346, "2.35").is_ok());
+/// assert_eq!(
+///     verify_faithful(2.346, "2.34"),
+///     Err(FaithfulnessError::NotClosest {
+///         found: String::from("2.34"),
+///         expected: String::from("2.35")
+///     })
+/// );
+/// ```
+pub fn verify_faithful(x: f64, s: &str) -> Result<(), FaithfulnessError> {
+    let found = s.trim();
+    if !x.is_finite() {
+        let expected = format_eng(x, None);
+        return match found == expected {
+            true => Ok(()),
+            false => Err(FaithfulnessError::NotClosest {
+                found: found.to_string(),
+                expected,
+            }),
+        };
+    }
+
+    let text = decimal_text(s).map_err(FaithfulnessError::Unparsable)?;
+    let n_padding = match text.has_point {
+        true => 0,
+        false => text.digits.len() - text.digits.trim_end_matches('0').len(),
+    };
+    let faithful = (0..=n_padding).any(|n_pad| {
+        let digits = &text.digits[..text.digits.len() - n_pad];
+        let place = text.place.saturating_add(n_pad as i32);
+        // zero matches either sign
+        let sign_matches = |is_zero: bool| is_zero || text.negative == (x < 0.);
+        if place < EXPANSION_END {
+            // rounding there leaves `x` unchanged, so the text must be exactly `x`
+            return cmp_abs_decimal(x, digits, place) == Ordering::Equal && sign_matches(x == 0.);
+        }
+        let expected = round_at_place(x, place);
+        expected == if digits.is_empty() { "0" } else { digits } && sign_matches(expected == "0")
+    });
+    if faithful {
+        return Ok(());
+    }
+    let (_, sf) = parse_eng_with_precision(s).map_err(FaithfulnessError::Unparsable)?;
+    Err(FaithfulnessError::NotClosest {
+        found: found.to_string(),
+        expected: format_eng(x, Some(sf.min(MAX_SIG_FIGS))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formatter_is_always_faithful() {
+        let mut values = vec![0., -0., 0.025, 0.85, 999.7, 2.5, 5e-324, f64::MAX];
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..1_000 {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let x = f64::from_bits(state);
+            if x.is_finite() {
+                values.push(x);
+            }
+        }
+        for x in values {
+            for sf in 1..=17 {
+                let formatted = format_eng(x, Some(sf));
+                assert_eq!(verify_faithful(x, &formatted), Ok(()), "x: {x:e}, sf: {sf}");
+            }
+        }
+    }
+    #[test]
+    fn test_other_notations() {
+        assert_eq!(verify_faithful(1.2345e-5, "1.23e-5"), Ok(()));
+        assert_eq!(verify_faithful(1.2345e-5, "0.0000123"), Ok(()));
+        assert_eq!(verify_faithful(1.2345e-5, "12.3 µ"), Ok(()));
+        assert_eq!(verify_faithful(-4700., "-4.7k"), Ok(()));
+        assert_eq!(verify_faithful(1e-9, "0.00"), Ok(()));
+    }
+    #[test]
+    fn test_truncated() {
+        assert_eq!(
+            verify_faithful(std::f64::consts::PI, "3.141"),
+            Err(FaithfulnessError::NotClosest {
+                found: String::from("3.141"),
+                expected: String::from("3.142")
+            })
+        );
+    }
+    #[test]
+    fn test_tie_to_even() {
+        assert_eq!(
+            verify_faithful(2.25, "2.2"),
+            Err(FaithfulnessError::NotClosest {
+                found: String::from("2.2"),
+                expected: String::from("2.3")
+            })
+        );
+    }
+    #[test]
+    fn test_double_rounded() {
+        // 0.85 is stored below the tie
+        assert_eq!(
+            verify_faithful(0.85, "0.9"),
+            Err(FaithfulnessError::NotClosest {
+                found: String::from("0.9"),
+                expected: String::from("800e-3")
+            })
+        );
+    }
+    #[test]
+    fn test_wrong_sign() {
+        assert_eq!(
+            verify_faithful(-2.5e-3, "2.50e-3"),
+            Err(FaithfulnessError::NotClosest {
+                found: String::from("2.50e-3"),
+                expected: String::from("-2.50e-3")
+            })
+        );
+    }
+    #[test]
+    fn test_wrong_exponent() {
+        assert_eq!(
+            verify_faithful(std::f64::consts::PI, "3.14e3"),
+            Err(FaithfulnessError::NotClosest {
+                found: String::from("3.14e3"),
+                expected: String::from("3.14")
+            })
+        );
+    }
+    #[test]
+    fn test_padding_is_not_precision() {
+        // 25e-3 shown with a padding zero would need to round to 30e-3
+        assert_eq!(verify_faithful(0.025, "30e-3"), Ok(()));
+        assert!(verify_faithful(0.025, "20e-3").is_err());
+        assert!(verify_faithful(0.025, "30.e-3").is_err());
+    }
+    #[test]
+    fn test_places_past_expansion() {
+        // exact expansions, written past the last digit of any f64
+        for x in [5e-324, 0.1, -f64::MAX] {
+            assert_eq!(verify_faithful(x, &format!("{x:.1200e}")), Ok(()), "{x:e}");
+        }
+        assert!(verify_faithful(0.1, &format!("{:.1200e}", 0.1_f32)).is_err());
+        // the place comes from the text, so must not size the work
+        assert_eq!(
+            verify_faithful(1., "1.0e-2147483640"),
+            Err(FaithfulnessError::NotClosest {
+                found: String::from("1.0e-2147483640"),
+                expected: String::from("1.0")
+            })
+        );
+        assert!(verify_faithful(1., "1.0e-100000000").is_err());
+        assert!(verify_faithful(1., "1000e2147483647").is_err());
+        assert!(verify_faithful(0., "0.0e-2147483640").is_ok());
+        let text = format!("1.{}1", "0".repeat(1000));
+        assert!(matches!(
+            verify_faithful(1., &text),
+            Err(FaithfulnessError::NotClosest { .. })
+        ));
+    }
+    #[test]
+    fn test_non_finite() {
+        assert_eq!(verify_faithful(f64::NAN, "NaN"), Ok(()));
+        assert!(verify_faithful(f64::INFINITY, "1e309").is_err());
+        assert!(matches!(
+            verify_faithful(1., "inf"),
+            Err(FaithfulnessError::Unparsable(_))
+        ));
+    }
+}
@@ -0,0 +1,256 @@
+//! Module for fitting values, with or without an uncertainty, into a fixed number of characters
+//! in [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use crate::format_eng;
+use crate::math::decade;
+use crate::truncate::truncated;
+
+/// Largest number of value digits shown by [format_eng_width] and [format_eng_uncertain_fit],
+/// enough to tell any two f64 apart
+const MAX_SF: usize = 17;
+
+/// Returns f64 as string in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation) with the most significant
+/// figures, up to `sf`, that fit in `max_chars` characters, or [TRUNCATED](crate::TRUNCATED) if
+/// even one does not fit, or an empty string if `max_chars` is 0.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - largest number of significant figures, defaults to 3
+/// - `max_chars` - maximum number of `char`s in the result
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_fit;
+/// assert_eq!(format_eng_fit(-3.14159e-3, Some(5), 7), "-3.1e-3");
+/// assert_eq!(format_eng_fit(-3.14159e-3, None, 4), "…");
+/// ```
+pub fn format_eng_fit(x: f64, sf: Option<usize>, max_chars: usize) -> String {
+    // rounded afresh at each number of significant figures, as truncating the text of `sf`
+    // would round twice
+    (1..=sf.unwrap_or(3))
+        .rev()
+        .map(|sf| format_eng(x, Some(sf)))
+        .find(|candidate| candidate.chars().count() <= max_chars)
+        .unwrap_or_else(|| String::from(truncated(max_chars)))
+}
+
+/// Returns f64 as string in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation) with the most significant
+/// figures, up to 17, that fit in `max_chars` characters counting the sign, mantissa, and
+/// exponent, e.g. for fixed-width table cells.  Values are rounded afresh at each number of
+/// significant figures, as by [format_eng], so a carry such as 999.96 becoming `"1.00e3"` is
+/// accounted for.  Returns [TRUNCATED](crate::TRUNCATED) if even 1 significant figure, or
+/// `"NaN"` or `"inf"`, does not fit, or an empty string if `max_chars` is 0.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `max_chars` - maximum number of `char`s in the result
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_width;
+/// assert_eq!(format_eng_width(std::f64::consts::PI, 8), "3.141593");
+/// assert_eq!(format_eng_width(-6.0221e-23, 8), "-60e-24");
+/// assert_eq!(format_eng_width(-6.0221e-23, 6), "…");
+/// ```
+pub fn format_eng_width(x: f64, max_chars: usize) -> String {
+    (1..=MAX_SF)
+        .rev()
+        .map(|sf| format_eng(x, Some(sf)))
+        .find(|candidate| candidate.chars().count() <= max_chars)
+        .unwrap_or_else(|| String::from(truncated(max_chars)))
+}
+
+/// Returns `x ± u` in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation), e.g. `"1.235e-3 ± 12e-6"`,
+/// giving up information in this order until the result fits in `max_chars` characters:
+/// 1. `x` to the place of the second significant figure of `u`, and `u` at 2 significant
+///    figures
+/// 2. `u` at 1 significant figure
+/// 3. one fewer significant figure of `x` at a time, down to 1
+/// 4. `x` alone, as [format_eng_fit] with the significant figures of step 1
+///
+/// The result never exceeds `max_chars`, and is [TRUNCATED](crate::TRUNCATED) if nothing fits,
+/// or an empty string if `max_chars` is 0.
+/// The sign of `u` is ignored, and a zero or non-finite `u` leaves `x` at 3 significant figures.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `u` - uncertainty of `x`
+/// - `max_chars` - maximum number of `char`s in the result
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_uncertain_fit;
+/// assert_eq!(format_eng_uncertain_fit(1.23456e-3, 1.2e-5, 20), "1.235e-3 ± 12e-6");
+/// assert_eq!(format_eng_uncertain_fit(1.23456e-3, 1.2e-5, 15), "1.23e-3 ± 10e-6");
+/// assert_eq!(format_eng_uncertain_fit(1.23456e-3, 1.2e-5, 7), "1.23e-3");
+/// ```
+pub fn format_eng_uncertain_fit(x: f64, u: f64, max_chars: usize) -> String {
+    let sf = value_sf(x, u);
+    uncertain_candidates(x, u, sf)
+        .find(|candidate| candidate.chars().count() <= max_chars)
+        .unwrap_or_else(|| format_eng_fit(x, Some(sf), max_chars))
+}
+
+/// Returns the number of significant figures of `x` reaching the place of the second
+/// significant figure of `u`
+fn value_sf(x: f64, u: f64) -> usize {
+    if x == 0. || u == 0. || !x.is_finite() || !u.is_finite() {
+        return 3;
+    }
+    (decade(x) - decade(u) + 2).clamp(1, MAX_SF as i32) as usize
+}
+
+/// Returns the `±` forms tried by [format_eng_uncertain_fit], most informative first
+fn uncertain_candidates(x: f64, u: f64, sf: usize) -> impl Iterator<Item = String> {
+    let u = u.abs();
+    [(sf, 2)]
+        .into_iter()
+        .chain((1..=sf).rev().map(|sf| (sf, 1)))
+        .map(move |(sf, sf_u)| {
+            format!(
+                "{} ± {}",
+                format_eng(x, Some(sf)),
+                format_eng(u, Some(sf_u))
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TRUNCATED;
+
+    #[test]
+    fn test_fit() {
+        assert_eq!(format_eng_fit(999.7, None, 6), String::from("1.00e3"));
+        assert_eq!(format_eng_fit(999.7, None, 4), String::from("1e3"));
+        assert_eq!(format_eng_fit(999.7, None, 2), String::from(TRUNCATED));
+        // 1.235e-3 at 4 significant figures must not be rounded again to 3
+        assert_eq!(
+            format_eng_fit(1.23456e-3, Some(4), 7),
+            String::from("1.23e-3")
+        );
+    }
+    #[test]
+    fn test_width() {
+        let values = [
+            std::f64::consts::PI,
+            -std::f64::consts::PI,
+            -6.0221e-23,
+            999.96,
+            -999.96,
+            9.9996e-10,
+            1e300,
+            -1.7976931348623157e308,
+            5e-324,
+            0.,
+            -0.,
+            123_456.,
+            f64::NAN,
+            f64::NEG_INFINITY,
+        ];
+        for x in values {
+            for max_chars in 4..=12 {
+                let formatted = format_eng_width(x, max_chars);
+                if formatted == TRUNCATED {
+                    assert!(format_eng(x, Some(1)).chars().count() > max_chars);
+                    continue;
+                }
+                assert!(formatted.chars().count() <= max_chars, "{formatted}");
+                // no more significant figures fit
+                let sf = (1..=MAX_SF)
+                    .rev()
+                    .find(|sf| format_eng(x, Some(*sf)) == formatted)
+                    .unwrap();
+                for more in sf + 1..=MAX_SF {
+                    assert!(
+                        format_eng(x, Some(more)).chars().count() > max_chars || !x.is_finite(),
+                        "{x:e} at {max_chars}: {formatted}"
+                    );
+                }
+            }
+        }
+        let expected = [
+            (-6.0221e-23, 12, "-60.2210e-24"),
+            (-6.0221e-23, 9, "-60.2e-24"),
+            (-6.0221e-23, 7, "-60e-24"),
+            (999.96, 6, "999.96"),
+            (999.96, 5, "1.0e3"),
+            (999.96, 4, "1e3"),
+            (-1.7976931348623157e308, 8, "-180e306"),
+            (-1.7976931348623157e308, 7, TRUNCATED),
+            (0., 4, "0.00"),
+            (-0., 4, "-0.0"),
+            (std::f64::consts::PI, 20, "3.1415926535897931"),
+            (f64::NAN, 3, "NaN"),
+            (f64::NEG_INFINITY, 3, TRUNCATED),
+        ];
+        for (x, max_chars, formatted) in expected {
+            assert_eq!(format_eng_width(x, max_chars), formatted);
+        }
+    }
+    #[test]
+    fn test_uncertain_budgets() {
+        let (x, u) = (-1234.56, 2.5);
+        let sf = value_sf(x, u);
+        let mut levels: Vec<String> = uncertain_candidates(x, u, sf).collect();
+        levels.extend((1..=sf).rev().map(|sf| format_eng(x, Some(sf))));
+        levels.push(String::from(TRUNCATED));
+
+        let mut last_level = 0;
+        for max_chars in (6..=20).rev() {
+            let formatted = format_eng_uncertain_fit(x, u, max_chars);
+            assert!(formatted.chars().count() <= max_chars, "{formatted}");
+            let level = levels.iter().position(|l| *l == formatted).unwrap();
+            // information is only ever lost as the budget shrinks
+            assert!(level >= last_level, "{max_chars}: {formatted}");
+            last_level = level;
+        }
+        let expected = [
+            (20, "-1.2346e3 ± 2.5"),
+            (15, "-1.2346e3 ± 2.5"),
+            (14, "-1.2346e3 ± 3"),
+            (12, "-1.235e3 ± 3"),
+            (11, "-1.23e3 ± 3"),
+            (10, "-1.2e3 ± 3"),
+            (8, "-1e3 ± 3"),
+            (7, "-1.23e3"),
+            (6, "-1.2e3"),
+            (4, "-1e3"),
+            (3, TRUNCATED),
+        ];
+        for (max_chars, formatted) in expected {
+            assert_eq!(
+                format_eng_uncertain_fit(x, u, max_chars),
+                String::from(formatted)
+            );
+        }
+    }
+    #[test]
+    fn test_uncertain_degenerate() {
+        assert_eq!(
+            format_eng_uncertain_fit(2.5, 0., 20),
+            String::from("2.50 ± 0.0")
+        );
+        assert_eq!(
+            format_eng_uncertain_fit(2.5, -0.25, 20),
+            String::from("2.50 ± 250e-3")
+        );
+    }
+    #[test]
+    fn test_zero_budget() {
+        for x in [999.7, -6.0221e-23, f64::NAN] {
+            assert_eq!(format_eng_fit(x, None, 1), String::from(TRUNCATED));
+            assert_eq!(format_eng_fit(x, None, 0), String::new());
+            assert_eq!(format_eng_width(x, 0), String::new());
+            assert_eq!(
+                format_eng_uncertain_fit(x, 1e-3, 1),
+                String::from(TRUNCATED)
+            );
+            assert_eq!(format_eng_uncertain_fit(x, 1e-3, 0), String::new());
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Module for formatting with a caller-chosen engineering exponent, so a column of values shares
+//! one scale.
+
+use std::fmt;
+
+use crate::error::check_sf;
+use crate::exact::{format_eng_checked, round_exact, RoundingMode};
+use crate::math::{eng_exponent, mod_floor_i32};
+
+/// Error returned by [format_eng_fixed]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FixedExpError {
+    /// Pinned exponent is not a multiple of 3
+    NotEngExponent(i32),
+}
+
+impl fmt::Display for FixedExpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixedExpError::NotEngExponent(exp) => {
+                write!(f, "exponent {exp} is not a multiple of 3")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FixedExpError {}
+
+/// Returns `x` at `sf` significant figures scaled to the pinned exponent `exp` rather than its
+/// own engineering exponent, e.g. `1.5` at `exp = -3` is `"1500e-3"` and `150e-6` is
+/// `"0.150e-3"`.  Any number of digits may end up left of the decimal point, and digits beyond
+/// the significant figures are filled with zeros.  Rounding matches
+/// [format_eng](crate::format_eng).  The `e{exp}` suffix is left off when `exp` is 0, zero keeps
+/// its sign, and NaN and infinities are formatted as by [format_eng](crate::format_eng).
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `exp` - exponent every value is scaled to, a multiple of 3
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{common_eng_exp, format_eng_fixed};
+/// let currents = [150e-6, 12e-3, 450e-3];
+/// let exp = common_eng_exp(&currents);
+/// let column: Vec<String> = currents
+///     .iter()
+///     .map(|x| format_eng_fixed(*x, exp, None).unwrap())
+///     .collect();
+/// assert_eq!(column, ["0.150e-3", "12.0e-3", "450e-3"]);
+/// assert_eq!(format_eng_fixed(1.5, -3, None).unwrap(), "1500e-3");
+/// assert!(format_eng_fixed(1., -2, None).is_err());
+/// ```
+pub fn format_eng_fixed(x: f64, exp: i32, sf: Option<usize>) -> Result<String, FixedExpError> {
+    if mod_floor_i32(exp, 3) != 0 {
+        return Err(FixedExpError::NotEngExponent(exp));
+    }
+    let sf = check_sf(sf, x).unwrap_or_else(|err| panic!("{err}"));
+    if !x.is_finite() {
+        return Ok(format_eng_checked(x, sf));
+    }
+    let (digits, exp_sci) = if x == 0. {
+        (vec![0; sf], exp)
+    } else {
+        let rounded = round_exact(x, sf, RoundingMode::HalfAwayFromZero);
+        (rounded.digits, rounded.exp_sci)
+    };
+    // power of ten of each digit relative to `exp`, from the first shown to the last
+    let place_first = i64::from(exp_sci) - i64::from(exp);
+    let place_last = (place_first - sf as i64 + 1).min(0);
+    let mut formatted = String::with_capacity((place_first.max(0) - place_last) as usize + 16);
+    if x.is_sign_negative() {
+        formatted.push('-');
+    }
+    for place in (place_last..=place_first.max(0)).rev() {
+        if place == -1 {
+            formatted.push('.');
+        }
+        let i = place_first - place;
+        let digit = match usize::try_from(i) {
+            Ok(i) => digits.get(i).copied().unwrap_or(0),
+            Err(_) => 0,
+        };
+        formatted.push(char::from(b'0' + digit));
+    }
+    if exp != 0 {
+        formatted.push_str(&format!("e{exp}"));
+    }
+    Ok(formatted)
+}
+
+/// Returns the engineering exponent of the largest-magnitude finite element of `values`, to be
+/// passed to [format_eng_fixed] for every element.  Returns 0 if there is no finite, nonzero
+/// element.
+///
+/// # Arguments
+/// - `values` - values that will share one exponent
+///
+/// # Examples
+/// ```
+/// use eng_fmt::common_eng_exp;
+/// assert_eq!(common_eng_exp(&[150e-6, -12e-3, 1.5e-3]), -3);
+/// assert_eq!(common_eng_exp(&[f64::NAN, 2.2e6, -3e9]), 9);
+/// assert_eq!(common_eng_exp(&[]), 0);
+/// ```
+pub fn common_eng_exp(values: &[f64]) -> i32 {
+    let largest = values
+        .iter()
+        .copied()
+        .filter(|x| x.is_finite())
+        .map(f64::abs)
+        .fold(0., f64::max);
+    eng_exponent(largest, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_eng;
+
+    #[test]
+    fn test_format_eng_fixed() {
+        assert_eq!(
+            format_eng_fixed(150e-6, -3, None),
+            Ok(String::from("0.150e-3"))
+        );
+        assert_eq!(
+            format_eng_fixed(12e-3, -3, None),
+            Ok(String::from("12.0e-3"))
+        );
+        assert_eq!(
+            format_eng_fixed(-1.5, -3, None),
+            Ok(String::from("-1500e-3"))
+        );
+        assert_eq!(
+            format_eng_fixed(1.5, -3, Some(5)),
+            Ok(String::from("1500.0e-3"))
+        );
+        assert_eq!(
+            format_eng_fixed(1e-9, -3, Some(2)),
+            Ok(String::from("0.0000010e-3"))
+        );
+        assert_eq!(
+            format_eng_fixed(123456., 0, Some(2)),
+            Ok(String::from("120000"))
+        );
+        assert_eq!(
+            format_eng_fixed(0.5, 3, Some(1)),
+            Ok(String::from("0.0005e3"))
+        );
+    }
+    #[test]
+    fn test_matches_format_eng_at_own_exponent() {
+        for x in [1., -9.995e-4, 999.95, 6.02214076e23, 5e-324, f64::MAX] {
+            for sf in 1..=8 {
+                let own = format_eng(x, Some(sf));
+                let exp = own
+                    .split_once('e')
+                    .map_or(0, |(_, exp)| exp.parse().unwrap());
+                assert_eq!(
+                    format_eng_fixed(x, exp, Some(sf)),
+                    Ok(own),
+                    "x: {x:e}, sf: {sf}"
+                );
+            }
+        }
+    }
+    #[test]
+    fn test_rounding_carry() {
+        // carry adds a digit left of the decimal point
+        assert_eq!(
+            format_eng_fixed(999.96, 0, Some(4)),
+            Ok(String::from("1000"))
+        );
+        assert_eq!(
+            format_eng_fixed(9.996e-3, -6, Some(3)),
+            Ok(String::from("10000e-6"))
+        );
+    }
+    #[test]
+    fn test_special_values() {
+        assert_eq!(format_eng_fixed(0., -3, None), Ok(String::from("0.00e-3")));
+        assert_eq!(format_eng_fixed(-0., 0, Some(1)), Ok(String::from("-0")));
+        assert_eq!(
+            format_eng_fixed(f64::NAN, -3, None),
+            Ok(String::from("NaN"))
+        );
+        assert_eq!(
+            format_eng_fixed(f64::NEG_INFINITY, 3, None),
+            Ok(String::from("-inf"))
+        );
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            format_eng_fixed(1., 1, None),
+            Err(FixedExpError::NotEngExponent(1))
+        );
+        assert_eq!(
+            format_eng_fixed(1., -4, None),
+            Err(FixedExpError::NotEngExponent(-4))
+        );
+        assert_eq!(
+            format_eng_fixed(1., i32::MIN, None),
+            Err(FixedExpError::NotEngExponent(i32::MIN))
+        );
+        assert_eq!(
+            FixedExpError::NotEngExponent(-4).to_string(),
+            "exponent -4 is not a multiple of 3"
+        );
+    }
+    #[test]
+    #[should_panic(expected = "0 significant figures")]
+    fn test_zero_sf_panics() {
+        let _ = format_eng_fixed(1., 0, Some(0));
+    }
+    #[test]
+    fn test_common_eng_exp() {
+        assert_eq!(common_eng_exp(&[999.9, 1e-9]), 0);
+        assert_eq!(common_eng_exp(&[-1e3, 999.]), 3);
+        assert_eq!(common_eng_exp(&[0., -0., f64::INFINITY]), 0);
+        assert_eq!(common_eng_exp(&[5e-324]), -324);
+    }
+}
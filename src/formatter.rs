@@ -0,0 +1,647 @@
+//! Module containing [EngFormatter], a reusable set of options for [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) output.
+
+use crate::error::{check_sf, EngFmtError, MAX_SIG_FIGS};
+use crate::exact::{format_eng_checked, format_eng_exact_rounded, RoundingMode};
+use crate::group::push_grouped;
+
+/// Character separating the integer and fractional digits of a mantissa
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DecimalMark {
+    /// `.`, as in `"3.14"`
+    #[default]
+    Point,
+    /// `,`, as in `"3,14"`, common in continental Europe
+    Comma,
+}
+
+impl DecimalMark {
+    /// Returns the character of this mark
+    pub const fn as_char(self) -> char {
+        match self {
+            DecimalMark::Point => '.',
+            DecimalMark::Comma => ',',
+        }
+    }
+}
+
+/// How the exponent of a mantissa is written
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ExpStyle {
+    /// `e` notation, e.g. `"314e-3"`, with the character set by
+    /// [exp_char](EngFormatter::exp_char)
+    #[default]
+    E,
+    /// Unicode multiplication sign and superscript digits, e.g. `"314×10⁻³"`, for plot labels
+    /// and documents
+    Times10Super,
+    /// ASCII-only power of ten, e.g. `"314x10^-3"`
+    Times10Caret,
+}
+
+/// Returns the Unicode superscript of ASCII digit or sign `c`
+fn superscript(c: char) -> char {
+    match c {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' => '⁻',
+        c => c,
+    }
+}
+
+/// Reusable formatter for [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation) built from chained options,
+/// and cheap to copy into application config.  [EngFormatter::new] gives the output of
+/// [format_eng](crate::format_eng) at 3 significant figures, which is itself a call into a
+/// default formatter.  Apart from the [RoundingMode] and dropping trailing zeros, options only
+/// change the punctuation around the digits, never the digits: NaN stays `"NaN"`, and infinities
+/// only take the leading `+`.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::EngFormatter;
+/// let fmt = EngFormatter::new()
+///     .sig_figs(4)
+///     .exp_char('E')
+///     .explicit_plus(true)
+///     .space_before_exp(false);
+/// assert_eq!(fmt.format(1234.5), "1.235E+3");
+/// assert_eq!(fmt.format(-1.5e-6), "-1.500E-6");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EngFormatter {
+    sf: usize,
+    exp_char: char,
+    explicit_plus: bool,
+    mantissa_plus: bool,
+    space_before_exp: bool,
+    always_exp: bool,
+    rounding: RoundingMode,
+    separator: Option<char>,
+    group_fraction: bool,
+    decimal_mark: DecimalMark,
+    exp_style: ExpStyle,
+    trim_trailing_zeros: bool,
+}
+
+impl Default for EngFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EngFormatter {
+    /// Returns a formatter matching [format_eng](crate::format_eng) at 3 significant figures
+    pub const fn new() -> Self {
+        Self {
+            sf: 3,
+            exp_char: 'e',
+            explicit_plus: false,
+            mantissa_plus: false,
+            space_before_exp: false,
+            always_exp: false,
+            rounding: RoundingMode::HalfAwayFromZero,
+            separator: None,
+            group_fraction: false,
+            decimal_mark: DecimalMark::Point,
+            exp_style: ExpStyle::E,
+            trim_trailing_zeros: false,
+        }
+    }
+
+    /// Sets the number of significant figures, which [format](EngFormatter::format) requires to
+    /// be from 1 to [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).  Use
+    /// [try_sig_figs](EngFormatter::try_sig_figs) to check it here instead.
+    pub const fn sig_figs(mut self, sf: usize) -> Self {
+        self.sf = sf;
+        self
+    }
+
+    /// Sets the number of significant figures, or returns [EngFmtError::SigFigsOutOfRange] if
+    /// it is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS), e.g. for a value read from
+    /// config
+    pub const fn try_sig_figs(self, sf: usize) -> Result<Self, EngFmtError> {
+        if sf == 0 || sf > MAX_SIG_FIGS {
+            return Err(EngFmtError::SigFigsOutOfRange { sf });
+        }
+        Ok(self.sig_figs(sf))
+    }
+
+    /// Sets the character introducing the exponent, e.g. `'E'` for `"1.00E3"`
+    pub const fn exp_char(mut self, exp_char: char) -> Self {
+        self.exp_char = exp_char;
+        self
+    }
+
+    /// Sets whether positive exponents get a `+`, e.g. `"1.00e+3"`
+    pub const fn explicit_plus(mut self, explicit_plus: bool) -> Self {
+        self.explicit_plus = explicit_plus;
+        self
+    }
+
+    /// Sets whether positive values, including positive zero, get a leading `+`, e.g. `"+1.00"`
+    pub const fn mantissa_plus(mut self, mantissa_plus: bool) -> Self {
+        self.mantissa_plus = mantissa_plus;
+        self
+    }
+
+    /// Sets whether a space separates the mantissa from the exponent, e.g. `"1.00 e3"`
+    pub const fn space_before_exp(mut self, space_before_exp: bool) -> Self {
+        self.space_before_exp = space_before_exp;
+        self
+    }
+
+    /// Sets whether values with exponent 0, including zero, still print it, e.g. `"1.00e0"`
+    pub const fn always_exp(mut self, always_exp: bool) -> Self {
+        self.always_exp = always_exp;
+        self
+    }
+
+    /// Sets how digits past the last significant figure are rounded away, e.g.
+    /// [RoundingMode::HalfEven] for `"120e-3"` from 0.125 at 2 significant figures
+    pub const fn rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Sets the character, if any, inserted every three digits left of the mantissa's decimal
+    /// point, e.g. `Some(',')`, as by [group_digits](crate::group_digits).  Use
+    /// [try_separator](EngFormatter::try_separator) to check it against the decimal mark here.
+    pub const fn separator(mut self, separator: Option<char>) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the digit-group [separator](EngFormatter::separator), or returns
+    /// [EngFmtError::AmbiguousSeparator] if it is the character of the current
+    /// [decimal_mark](EngFormatter::decimal_mark)
+    pub const fn try_separator(self, separator: Option<char>) -> Result<Self, EngFmtError> {
+        match Self::check_separator(separator, self.decimal_mark) {
+            Ok(()) => Ok(self.separator(separator)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Sets whether the [separator](EngFormatter::separator) is also inserted every three
+    /// digits right of the decimal point, e.g. `"1.234 567e6"`
+    pub const fn group_fraction(mut self, group_fraction: bool) -> Self {
+        self.group_fraction = group_fraction;
+        self
+    }
+
+    /// Sets the character between the integer and fractional digits of the mantissa, e.g.
+    /// [DecimalMark::Comma] for `"10,0e-3"`.  A [separator](EngFormatter::separator) equal to
+    /// it makes [try_format](EngFormatter::try_format) return
+    /// [EngFmtError::AmbiguousSeparator], so use e.g. `'.'` or `'\u{2009}'` (thin space) to
+    /// group digits with a comma mark.
+    pub const fn decimal_mark(mut self, decimal_mark: DecimalMark) -> Self {
+        self.decimal_mark = decimal_mark;
+        self
+    }
+
+    /// Sets the [decimal_mark](EngFormatter::decimal_mark), or returns
+    /// [EngFmtError::AmbiguousSeparator] if it is the character of the current
+    /// [separator](EngFormatter::separator)
+    pub const fn try_decimal_mark(self, decimal_mark: DecimalMark) -> Result<Self, EngFmtError> {
+        match Self::check_separator(self.separator, decimal_mark) {
+            Ok(()) => Ok(self.decimal_mark(decimal_mark)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns [EngFmtError::AmbiguousSeparator] if `separator` is the character of
+    /// `decimal_mark`
+    const fn check_separator(
+        separator: Option<char>,
+        decimal_mark: DecimalMark,
+    ) -> Result<(), EngFmtError> {
+        match separator {
+            Some(separator) if separator == decimal_mark.as_char() => {
+                Err(EngFmtError::AmbiguousSeparator { separator })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Sets how the exponent is written, e.g. [ExpStyle::Times10Super] for `"3.14×10³"`.
+    /// Exponent 0 is left off in every style unless [always_exp](EngFormatter::always_exp) is
+    /// set.
+    pub const fn exp_style(mut self, exp_style: ExpStyle) -> Self {
+        self.exp_style = exp_style;
+        self
+    }
+
+    /// Sets whether zeros at the end of the mantissa's fraction are dropped, along with a
+    /// decimal point left with nothing after it, e.g. `"1.5e3"` rather than `"1.50e3"` and `"2"`
+    /// rather than `"2.00"`.  The value is still rounded to the set significant figures, and
+    /// zeros left of the decimal point stay, e.g. `"100e-3"`.
+    pub const fn trim_trailing_zeros(mut self, trim_trailing_zeros: bool) -> Self {
+        self.trim_trailing_zeros = trim_trailing_zeros;
+        self
+    }
+
+    /// Returns the number of significant figures
+    pub const fn get_sig_figs(&self) -> usize {
+        self.sf
+    }
+
+    /// Returns `x` formatted with these options, or an error if the number of significant
+    /// figures is out of range
+    ///
+    /// # Arguments
+    /// - `x` - value to be formatted
+    pub fn try_format(&self, x: f64) -> Result<String, EngFmtError> {
+        let sf = check_sf(Some(self.sf), x)?;
+        Self::check_separator(self.separator, self.decimal_mark)?;
+        let formatted = match self.rounding {
+            RoundingMode::HalfAwayFromZero => format_eng_checked(x, sf),
+            // the fast paths only round half away from zero
+            _ if x.is_finite() && x != 0. => format_eng_exact_rounded(x, sf, self.rounding),
+            _ => format_eng_checked(x, sf),
+        };
+        if *self == Self::new().sig_figs(sf) {
+            return Ok(formatted);
+        }
+        if x.is_nan() {
+            return Ok(formatted);
+        }
+
+        let (mantissa, exp) = match formatted.split_once('e') {
+            Some((mantissa, exp)) => (mantissa, Some(exp)),
+            None => (formatted.as_str(), None),
+        };
+        let mantissa = match self.trim_trailing_zeros && mantissa.contains('.') {
+            true => mantissa.trim_end_matches('0').trim_end_matches('.'),
+            false => mantissa,
+        };
+        let mut styled = String::with_capacity(formatted.len() + 4);
+        if self.mantissa_plus && !x.is_sign_negative() {
+            styled.push('+');
+        }
+        let decimal_mark = self.decimal_mark.as_char();
+        match self.separator {
+            Some(separator) => push_grouped(
+                &mut styled,
+                mantissa,
+                separator,
+                self.group_fraction,
+                decimal_mark,
+            ),
+            None => styled.extend(mantissa.chars().map(|c| match c {
+                '.' => decimal_mark,
+                c => c,
+            })),
+        }
+        let exp = match exp {
+            Some(exp) => exp,
+            None if self.always_exp && x.is_finite() => "0",
+            None => return Ok(styled),
+        };
+        if self.space_before_exp {
+            styled.push(' ');
+        }
+        let plus = self.explicit_plus && !exp.starts_with('-') && exp != "0";
+        match self.exp_style {
+            ExpStyle::E => {
+                styled.push(self.exp_char);
+                if plus {
+                    styled.push('+');
+                }
+                styled.push_str(exp);
+            }
+            ExpStyle::Times10Super => {
+                styled.push_str("×10");
+                if plus {
+                    styled.push('⁺');
+                }
+                styled.extend(exp.chars().map(superscript));
+            }
+            ExpStyle::Times10Caret => {
+                styled.push_str("x10^");
+                if plus {
+                    styled.push('+');
+                }
+                styled.push_str(exp);
+            }
+        }
+        Ok(styled)
+    }
+
+    /// Returns `x` formatted with these options
+    ///
+    /// # Arguments
+    /// - `x` - value to be formatted
+    ///
+    /// # Panics
+    /// If the number of significant figures is 0 or more than
+    /// [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+    pub fn format(&self, x: f64) -> String {
+        self.try_format(x).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_eng;
+
+    #[test]
+    fn test_default_matches_format_eng() {
+        for x in [
+            0.,
+            -0.,
+            1e3,
+            -12.345e-9,
+            999.96,
+            f64::NAN,
+            f64::NEG_INFINITY,
+        ] {
+            assert_eq!(EngFormatter::new().format(x), format_eng(x, None));
+            assert_eq!(EngFormatter::default().format(x), format_eng(x, None));
+        }
+    }
+    #[test]
+    fn test_sig_figs() {
+        let fmt = EngFormatter::new().sig_figs(5);
+        assert_eq!(fmt.format(std::f64::consts::PI), String::from("3.1416"));
+        assert_eq!(fmt.get_sig_figs(), 5);
+        assert_eq!(
+            EngFormatter::new().sig_figs(0).try_format(2.),
+            Err(EngFmtError::SigFigsZero { value: 2. })
+        );
+    }
+    #[test]
+    fn test_try_sig_figs() {
+        let fmt = EngFormatter::new().try_sig_figs(5).unwrap();
+        assert_eq!(fmt, EngFormatter::new().sig_figs(5));
+        assert_eq!(
+            EngFormatter::new()
+                .try_sig_figs(MAX_SIG_FIGS)
+                .map(|fmt| fmt.get_sig_figs()),
+            Ok(MAX_SIG_FIGS)
+        );
+        // the error comes from the setter, before anything is formatted
+        assert_eq!(
+            EngFormatter::new().try_sig_figs(0),
+            Err(EngFmtError::SigFigsOutOfRange { sf: 0 })
+        );
+        assert_eq!(
+            EngFormatter::new().try_sig_figs(MAX_SIG_FIGS + 1),
+            Err(EngFmtError::SigFigsOutOfRange {
+                sf: MAX_SIG_FIGS + 1
+            })
+        );
+        assert_eq!(
+            EngFmtError::SigFigsOutOfRange { sf: 0 }.to_string(),
+            "number of significant figures must be from 1 to 767, not 0"
+        );
+    }
+    #[test]
+    fn test_exp_char() {
+        let fmt = EngFormatter::new().exp_char('E');
+        assert_eq!(fmt.format(1e3), String::from("1.00E3"));
+        assert_eq!(fmt.format(-2.5e-6), String::from("-2.50E-6"));
+        assert_eq!(fmt.format(1.), String::from("1.00"));
+    }
+    #[test]
+    fn test_explicit_plus() {
+        let fmt = EngFormatter::new().explicit_plus(true);
+        assert_eq!(fmt.format(1e3), String::from("1.00e+3"));
+        assert_eq!(fmt.format(1e-3), String::from("1.00e-3"));
+        assert_eq!(fmt.format(1.), String::from("1.00"));
+    }
+    #[test]
+    fn test_mantissa_plus() {
+        let fmt = EngFormatter::new().mantissa_plus(true);
+        assert_eq!(fmt.format(1e3), String::from("+1.00e3"));
+        assert_eq!(fmt.format(-1e3), String::from("-1.00e3"));
+        assert_eq!(fmt.format(0.), String::from("+0.00"));
+        assert_eq!(fmt.format(-0.), String::from("-0.00"));
+        assert_eq!(fmt.format(f64::INFINITY), String::from("+inf"));
+        assert_eq!(fmt.format(f64::NAN), String::from("NaN"));
+    }
+    #[test]
+    fn test_space_before_exp() {
+        let fmt = EngFormatter::new().space_before_exp(true);
+        assert_eq!(fmt.format(4.7e3), String::from("4.70 e3"));
+        assert_eq!(fmt.format(4.7), String::from("4.70"));
+    }
+    #[test]
+    fn test_always_exp() {
+        let fmt = EngFormatter::new().always_exp(true);
+        assert_eq!(fmt.format(4.7), String::from("4.70e0"));
+        assert_eq!(fmt.format(0.), String::from("0.00e0"));
+        assert_eq!(fmt.format(4.7e3), String::from("4.70e3"));
+        assert_eq!(fmt.format(f64::INFINITY), String::from("inf"));
+    }
+    #[test]
+    fn test_separator() {
+        let fmt = EngFormatter::new().sig_figs(7).separator(Some(' '));
+        assert_eq!(fmt.format(-1_234_567.), String::from("-1.234567e6"));
+        let fmt = fmt.group_fraction(true);
+        assert_eq!(fmt.format(-1_234_567.), String::from("-1.234 567e6"));
+        assert_eq!(
+            fmt.exp_char('E').explicit_plus(true).format(1.234_567e-3),
+            String::from("1.234 567E-3")
+        );
+        assert_eq!(fmt.format(123_456.789), String::from("123.456 8e3"));
+        // no grouping needed
+        assert_eq!(fmt.sig_figs(3).format(-12.3e6), String::from("-12.3e6"));
+        assert_eq!(fmt.format(f64::NAN), String::from("NaN"));
+        // only grouping the fraction needs the flag
+        assert_eq!(
+            EngFormatter::new().group_fraction(true).format(1.),
+            String::from("1.00")
+        );
+    }
+    #[test]
+    fn test_trim_trailing_zeros() {
+        let fmt = EngFormatter::new().trim_trailing_zeros(true);
+        // the cases of the `format_eng` tests in lib.rs, untrimmed then trimmed
+        let table = [
+            (2., 3, "2.00", "2"),
+            (std::f64::consts::PI / 10., 3, "314e-3", "314e-3"),
+            (std::f64::consts::PI / 5., 4, "628.3e-3", "628.3e-3"),
+            (-std::f64::consts::PI / 10., 3, "-314e-3", "-314e-3"),
+            (std::f64::consts::PI, 3, "3.14", "3.14"),
+            (33.333, 7, "33.33300", "33.333"),
+            (66.666, 3, "66.7", "66.7"),
+            (333.33, 3, "333", "333"),
+            (3.3333e3, 3, "3.33e3", "3.33e3"),
+            (33.333e6, 3, "33.3e6", "33.3e6"),
+            (std::f64::consts::PI * 2., 5, "6.2832", "6.2832"),
+            (-std::f64::consts::PI * 2., 2, "-6.3", "-6.3"),
+            (std::f64::consts::PI * 2e5, 1, "600e3", "600e3"),
+            (0., 5, "0.0000", "0"),
+            (-0., 3, "-0.00", "-0"),
+            (0.010, 3, "10.0e-3", "10e-3"),
+            (0.1, 3, "100e-3", "100e-3"),
+            (1.5e3, 3, "1.50e3", "1.5e3"),
+            (999.96, 3, "1.00e3", "1e3"),
+            (-1.2e-6, 4, "-1.200e-6", "-1.2e-6"),
+            (1.0001, 4, "1.000", "1"),
+            (120., 2, "120", "120"),
+            (f64::NAN, 3, "NaN", "NaN"),
+            (f64::NEG_INFINITY, 3, "-inf", "-inf"),
+        ];
+        for (x, sf, untrimmed, trimmed) in table {
+            assert_eq!(format_eng(x, Some(sf)), untrimmed);
+            assert_eq!(fmt.sig_figs(sf).format(x), trimmed);
+        }
+        // trims before the other options are applied
+        assert_eq!(
+            fmt.decimal_mark(DecimalMark::Comma)
+                .mantissa_plus(true)
+                .always_exp(true)
+                .format(2.5),
+            String::from("+2,5e0")
+        );
+        assert_eq!(
+            fmt.sig_figs(8)
+                .separator(Some(' '))
+                .group_fraction(true)
+                .format(1.234_5e6),
+            String::from("1.234 5e6")
+        );
+        assert_eq!(
+            fmt.sig_figs(10)
+                .separator(Some(' '))
+                .group_fraction(true)
+                .format(1_234.567_8),
+            String::from("1.234 567 8e3")
+        );
+    }
+    #[test]
+    fn test_decimal_mark() {
+        let fmt = EngFormatter::new().decimal_mark(DecimalMark::Comma);
+        assert_eq!(fmt.format(std::f64::consts::PI), String::from("3,14"));
+        assert_eq!(fmt.format(0.01), String::from("10,0e-3"));
+        assert_eq!(fmt.format(0.), String::from("0,00"));
+        assert_eq!(fmt.format(-0.), String::from("-0,00"));
+        assert_eq!(fmt.format(-4.7e-6), String::from("-4,70e-6"));
+        // no fractional digits
+        assert_eq!(fmt.format(123e6), String::from("123e6"));
+        assert_eq!(fmt.sig_figs(1).format(-2.), String::from("-2"));
+        assert_eq!(fmt.format(f64::NEG_INFINITY), String::from("-inf"));
+        assert_eq!(
+            fmt.exp_char('E').explicit_plus(true).format(1.5e3),
+            String::from("1,50E+3")
+        );
+    }
+    #[test]
+    fn test_decimal_mark_with_separator() {
+        let fmt = EngFormatter::new()
+            .sig_figs(7)
+            .decimal_mark(DecimalMark::Comma)
+            .group_fraction(true);
+        assert_eq!(
+            fmt.separator(Some('\u{2009}')).format(-1_234_567.),
+            String::from("-1,234\u{2009}567e6")
+        );
+        assert_eq!(
+            fmt.separator(Some('.')).format(1.234_567e-3),
+            String::from("1,234.567e-3")
+        );
+        assert_eq!(
+            fmt.separator(Some(',')).try_format(1.),
+            Err(EngFmtError::AmbiguousSeparator { separator: ',' })
+        );
+        assert_eq!(
+            EngFormatter::new().separator(Some('.')).try_format(1.),
+            Err(EngFmtError::AmbiguousSeparator { separator: '.' })
+        );
+        assert_eq!(
+            EngFmtError::AmbiguousSeparator { separator: ',' }.to_string(),
+            "digit separator ',' is also the decimal mark"
+        );
+    }
+    #[test]
+    fn test_try_separator_and_decimal_mark() {
+        let comma = EngFormatter::new().decimal_mark(DecimalMark::Comma);
+        assert_eq!(
+            comma.try_separator(Some('.')),
+            Ok(comma.separator(Some('.')))
+        );
+        assert_eq!(comma.try_separator(None), Ok(comma));
+        // the error comes from whichever setter makes the pair ambiguous
+        assert_eq!(
+            comma.try_separator(Some(',')),
+            Err(EngFmtError::AmbiguousSeparator { separator: ',' })
+        );
+        assert_eq!(
+            EngFormatter::new().try_separator(Some('.')),
+            Err(EngFmtError::AmbiguousSeparator { separator: '.' })
+        );
+        let grouped = EngFormatter::new().separator(Some(','));
+        assert_eq!(
+            grouped.try_decimal_mark(DecimalMark::Comma),
+            Err(EngFmtError::AmbiguousSeparator { separator: ',' })
+        );
+        assert_eq!(grouped.try_decimal_mark(DecimalMark::Point), Ok(grouped));
+    }
+    #[test]
+    fn test_exp_style() {
+        let sup = EngFormatter::new().exp_style(ExpStyle::Times10Super);
+        assert_eq!(sup.format(3.14159e3), String::from("3.14×10³"));
+        assert_eq!(sup.format(0.314), String::from("314×10⁻³"));
+        assert_eq!(sup.format(-6.022e-23), String::from("-60.2×10⁻²⁴"));
+        assert_eq!(sup.format(6.022e23), String::from("602×10²¹"));
+        assert_eq!(sup.format(1.5e24), String::from("1.50×10²⁴"));
+        assert_eq!(sup.format(5e-324), String::from("4.94×10⁻³²⁴"));
+        assert_eq!(sup.format(std::f64::consts::PI), String::from("3.14"));
+        assert_eq!(sup.format(0.), String::from("0.00"));
+        assert_eq!(
+            sup.explicit_plus(true).format(1e3),
+            String::from("1.00×10⁺³")
+        );
+        assert_eq!(sup.always_exp(true).format(1.), String::from("1.00×10⁰"));
+        assert_eq!(
+            sup.space_before_exp(true).format(1e-3),
+            String::from("1.00 ×10⁻³")
+        );
+        // U+00D7 MULTIPLICATION SIGN, U+207B SUPERSCRIPT MINUS, U+00B3 SUPERSCRIPT THREE
+        assert_eq!(
+            sup.format(-1e-3).as_bytes(),
+            "-1.00\u{d7}10\u{207b}\u{b3}".as_bytes()
+        );
+
+        let caret = EngFormatter::new().exp_style(ExpStyle::Times10Caret);
+        assert_eq!(caret.format(3.14159e3), String::from("3.14x10^3"));
+        assert_eq!(caret.format(-0.314), String::from("-314x10^-3"));
+        assert_eq!(caret.format(1.5e-24), String::from("1.50x10^-24"));
+        assert_eq!(caret.format(std::f64::consts::PI), String::from("3.14"));
+        assert_eq!(caret.format(f64::INFINITY), String::from("inf"));
+        assert!(caret.format(1.2e24).is_ascii());
+    }
+    #[test]
+    fn test_combinations() {
+        let fortran = EngFormatter::new()
+            .sig_figs(4)
+            .exp_char('E')
+            .explicit_plus(true)
+            .always_exp(true);
+        assert_eq!(fortran.format(999.96), String::from("1.000E+3"));
+        assert_eq!(fortran.format(12.5), String::from("12.50E0"));
+        assert_eq!(fortran.format(-3e-9), String::from("-3.000E-9"));
+
+        let signed = EngFormatter::new()
+            .mantissa_plus(true)
+            .explicit_plus(true)
+            .space_before_exp(true);
+        assert_eq!(signed.format(6.022e23), String::from("+602 e+21"));
+        assert_eq!(signed.format(-6.022e-23), String::from("-60.2 e-24"));
+
+        // copied into config and reused
+        let config = [signed; 2];
+        assert_eq!(config[1].format(1.), String::from("+1.00"));
+    }
+}
@@ -0,0 +1,113 @@
+//! Module for grouping the digits of formatted numbers in threes, e.g. `"123,456.789"` or
+//! `"1.234 567e6"`.
+
+/// Returns formatted number `text`, e.g. from [format_eng](crate::format_eng) or
+/// [format_eng_fixed](crate::format_eng_fixed), with `separator` inserted every three digits
+/// left of the decimal point, counting from the point, and also right of it if
+/// `group_fraction`.  Only the mantissa is grouped, never a sign or exponent, and text without
+/// a leading number such as `"NaN"` is returned unchanged.
+/// [EngFormatter::separator](crate::EngFormatter::separator) does the same within a formatter.
+///
+/// # Arguments
+/// - `text` - formatted number
+/// - `separator` - character inserted between groups, e.g. `','`, `' '`, or `'\u{2009}'`
+/// - `group_fraction` - whether digits after the decimal point are grouped too
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_eng, format_eng_fixed, group_digits};
+/// let x = 123_456.789;
+/// assert_eq!(group_digits(&format_eng(x, Some(9)), ' ', true), "123.456 789e3");
+/// assert_eq!(
+///     group_digits(&format_eng_fixed(x, 0, Some(9)).unwrap(), ',', false),
+///     "123,456.789"
+/// );
+/// ```
+pub fn group_digits(text: &str, separator: char, group_fraction: bool) -> String {
+    let mut grouped = String::with_capacity(text.len() + text.len() / 3);
+    push_grouped(&mut grouped, text, separator, group_fraction, '.');
+    grouped
+}
+
+/// Pushes `text` onto `out` grouped as described for [group_digits], with `decimal_mark` in
+/// place of its decimal point
+pub(crate) fn push_grouped(
+    out: &mut String,
+    text: &str,
+    separator: char,
+    group_fraction: bool,
+    decimal_mark: char,
+) {
+    let n_sign = usize::from(text.starts_with(['-', '+']));
+    let (sign, rest) = text.split_at(n_sign);
+    let n_mantissa = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rest.len());
+    let (mantissa, suffix) = rest.split_at(n_mantissa);
+    let (int, frac) = match mantissa.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (mantissa, None),
+    };
+    out.push_str(sign);
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            out.push(separator);
+        }
+        out.push(c);
+    }
+    if let Some(frac) = frac {
+        out.push(decimal_mark);
+        for (i, c) in frac.chars().enumerate() {
+            if group_fraction && i > 0 && i % 3 == 0 {
+                out.push(separator);
+            }
+            out.push(c);
+        }
+    }
+    out.push_str(suffix);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{format_eng, format_eng_fixed};
+
+    #[test]
+    fn test_group_digits() {
+        let table = [
+            ("123456.789", ',', false, "123,456.789"),
+            ("123456.789", ',', true, "123,456.789"),
+            ("-1234567.0", ',', true, "-1,234,567.0"),
+            ("+1000", '_', false, "+1_000"),
+            ("1.234567e6", ' ', true, "1.234 567e6"),
+            ("-1.2345678E-9", ' ', true, "-1.234 567 8E-9"),
+            ("1.2345678e-9", ' ', false, "1.2345678e-9"),
+            ("1500e-3", ',', false, "1,500e-3"),
+            ("12.3 e3", ',', true, "12.3 e3"),
+        ];
+        for (text, separator, group_fraction, expected) in table {
+            assert_eq!(group_digits(text, separator, group_fraction), expected);
+        }
+    }
+    #[test]
+    fn test_no_grouping_needed() {
+        for text in ["1.23e3", "-999", "0.00", "-0", "100e-3", "NaN", "-inf", ""] {
+            assert_eq!(group_digits(text, ',', true), text);
+        }
+    }
+    #[test]
+    fn test_formatted_values() {
+        assert_eq!(
+            group_digits(&format_eng(-1_234_567., Some(7)), ',', true),
+            "-1.234,567e6"
+        );
+        assert_eq!(
+            group_digits(
+                &format_eng_fixed(-1_234_567., 0, Some(7)).unwrap(),
+                ',',
+                true
+            ),
+            "-1,234,567"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Module for formatting byte counts and other binary quantities with [IEC
+//! prefixes](https://en.wikipedia.org/wiki/Binary_prefix) for powers of 1024.
+
+use crate::error::check_sf;
+use crate::exact::{expansion_digits, format_rounded, round_digits, RoundingMode, EXACT_PRECISION};
+use crate::format_eng;
+use crate::prefix::IEC_PREFIXES;
+
+/// Largest power of 1024 with a prefix, i.e. quebi
+const MAX_POWER: u32 = IEC_PREFIXES.len() as u32 - 1;
+
+/// Returns `x` scaled by the largest power of 1024 not exceeding it and followed by that
+/// power's [IEC prefix](https://en.wikipedia.org/wiki/Binary_prefix), e.g. `"1.50Ki"` for 1536,
+/// with the mantissa rounded to `sf` significant figures half away from zero from its exact
+/// value as by [format_eng].  A mantissa that would round to 1000 or more moves to the next
+/// prefix, so 1023.9 is `"1.00Ki"` rather than `"1020"`.  Values whose mantissa rounds below
+/// 1000, and zero, NaN, and infinities, are exactly as [format_eng] formats them, and values
+/// beyond 1024 quebi keep an `e` exponent on the mantissa, e.g. `"1.00e6Qi"`.
+///
+/// So not every value below 1024 is left without a prefix: 1000 through 1023 would need a
+/// fourth digit left of the decimal point, so they and values rounding up to them take `Ki`,
+/// e.g. 999.6 is `"0.976Ki"` and 1000 is `"0.977Ki"`.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_bin;
+/// assert_eq!(format_bin(1536., None), "1.50Ki");
+/// assert_eq!(format_bin(3_221_225_472., None), "3.00Gi");
+/// assert_eq!(format_bin(1023.9, None), "1.00Ki");
+/// assert_eq!(format_bin(512., None), "512");
+/// assert_eq!(format_bin(999.6, None), "0.976Ki");
+/// assert_eq!(format_bin(1000., None), "0.977Ki");
+/// ```
+pub fn format_bin(x: f64, sf: Option<usize>) -> String {
+    let sf = check_sf(sf, x).unwrap_or_else(|err| panic!("{err}"));
+    if !x.is_finite() || x.abs() < 1. {
+        return format_eng(x, Some(sf));
+    }
+    let log2 = ((x.abs().to_bits() >> 52) as i32 - 1023) as u32;
+    format_scaled(x < 0., log2, sf, |power| {
+        expansion_digits(x / 2_f64.powi(10 * power as i32), EXACT_PRECISION)
+    })
+}
+
+/// Same as [format_bin] for the integer with sign `negative` and magnitude `magnitude`,
+/// scaling its exact value rather than first rounding it to an f64
+pub(crate) fn format_bin_int(negative: bool, magnitude: u128, sf: Option<usize>) -> String {
+    let value = if negative {
+        -(magnitude as f64)
+    } else {
+        magnitude as f64
+    };
+    let sf = check_sf(sf, value).unwrap_or_else(|err| panic!("{err}"));
+    if magnitude == 0 {
+        return format_eng(0., Some(sf));
+    }
+    format_scaled(negative, 127 - magnitude.leading_zeros(), sf, |power| {
+        shifted_digits(magnitude, 10 * power)
+    })
+}
+
+/// Formats the magnitude whose floor of log2 is `log2` with the largest IEC prefix that keeps
+/// the rounded mantissa below 1000, where `digits(power)` returns the decimal digits and power
+/// of ten of the leading digit of the magnitude divided by `1024^power`
+fn format_scaled(
+    negative: bool,
+    log2: u32,
+    sf: usize,
+    digits: impl Fn(u32) -> (Vec<u8>, i32),
+) -> String {
+    let round = |power| {
+        let (all_digits, exp_sci) = digits(power);
+        round_digits(
+            &all_digits,
+            exp_sci,
+            sf,
+            RoundingMode::HalfAwayFromZero,
+            negative,
+        )
+    };
+    let mut power = (log2 / 10).min(MAX_POWER);
+    let mut rounded = round(power);
+    if rounded.exp_sci >= 3 && power < MAX_POWER {
+        power += 1;
+        rounded = round(power);
+    }
+    let mantissa = if rounded.exp_sci < 0 && power > 0 {
+        // only a mantissa moved up to the next prefix, e.g. 0.977Ki
+        let digits: String = rounded
+            .digits
+            .iter()
+            .map(|d| char::from(b'0' + d))
+            .collect();
+        format!("{}0.{digits}", if negative { "-" } else { "" })
+    } else {
+        format_rounded(negative, &rounded)
+    };
+    format!("{mantissa}{}", IEC_PREFIXES[power as usize])
+}
+
+/// Returns the decimal digits of `magnitude / 2^shift`, most significant first, and the power
+/// of ten of the leading digit, exactly since every binary fraction has a finite decimal
+/// expansion
+fn shifted_digits(magnitude: u128, shift: u32) -> (Vec<u8>, i32) {
+    let (integer, mut fraction, mask) = match shift {
+        0 => (magnitude, 0, 0),
+        _ => {
+            let mask = (1_u128 << shift) - 1;
+            (magnitude >> shift, magnitude & mask, mask)
+        }
+    };
+    let mut digits: Vec<u8> = match integer {
+        0 => vec![],
+        _ => integer.to_string().bytes().map(|b| b - b'0').collect(),
+    };
+    let mut exp_sci = digits.len() as i32 - 1;
+    // `fraction < 2^shift <= 2^100`, so multiplying by 10 cannot overflow
+    while fraction != 0 {
+        fraction *= 10;
+        let digit = (fraction >> shift) as u8;
+        fraction &= mask;
+        if digits.is_empty() && digit == 0 {
+            exp_sci -= 1;
+        } else {
+            digits.push(digit);
+        }
+    }
+    (digits, exp_sci)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefixes() {
+        let table = [
+            (1536., None, "1.50Ki"),
+            (3_221_225_472., None, "3.00Gi"),
+            (-1536., Some(4), "-1.500Ki"),
+            (1024., Some(1), "1Ki"),
+            (1_048_576., None, "1.00Mi"),
+            (5e15, None, "4.44Pi"),
+            (2_f64.powi(100), None, "1.00Qi"),
+            (2_f64.powi(120), None, "1.05e6Qi"),
+        ];
+        for (x, sf, expected) in table {
+            assert_eq!(format_bin(x, sf), expected);
+        }
+    }
+    #[test]
+    fn test_below_1024() {
+        for x in [
+            0.,
+            -0.,
+            1.,
+            512.,
+            -999.4,
+            0.5,
+            1e-9,
+            f64::NAN,
+            f64::NEG_INFINITY,
+        ] {
+            assert_eq!(format_bin(x, None), format_eng(x, None));
+        }
+    }
+    #[test]
+    fn test_carry() {
+        assert_eq!(format_bin(1023.9, None), "1.00Ki");
+        assert_eq!(format_bin(1023.9, Some(5)), "0.99990Ki");
+        assert_eq!(format_bin(1000., None), "0.977Ki");
+        assert_eq!(format_bin(999.4, None), "999");
+        assert_eq!(format_bin(999.6, None), "0.976Ki");
+        assert_eq!(format_bin(-1_048_575., Some(4)), "-1.000Mi");
+        assert_eq!(format_bin(1_048_575., Some(7)), "0.9999990Mi");
+    }
+    #[test]
+    fn test_int_matches_f64() {
+        for n in [
+            1_u64,
+            999,
+            1000,
+            1023,
+            1024,
+            1536,
+            1_048_575,
+            3_221_225_472,
+            1 << 53,
+        ] {
+            for sf in [1, 3, 7] {
+                assert_eq!(
+                    format_bin_int(false, n as u128, Some(sf)),
+                    format_bin(n as f64, Some(sf))
+                );
+            }
+        }
+    }
+    #[test]
+    fn test_int_exact() {
+        // 2^53 + 1 is not an f64
+        assert_eq!(
+            format_bin_int(false, (1 << 53) + 1, Some(17)),
+            "8.0000000000000009Pi"
+        );
+        assert_eq!(
+            format_bin((1_u64 << 53) as f64 + 1., Some(17)),
+            "8.0000000000000000Pi"
+        );
+        assert_eq!(format_bin_int(false, u128::MAX, None), "268e6Qi");
+        assert_eq!(format_bin_int(true, 1536, None), "-1.50Ki");
+        assert_eq!(format_bin_int(false, 0, None), "0.00");
+    }
+    #[test]
+    #[should_panic]
+    fn test_sf_zero() {
+        format_bin(1536., Some(0));
+    }
+}
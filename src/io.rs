@@ -0,0 +1,394 @@
+//! Module for converting binary files of floats and CSV time series into text files of values
+//! formatted in [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::error::{check_sf, MAX_SIG_FIGS};
+use crate::exact::write_eng_checked;
+use crate::format_eng;
+
+/// Size of chunks read from the input file
+const CHUNK_BYTES: usize = 1 << 16;
+/// Capacity of the output buffer
+const WRITE_BUF_BYTES: usize = 1 << 20;
+
+/// Byte order of values in a binary input file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endian {
+    /// Least significant byte first, as written by x86 and most ARM systems
+    #[default]
+    Little,
+    /// Most significant byte first, as in network byte order
+    Big,
+}
+
+/// Width of values in a binary input file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FloatWidth {
+    /// 4-byte IEEE 754 single precision, widened exactly to f64 for formatting
+    F32,
+    /// 8-byte IEEE 754 double precision
+    #[default]
+    F64,
+}
+
+impl FloatWidth {
+    fn n_bytes(&self) -> usize {
+        match self {
+            FloatWidth::F32 => 4,
+            FloatWidth::F64 => 8,
+        }
+    }
+}
+
+/// Options for [format_eng_file]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FileFormatOptions {
+    /// Number of significant figures, defaults to 3
+    pub sf: Option<usize>,
+    /// Byte order of the input values
+    pub endian: Endian,
+    /// Width of the input values
+    pub width: FloatWidth,
+}
+
+/// Summary of a [format_eng_file] run
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    /// Number of values written, including non-finite values
+    pub count: usize,
+    /// Smallest value encountered, ignoring NaN
+    pub min: Option<f64>,
+    /// Largest value encountered, ignoring NaN
+    pub max: Option<f64>,
+}
+
+impl Stats {
+    fn update(&mut self, x: f64) {
+        self.count += 1;
+        if !x.is_nan() {
+            self.min = Some(self.min.map_or(x, |min| min.min(x)));
+            self.max = Some(self.max.map_or(x, |max| max.max(x)));
+        }
+    }
+}
+
+/// Reads the binary floats in `input` in chunks and writes them to `output`, one value per
+/// line, formatted with [format_eng].  Non-finite values are written as `NaN`, `inf`, and
+/// `-inf`.  Returns an error of kind [io::ErrorKind::InvalidData] if the input length is not a
+/// multiple of the value width, or of kind [io::ErrorKind::InvalidInput], before `output` is
+/// created, if `opts.sf` is 0 or more than [MAX_SIG_FIGS].
+///
+/// # Arguments
+/// - `input` - path of binary file to be read
+/// - `output` - path of text file to be created or truncated
+/// - `opts` - significant figures, byte order, and value width
+pub fn format_eng_file(input: &Path, output: &Path, opts: FileFormatOptions) -> io::Result<Stats> {
+    check_sf_io(opts.sf)?;
+    let reader = BufReader::with_capacity(CHUNK_BYTES, File::open(input)?);
+    let writer = BufWriter::with_capacity(WRITE_BUF_BYTES, File::create(output)?);
+    format_eng_stream(reader, writer, opts)
+}
+
+/// Same as [format_eng_file] but for arbitrary readers and writers
+pub fn format_eng_stream<R: Read, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    opts: FileFormatOptions,
+) -> io::Result<Stats> {
+    let sf = check_sf_io(opts.sf)?;
+    let width = opts.width.n_bytes();
+    let mut stats = Stats::default();
+    let mut buf = vec![0_u8; CHUNK_BYTES];
+    // reused for every line so that formatting does not allocate
+    let mut line = String::new();
+    // number of bytes at the start of `buf` left over from the previous chunk
+    let mut n_carry = 0;
+    loop {
+        let n_read = reader.read(&mut buf[n_carry..])?;
+        if n_read == 0 {
+            break;
+        }
+        let n_filled = n_carry + n_read;
+        let n_whole = n_filled - n_filled % width;
+        for bytes in buf[..n_whole].chunks_exact(width) {
+            let x = decode(bytes, opts);
+            stats.update(x);
+            write_value(&mut writer, &mut line, x, sf)?;
+        }
+        buf.copy_within(n_whole..n_filled, 0);
+        n_carry = n_filled - n_whole;
+    }
+    if n_carry != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("input ends with {n_carry} bytes that do not form a whole value"),
+        ));
+    }
+    writer.flush()?;
+    Ok(stats)
+}
+
+/// Options for [downsample_format_csv]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DownsampleOptions {
+    /// Number of significant figures, defaults to 3
+    pub sf: Option<usize>,
+    /// True if the first line of the input is a header naming the two columns
+    pub has_header: bool,
+}
+
+/// Reads `timestamp,value` rows from `reader`, splits them into at most `n_buckets` buckets of
+/// consecutive rows, and writes one `timestamp,min,mean,max` row per bucket to `writer`, with
+/// the timestamp text of the bucket's first row copied untouched and the statistics of its
+/// finite values formatted with [format_eng].  Rows are bucketed by position rather than by
+/// time, so irregular sampling only affects which timestamps are kept.  Inputs with fewer rows
+/// than `n_buckets` get one bucket per row.  A bucket with no finite value gets `NaN` for all
+/// three statistics.  With a header, the output header keeps the input's timestamp column name.
+///
+/// All rows are read before any are written, since bucket boundaries depend on the row count.
+/// Values are parsed as by [str::parse], so `NaN` and `inf` are accepted and only left out of
+/// the statistics.  Returns an error of kind [io::ErrorKind::InvalidData] naming the line of
+/// any row that does not have exactly two fields or whose value does not parse, or of kind
+/// [io::ErrorKind::InvalidInput], before anything is read, if `opts.sf` is 0 or more than
+/// [MAX_SIG_FIGS].
+///
+/// # Arguments
+/// - `reader` - CSV text with a timestamp column and a value column
+/// - `writer` - destination for the downsampled CSV
+/// - `n_buckets` - maximum number of output rows, excluding the header
+/// - `opts` - significant figures and header handling
+///
+/// # Examples
+/// ```
+/// use eng_fmt::io::{downsample_format_csv, DownsampleOptions};
+/// let input = "t0,1e-3\nt1,3e-3\nt2,2e-3\nt3,6e-3\n";
+/// let mut output = Vec::new();
+/// downsample_format_csv(input.as_bytes(), &mut output, 2, DownsampleOptions::default()).unwrap();
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "t0,1.00e-3,2.00e-3,3.00e-3\nt2,2.00e-3,4.00e-3,6.00e-3\n"
+/// );
+/// ```
+pub fn downsample_format_csv<R: BufRead, W: Write>(
+    reader: R,
+    mut writer: W,
+    n_buckets: usize,
+    opts: DownsampleOptions,
+) -> io::Result<Stats> {
+    let sf = check_sf_io(opts.sf)?;
+    let mut stats = Stats::default();
+    let mut rows = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if i == 0 && opts.has_header {
+            let timestamp = line.split(',').next().unwrap_or_default();
+            writeln!(writer, "{timestamp},min,mean,max")?;
+            continue;
+        }
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {} is not a `timestamp,value` row: {line:?}", i + 1),
+            )
+        };
+        let (timestamp, value) = line.split_once(',').ok_or_else(invalid)?;
+        if value.contains(',') {
+            return Err(invalid());
+        }
+        let x: f64 = value.trim().parse().map_err(|_| invalid())?;
+        stats.update(x);
+        rows.push((timestamp.to_string(), x));
+    }
+
+    let n_buckets = rows.len().min(n_buckets);
+    for i in 0..n_buckets {
+        let bucket = &rows[i * rows.len() / n_buckets..(i + 1) * rows.len() / n_buckets];
+        let finite = bucket.iter().map(|(_, x)| *x).filter(|x| x.is_finite());
+        let (min, max, sum, n) = finite.fold(
+            (f64::INFINITY, f64::NEG_INFINITY, 0., 0),
+            |(min, max, sum, n), x| (min.min(x), max.max(x), sum + x, n + 1),
+        );
+        let (min, mean, max) = match n {
+            0 => (f64::NAN, f64::NAN, f64::NAN),
+            n => (min, sum / n as f64, max),
+        };
+        writeln!(
+            writer,
+            "{},{},{},{}",
+            bucket[0].0,
+            format_eng(min, Some(sf)),
+            format_eng(mean, Some(sf)),
+            format_eng(max, Some(sf))
+        )?;
+    }
+    writer.flush()?;
+    Ok(stats)
+}
+
+fn decode(bytes: &[u8], opts: FileFormatOptions) -> f64 {
+    match (opts.width, opts.endian) {
+        (FloatWidth::F64, Endian::Little) => f64::from_le_bytes(bytes.try_into().unwrap()),
+        (FloatWidth::F64, Endian::Big) => f64::from_be_bytes(bytes.try_into().unwrap()),
+        (FloatWidth::F32, Endian::Little) => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+        (FloatWidth::F32, Endian::Big) => f32::from_be_bytes(bytes.try_into().unwrap()) as f64,
+    }
+}
+
+/// Returns `sf` or its default, or an error of kind [io::ErrorKind::InvalidInput] if it is out
+/// of range
+fn check_sf_io(sf: Option<usize>) -> io::Result<usize> {
+    check_sf(sf, f64::NAN).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "number of significant figures must be from 1 to {MAX_SIG_FIGS}, not {}",
+                sf.unwrap_or_default()
+            ),
+        )
+    })
+}
+
+/// Writes `x` at `sf` significant figures and a newline to `writer`, formatting into `line`
+fn write_value<W: Write>(writer: &mut W, line: &mut String, x: f64, sf: usize) -> io::Result<()> {
+    line.clear();
+    // writing into a `String` cannot fail
+    write_eng_checked(line, x, sf).unwrap();
+    line.push('\n');
+    writer.write_all(line.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_f32_big_endian() {
+        let input: Vec<u8> = [0.25_f32, -2.5e6, f32::NAN]
+            .iter()
+            .flat_map(|x| x.to_be_bytes())
+            .collect();
+        let mut output = Vec::new();
+        let opts = FileFormatOptions {
+            sf: Some(2),
+            endian: Endian::Big,
+            width: FloatWidth::F32,
+        };
+        let stats = format_eng_stream(input.as_slice(), &mut output, opts).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            String::from("250e-3\n-2.5e6\nNaN\n")
+        );
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.min, Some(-2.5e6));
+        assert_eq!(stats.max, Some(0.25));
+    }
+    #[test]
+    fn test_stream_partial_value() {
+        let input = [0_u8; 12];
+        let err = format_eng_stream(input.as_slice(), io::sink(), Default::default()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+    #[test]
+    fn test_stream_invalid_sf() {
+        for sf in [0, MAX_SIG_FIGS + 1] {
+            let opts = FileFormatOptions {
+                sf: Some(sf),
+                ..Default::default()
+            };
+            let err = format_eng_stream(io::empty(), io::sink(), opts).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            assert!(err.to_string().contains(&format!("not {sf}")), "{err}");
+        }
+    }
+    #[test]
+    fn test_stream_empty() {
+        let stats = format_eng_stream(io::empty(), io::sink(), Default::default()).unwrap();
+        assert_eq!(stats, Stats::default());
+    }
+    #[test]
+    fn test_downsample_irregular() {
+        // irregular timestamps, bucketed by position
+        let input = "time,volts\n\
+                     0.000,1e-3\n0.001,2e-3\n0.010,3e-3\n\
+                     0.011,-4e-3\n0.500,5e-3\n0.501,6e-3\n\
+                     2.000,7e-3\n";
+        let mut output = Vec::new();
+        let opts = DownsampleOptions {
+            sf: Some(2),
+            has_header: true,
+        };
+        let stats = downsample_format_csv(input.as_bytes(), &mut output, 3, opts).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            String::from(
+                "time,min,mean,max\n\
+                 0.000,1.0e-3,1.5e-3,2.0e-3\n\
+                 0.010,-4.0e-3,-500e-6,3.0e-3\n\
+                 0.500,5.0e-3,6.0e-3,7.0e-3\n"
+            )
+        );
+        assert_eq!(stats.count, 7);
+        assert_eq!(stats.min, Some(-4e-3));
+        assert_eq!(stats.max, Some(7e-3));
+    }
+    #[test]
+    fn test_downsample_fewer_rows_than_buckets() {
+        let input = "a,1\nb,2\n";
+        let mut output = Vec::new();
+        downsample_format_csv(input.as_bytes(), &mut output, 10, Default::default()).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            String::from("a,1.00,1.00,1.00\nb,2.00,2.00,2.00\n")
+        );
+    }
+    #[test]
+    fn test_downsample_nan() {
+        let input = "a,NaN\nb,4\nc,NaN\nd,NaN\n";
+        let mut output = Vec::new();
+        let stats =
+            downsample_format_csv(input.as_bytes(), &mut output, 2, Default::default()).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            String::from("a,4.00,4.00,4.00\nc,NaN,NaN,NaN\n")
+        );
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.min, Some(4.));
+
+        let input = "a,inf\nb,2\nc,-inf\n";
+        let mut output = Vec::new();
+        let stats =
+            downsample_format_csv(input.as_bytes(), &mut output, 1, Default::default()).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            String::from("a,2.00,2.00,2.00\n")
+        );
+        assert_eq!(stats.max, Some(f64::INFINITY));
+    }
+    #[test]
+    fn test_downsample_invalid_row() {
+        let input = "a,1\nb,x\n";
+        let err =
+            downsample_format_csv(input.as_bytes(), io::sink(), 1, Default::default()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 2"), "{err}");
+    }
+    #[test]
+    fn test_downsample_invalid_sf() {
+        let opts = DownsampleOptions {
+            sf: Some(0),
+            has_header: false,
+        };
+        let err = downsample_format_csv("a,1\n".as_bytes(), io::sink(), 1, opts).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+    #[test]
+    fn test_downsample_empty() {
+        let mut output = Vec::new();
+        let stats = downsample_format_csv(io::empty(), &mut output, 5, Default::default()).unwrap();
+        assert!(output.is_empty());
+        assert_eq!(stats, Stats::default());
+    }
+}
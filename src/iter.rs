@@ -0,0 +1,124 @@
+//! Module containing adapters for formatting iterators of fallible values in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use crate::format_eng;
+
+/// Lazily formats every `Ok` value of `iter` with [format_eng] and passes every `Err` through
+/// untouched.  Nothing is allocated for the error arm.
+///
+/// # Arguments
+/// - `iter` - values to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_results;
+/// let values: Vec<Result<f64, &str>> = vec![Ok(0.010), Err("bad sensor"), Ok(6.022e-23)];
+/// let formatted: Vec<_> = format_eng_results(values, None).collect();
+/// assert_eq!(
+///     formatted,
+///     vec![Ok("10.0e-3".to_string()), Err("bad sensor"), Ok("60.2e-24".to_string())]
+/// );
+/// ```
+pub fn format_eng_results<I, E>(
+    iter: I,
+    sf: Option<usize>,
+) -> impl Iterator<Item = Result<String, E>>
+where
+    I: IntoIterator<Item = Result<f64, E>>,
+{
+    iter.into_iter().map(move |r| r.map(|x| format_eng(x, sf)))
+}
+
+/// Formats every `Ok` value of `iter` with [format_eng] and partitions the results into the
+/// formatted strings and the errors paired with their index in `iter`.
+///
+/// # Arguments
+/// - `iter` - values to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_collect_errors;
+/// let values: Vec<Result<f64, &str>> = vec![Ok(0.010), Err("bad sensor"), Ok(6.022e-23)];
+/// let (formatted, errors) = format_eng_collect_errors(values, None);
+/// assert_eq!(formatted, vec!["10.0e-3".to_string(), "60.2e-24".to_string()]);
+/// assert_eq!(errors, vec![(1, "bad sensor")]);
+/// ```
+pub fn format_eng_collect_errors<I, E>(iter: I, sf: Option<usize>) -> (Vec<String>, Vec<(usize, E)>)
+where
+    I: IntoIterator<Item = Result<f64, E>>,
+{
+    let mut formatted = Vec::new();
+    let mut errors = Vec::new();
+    for (i, r) in format_eng_results(iter, sf).enumerate() {
+        match r {
+            Ok(s) => formatted.push(s),
+            Err(e) => errors.push((i, e)),
+        }
+    }
+    (formatted, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct MyErr(u8);
+
+    fn mixed() -> Vec<Result<f64, MyErr>> {
+        vec![Err(MyErr(0)), Ok(2.), Ok(1e3), Err(MyErr(3)), Ok(-0.1)]
+    }
+
+    #[test]
+    fn test_results_mixed() {
+        assert_eq!(
+            format_eng_results(mixed(), None).collect::<Vec<_>>(),
+            vec![
+                Err(MyErr(0)),
+                Ok(String::from("2.00")),
+                Ok(String::from("1.00e3")),
+                Err(MyErr(3)),
+                Ok(String::from("-100e-3")),
+            ]
+        );
+    }
+    #[test]
+    fn test_results_lazy() {
+        let mut n_pulled = 0;
+        let endless = std::iter::repeat_with(|| {
+            n_pulled += 1;
+            Ok::<f64, MyErr>(1.0)
+        });
+        let first: Vec<_> = format_eng_results(endless, Some(2)).take(2).collect();
+        assert_eq!(
+            first,
+            vec![Ok(String::from("1.0")), Ok(String::from("1.0"))]
+        );
+        assert_eq!(n_pulled, 2);
+    }
+    #[test]
+    fn test_collect_errors_mixed() {
+        let (formatted, errors) = format_eng_collect_errors(mixed(), Some(4));
+        assert_eq!(
+            formatted,
+            vec![
+                String::from("2.000"),
+                String::from("1.000e3"),
+                String::from("-100.0e-3")
+            ]
+        );
+        assert_eq!(errors, vec![(0, MyErr(0)), (3, MyErr(3))]);
+    }
+    #[test]
+    fn test_collect_errors_all_errors() {
+        let all_err = (0..4).map(|i| Err::<f64, _>(MyErr(i)));
+        let (formatted, errors) = format_eng_collect_errors(all_err, None);
+        assert!(formatted.is_empty());
+        assert_eq!(
+            errors,
+            vec![(0, MyErr(0)), (1, MyErr(1)), (2, MyErr(2)), (3, MyErr(3))]
+        );
+    }
+}
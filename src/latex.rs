@@ -0,0 +1,96 @@
+//! Module for formatting in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) as LaTeX.
+
+use crate::format_eng;
+
+/// LaTeX markup written by [format_latex]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LatexStyle {
+    /// Input to the siunitx `\num` macro, e.g. `\num{314e-3}`, which typesets it per the
+    /// document's siunitx settings
+    #[default]
+    Siunitx,
+    /// Plain math mode, e.g. `$314 \times 10^{-3}$`, needing no packages
+    Math,
+}
+
+/// Returns `x` in [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation) as
+/// LaTeX, ready to drop into a tabular cell, with the digits of [format_eng].  A value whose
+/// engineering exponent is 0 has no power of ten, and the sign of a negative value is inside
+/// math mode so it is typeset as a minus rather than a hyphen.  NaN and infinities are
+/// `\num{nan}` and `\num{inf}` for siunitx and `$\mathrm{NaN}$` and `$\infty$` in math mode.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+/// - `style` - siunitx or plain math markup
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_latex, LatexStyle};
+/// assert_eq!(format_latex(0.314, None, LatexStyle::Siunitx), r"\num{314e-3}");
+/// assert_eq!(format_latex(-3.14e3, None, LatexStyle::Math), r"$-3.14 \times 10^{3}$");
+/// ```
+pub fn format_latex(x: f64, sf: Option<usize>, style: LatexStyle) -> String {
+    let formatted = format_eng(x, sf);
+    match style {
+        LatexStyle::Siunitx => {
+            let number = match formatted.as_str() {
+                "NaN" => "nan",
+                number => number,
+            };
+            format!(r"\num{{{number}}}")
+        }
+        LatexStyle::Math => {
+            let body = match formatted.split_once('e') {
+                _ if x.is_nan() => String::from(r"\mathrm{NaN}"),
+                _ if x.is_infinite() => String::from(if x > 0. { r"\infty" } else { r"-\infty" }),
+                Some((mantissa, exp)) => format!(r"{mantissa} \times 10^{{{exp}}}"),
+                None => formatted,
+            };
+            format!("${body}$")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_siunitx() {
+        let table = [
+            (3.14159e3, None, r"\num{3.14e3}"),
+            (-3.14159e3, None, r"\num{-3.14e3}"),
+            (0.314, None, r"\num{314e-3}"),
+            (-6.022e-23, Some(4), r"\num{-60.22e-24}"),
+            (0., None, r"\num{0.00}"),
+            (-0., Some(1), r"\num{-0}"),
+            (2.5, Some(2), r"\num{2.5}"),
+            (f64::NAN, None, r"\num{nan}"),
+            (f64::NEG_INFINITY, None, r"\num{-inf}"),
+        ];
+        for (x, sf, expected) in table {
+            assert_eq!(format_latex(x, sf, LatexStyle::Siunitx), expected);
+        }
+    }
+    #[test]
+    fn test_math() {
+        let table = [
+            (3.14159e3, None, r"$3.14 \times 10^{3}$"),
+            (-3.14159e3, None, r"$-3.14 \times 10^{3}$"),
+            (0.314, None, r"$314 \times 10^{-3}$"),
+            (-6.022e-23, Some(4), r"$-60.22 \times 10^{-24}$"),
+            (1.5e-300, None, r"$1.50 \times 10^{-300}$"),
+            (0., None, r"$0.00$"),
+            (2.5, Some(2), r"$2.5$"),
+            (-999.4, None, r"$-999$"),
+            (f64::NAN, None, r"$\mathrm{NaN}$"),
+            (f64::INFINITY, None, r"$\infty$"),
+            (f64::NEG_INFINITY, None, r"$-\infty$"),
+        ];
+        for (x, sf, expected) in table {
+            assert_eq!(format_latex(x, sf, LatexStyle::Math), expected);
+        }
+    }
+}
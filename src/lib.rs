@@ -23,15 +23,165 @@
 //! assert_eq!(x.format_eng(None), expected);
 //! ```
 
+mod assert;
+mod big;
+mod bound;
+mod const_sf;
+mod decades;
+mod diff;
+mod dp;
+mod duration;
+mod eng;
+mod error;
+mod exact;
+mod faithful;
+mod fit;
+mod fixed;
+mod formatter;
+mod group;
+mod iec;
+#[cfg(feature = "io")]
+pub mod io;
+mod iter;
+mod latex;
+mod literal;
+pub mod math;
+mod multiple;
+mod parse;
+mod partial;
+mod parts;
+mod pow2;
+mod prefix;
+mod preimage;
+mod primitive;
+mod prob;
+mod prose;
+mod quantity;
+mod ratio;
+mod report;
+mod scale;
+mod sci;
+mod si;
+mod sink;
+mod slice;
+mod spoken;
+mod symmetric;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+mod tracker;
+mod truncate;
+mod unc;
+#[cfg(feature = "viz")]
+pub mod viz;
+#[doc(hidden)]
+pub use assert::{__eng_close_failure, __eng_range_failure};
+pub use big::format_eng_big;
+pub use bound::{format_eng_bound, format_eng_ceil, format_eng_floor, Bound};
+pub use const_sf::format_eng_const;
+pub use decades::{eng_decades, DecadeSummary};
+pub use diff::{eng_diff, DiffError, DiffItem};
+pub use dp::format_eng_dp;
+pub use duration::format_duration;
+pub use eng::Eng;
+pub use error::{EngFmtError, MAX_SIG_FIGS};
+pub use exact::RoundingMode;
+pub use faithful::{verify_faithful, FaithfulnessError};
+pub use fit::{format_eng_fit, format_eng_uncertain_fit, format_eng_width};
+pub use fixed::{common_eng_exp, format_eng_fixed, FixedExpError};
+pub use formatter::{DecimalMark, EngFormatter, ExpStyle};
+pub use group::group_digits;
+pub use iec::format_bin;
+pub use iter::{format_eng_collect_errors, format_eng_results};
+pub use latex::{format_latex, LatexStyle};
+pub use literal::{format_eng_literal, format_eng_literal_grouped, Lang};
+pub use multiple::{format_eng_to_multiple, MultipleError};
+pub use parse::{
+    parse_bin, parse_eng, parse_eng_with_mark, parse_eng_with_precision,
+    parse_eng_with_precision_rule, parse_si, parse_si_unit, DataUnit, ParseEngError, PrecisionF64,
+    TrailingZeros,
+};
+pub use partial::{validate_partial_eng, Partial, PartialOptions};
+pub use parts::{eng_parts, EngParts, Sign};
+pub use pow2::{format_pow2, Pow2Style, POW2_ENG_3SF};
+pub use preimage::next_display_boundary;
+pub use prob::{format_ber, format_prob, try_format_prob, ProbError, PROB_THRESHOLD};
+pub use prose::reformat_prose;
+pub use quantity::{
+    format_quantity, format_quantity_with, QuantityNotation, QuantityOptions, UnitSeparator,
+};
+pub use ratio::{format_decades, format_octaves, format_slope_db_per_decade, RatioError};
+pub use report::{format_eng_approx, format_eng_report, RoundingReport, APPROX};
+pub use scale::{scale_bar, ScaleBar, ScaleBarError};
+pub use sci::{FormatSci, Sci};
+pub use si::{format_si, format_si_tagged, SiPrefix, SiTagged};
+pub use sink::{format_eng_buf, format_eng_into};
+pub use slice::{format_eng_slice, ColumnExponent};
+pub use spoken::{
+    english_plural, english_unit_word, format_eng_spoken, format_eng_spoken_with, SpokenWords,
+};
+pub use symmetric::format_eng_symmetric;
+pub use tracker::{EngTracker, TrackerOptions};
+pub use truncate::{truncate_eng, TRUNCATED};
+pub use unc::{format_eng_unc, UncertaintyStyle};
+
 /// Trait providing method for formatting numbers in [engineering
 /// notation](https://en.wikipedia.org/wiki/Engineering_notation)
 pub trait FormatEng {
     /// Method for converting numeric value into formatted string with [engineering
-    /// notation](https://en.wikipedia.org/wiki/Engineering_notation)
-    fn format_eng(&self, sf: Option<usize>) -> String;
+    /// notation](https://en.wikipedia.org/wiki/Engineering_notation), or an error if `sf` is
+    /// out of range
+    fn try_format_eng(&self, sf: Option<usize>) -> Result<String, EngFmtError>;
+
+    /// Method for converting numeric value into formatted string with [engineering
+    /// notation](https://en.wikipedia.org/wiki/Engineering_notation), panicking where
+    /// [try_format_eng](FormatEng::try_format_eng) returns an error
+    fn format_eng(&self, sf: Option<usize>) -> String {
+        self.try_format_eng(sf)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Method for converting numeric value into formatted string with the engineering exponent
+    /// replaced by its [SI prefix](https://en.wikipedia.org/wiki/Metric_prefix)
+    fn format_si(&self, sf: Option<usize>) -> String {
+        si::si_from_eng(self.format_eng(sf))
+    }
+
+    /// Method for converting numeric value into formatted string with [engineering
+    /// notation](https://en.wikipedia.org/wiki/Engineering_notation) and a fixed number of
+    /// decimal places in the mantissa, as by [format_eng_dp]
+    fn format_eng_dp(&self, decimal_places: usize) -> String {
+        dp::format_text_dp(self.format_eng(Some(MAX_SIG_FIGS)), decimal_places)
+    }
+
+    /// Method for converting numeric value into formatted string scaled by a power of 1024 with
+    /// its [IEC prefix](https://en.wikipedia.org/wiki/Binary_prefix), as by [format_bin]
+    fn format_bin(&self, sf: Option<usize>) -> String {
+        let value: f64 = self.format_eng(Some(MAX_SIG_FIGS)).parse().unwrap();
+        format_bin(value, sf)
+    }
+
+    /// Method for converting numeric value into formatted string with [engineering
+    /// notation](https://en.wikipedia.org/wiki/Engineering_notation) at `SF` significant
+    /// figures, checked at compile time as by [format_eng_const]
+    fn format_eng_const<const SF: usize>(&self) -> String
+    where
+        Self: Sized,
+    {
+        const {
+            assert!(
+                SF >= 1 && SF <= MAX_SIG_FIGS,
+                "`format_eng_const` arg `SF` must be from 1 to `MAX_SIG_FIGS`."
+            )
+        };
+        self.format_eng(Some(SF))
+    }
 }
 
 impl FormatEng for f64 {
+    fn try_format_eng(&self, sf: Option<usize>) -> Result<String, EngFmtError> {
+        try_format_eng(*self, sf)
+    }
+
     /// Returns f64 as string in [engineering
     /// notation](https://en.wikipedia.org/wiki/Engineering_notation) with last digit rounded to nearest
     /// rather than truncated.
@@ -42,73 +192,137 @@ impl FormatEng for f64 {
     fn format_eng(&self, sf: Option<usize>) -> String {
         format_eng(*self, sf)
     }
+
+    /// Returns f64 as string like [format_eng](FormatEng::format_eng) but with an [SI
+    /// prefix](https://en.wikipedia.org/wiki/Metric_prefix) such as `k` or `µ` in place of the
+    /// exponent, keeping `e` notation outside yocto to yotta.
+    ///
+    /// # Arguments
+    /// - `sf` - Number of significant figures, defaults to 3
+    ///
+    /// # Examples
+    /// ```
+    /// use eng_fmt::FormatEng;
+    /// assert_eq!(0.010_f64.format_si(None), "10.0m");
+    /// ```
+    fn format_si(&self, sf: Option<usize>) -> String {
+        format_si(*self, sf)
+    }
+
+    /// Returns f64 as string like [format_eng](FormatEng::format_eng) but with a fixed number of
+    /// decimal places in the mantissa rather than significant figures.
+    ///
+    /// # Arguments
+    /// - `decimal_places` - Number of digits after the decimal point
+    ///
+    /// # Examples
+    /// ```
+    /// use eng_fmt::FormatEng;
+    /// assert_eq!(12.345_678e6_f64.format_eng_dp(2), "12.35e6");
+    /// ```
+    fn format_eng_dp(&self, decimal_places: usize) -> String {
+        format_eng_dp(*self, decimal_places)
+    }
+
+    /// Returns f64 as string scaled by the largest power of 1024 not exceeding it, with its [IEC
+    /// prefix](https://en.wikipedia.org/wiki/Binary_prefix) such as `Ki` or `Gi`.
+    ///
+    /// # Arguments
+    /// - `sf` - Number of significant figures, defaults to 3
+    ///
+    /// # Examples
+    /// ```
+    /// use eng_fmt::FormatEng;
+    /// assert_eq!(1536.0.format_bin(None), "1.50Ki");
+    /// ```
+    fn format_bin(&self, sf: Option<usize>) -> String {
+        format_bin(*self, sf)
+    }
+
+    /// Returns f64 as string in [engineering
+    /// notation](https://en.wikipedia.org/wiki/Engineering_notation) at `SF` significant figures,
+    /// checked at compile time.
+    ///
+    /// # Examples
+    /// ```
+    /// use eng_fmt::FormatEng;
+    /// assert_eq!(0.010_f64.format_eng_const::<4>(), "10.00e-3");
+    /// ```
+    #[inline]
+    fn format_eng_const<const SF: usize>(&self) -> String {
+        format_eng_const::<SF>(*self)
+    }
 }
 
 /// Returns f64 as string in [engineering
 /// notation](https://en.wikipedia.org/wiki/Engineering_notation) with last digit rounded to nearest
 /// rather than truncated.
+///
+/// Digits are rounded half away from zero from the exact binary value of `x`, so e.g. 0.85 at 1
+/// significant figure is `"800e-3"` because 0.85 is stored as 0.8499999999999999778.  The
+/// canonical semantics are documented in `tools/gen_reference.py`, which generates the
+/// reference outputs this function is tested against.
+///
+/// Special values are returned early: NaN as `"NaN"`, infinities as `"inf"` and `"-inf"`, and
+/// zero as zero padded to `sf` significant figures, keeping the sign of negative zero (e.g.
+/// `"-0.00"`) as `{:.2}` does, so that the sign bit is never silently lost.  Subnormal values
+/// are formatted from their exact value like any other, e.g. `5e-320` is `"50.0e-321"`.
 /// # Arguments
 /// - `x` - value to be formatted
 /// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS]; see [try_format_eng].
 pub fn format_eng(x: f64, sf: Option<usize>) -> String {
-    let sf = sf.unwrap_or(3);
-    assert!(sf >= 1, "`format_eng` arg `sf` must be at least 1.");
-
-    if x == 0. {
-        return format!("{x:.*}", sf - 1);
-    }
-
-    let abs_log10 = x.abs().log10();
-
-    let exp_sci: i32 = if abs_log10 >= 0. {
-        abs_log10.floor()
-    } else {
-        abs_log10.ceil()
-    } as i32;
-
-    // engineering notation exponent
-    let exp_eng: i32 = if abs_log10 >= 0. {
-        exp_sci - abs_log10.floor() as i32 % 3
-    } else if abs_log10.fract() == 0. && abs_log10.abs() as u32 % 3 == 0 {
-        exp_sci - abs_log10.ceil() as i32 % 3
-    } else {
-        exp_sci - abs_log10.ceil() as i32 % 3 - 3
-    };
-
-    let mut x_base = match exp_eng {
-        // _ if exp_eng < 0 => ,
-        _ if exp_eng.abs() <= 2 => x,
-        _ => x / 10_f64.powi(exp_eng),
-    };
-
-    // number of digits left of decimal _after_ formatting for engineering notation, should never
-    // exceed 3
-    let n_left_of_dec: i32 = if abs_log10 > 0. {
-        abs_log10.floor() as i32 % 3 + 1
-    } else if abs_log10 == 0. {
-        1
-    } else if abs_log10.fract() == 0. {
-        3 - (-(abs_log10 as i32 + 1) % 3)
-    } else {
-        3 - (-abs_log10.ceil() as i32 % 3)
-    };
-
-    assert!(
-        n_left_of_dec <= 3,
-        "n_left_of_dec: {} exceeds 3",
-        n_left_of_dec
-    );
-
-    let n_dec = sf as i32 - n_left_of_dec;
+    try_format_eng(x, sf).unwrap_or_else(|err| panic!("{err}"))
+}
 
-    // round `x_base` as appropriate
-    let exp = sf as i32 - n_left_of_dec;
-    x_base = (x_base * 10_f64.powi(exp)).round() * 10_f64.powi(-exp);
+/// Same as [format_eng], but returns an error rather than panicking if `sf` is 0 or more than
+/// [MAX_SIG_FIGS].
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{try_format_eng, EngFmtError};
+/// assert_eq!(try_format_eng(0.010, None), Ok("10.0e-3".to_string()));
+/// assert_eq!(
+///     try_format_eng(0.010, Some(0)),
+///     Err(EngFmtError::SigFigsZero { value: 0.010 })
+/// );
+/// ```
+pub fn try_format_eng(x: f64, sf: Option<usize>) -> Result<String, EngFmtError> {
+    EngFormatter::new().sig_figs(sf.unwrap_or(3)).try_format(x)
+}
 
-    match exp_eng {
-        _ if (0..=2).contains(&exp_eng) => format!("{x_base:.*}", n_dec.max(0) as usize),
-        _ => format!("{x_base:.*}e{}", n_dec.max(0) as usize, exp_eng),
-    }
+/// Same as [format_eng] but rounds the exact binary value of `x` per `mode` rather than half
+/// away from zero, e.g. 0.125 at 2 significant figures is `"120e-3"` with
+/// [RoundingMode::HalfEven] and `"130e-3"` by default.  Special values are formatted as by
+/// [format_eng].
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+/// - `mode` - rounding applied to the digits past `sf`
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS].
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_eng_with, RoundingMode};
+/// assert_eq!(format_eng_with(0.125, Some(2), RoundingMode::HalfEven), "120e-3");
+/// assert_eq!(format_eng_with(0.375, Some(2), RoundingMode::HalfEven), "380e-3");
+/// assert_eq!(format_eng_with(-1.299, Some(2), RoundingMode::TruncateTowardZero), "-1.2");
+/// assert_eq!(format_eng_with(1.201, Some(2), RoundingMode::Ceil), "1.3");
+/// ```
+pub fn format_eng_with(x: f64, sf: Option<usize>, mode: RoundingMode) -> String {
+    EngFormatter::new()
+        .sig_figs(sf.unwrap_or(3))
+        .rounding(mode)
+        .format(x)
 }
 
 #[allow(unused_macros)]
@@ -260,6 +474,101 @@ mod tests {
         assert_eq!(0_f64.format_eng(None), String::from("0.00"));
     }
     #[test]
+    fn test_neg_zero() {
+        assert_eq!((-0_f64).format_eng(None), String::from("-0.00"));
+        assert_eq!((-0_f64).format_eng(Some(1)), String::from("-0"));
+    }
+    #[test]
+    fn test_nan() {
+        assert_eq!(f64::NAN.format_eng(None), String::from("NaN"));
+        assert_eq!((-f64::NAN).format_eng(Some(7)), String::from("NaN"));
+    }
+    #[test]
+    fn test_inf() {
+        assert_eq!(f64::INFINITY.format_eng(None), String::from("inf"));
+        assert_eq!(f64::NEG_INFINITY.format_eng(Some(1)), String::from("-inf"));
+    }
+    #[test]
+    fn test_subnormal() {
+        assert_eq!(5e-320.format_eng(None), String::from("50.0e-321"));
+        assert_eq!(5e-324.format_eng(Some(4)), String::from("4.941e-324"));
+        assert_eq!((-5e-324).format_eng(Some(1)), String::from("-5e-324"));
+    }
+    #[test]
+    fn test_min_positive() {
+        assert_eq!(
+            f64::MIN_POSITIVE.format_eng(None),
+            String::from("22.3e-309")
+        );
+        assert_eq!(
+            f64::MIN_POSITIVE.next_down().format_eng(Some(17)),
+            String::from("22.250738585072009e-309")
+        );
+    }
+    #[test]
+    fn test_max() {
+        assert_eq!(f64::MAX.format_eng(None), String::from("180e306"));
+        assert_eq!(f64::MIN.format_eng(Some(5)), String::from("-179.77e306"));
+    }
+    #[test]
+    fn test_extreme_magnitudes_high_sf() {
+        // expected digits from the exact decimal expansion of each value
+        let table = [
+            (f64::MAX, 15, "179.769313486232e306"),
+            (f64::MAX, 16, "179.7693134862316e306"),
+            (f64::MAX, 17, "179.76931348623157e306"),
+            (f64::MIN_POSITIVE, 15, "22.2507385850720e-309"),
+            (f64::MIN_POSITIVE, 16, "22.25073858507201e-309"),
+            (f64::MIN_POSITIVE, 17, "22.250738585072014e-309"),
+            (1.234_567_890_123_4e300, 12, "1.23456789012e300"),
+            (1.234_567_890_123_4e300, 17, "1.2345678901234000e300"),
+            (1e300, 17, "1.0000000000000001e300"),
+            (1e-300, 17, "1.0000000000000000e-300"),
+            (9.999_999_999_999_999e-301, 16, "999.9999999999999e-303"),
+            (9.999_999_999_999_999e-301, 15, "1.00000000000000e-300"),
+            (5e-324, 17, "4.9406564584124654e-324"),
+        ];
+        for (x, sf, expected) in table {
+            assert_eq!(format_eng(x, Some(sf)), expected, "x: {x:e}, sf: {sf}");
+            assert_eq!(format_eng(-x, Some(sf)), format!("-{expected}"));
+        }
+    }
+    #[test]
+    fn test_try_invalid_sf() {
+        assert_eq!(
+            try_format_eng(1., Some(0)),
+            Err(EngFmtError::SigFigsZero { value: 1. })
+        );
+        assert_eq!(
+            2.5.try_format_eng(Some(MAX_SIG_FIGS + 1)),
+            Err(EngFmtError::SigFigsTooLarge {
+                sf: MAX_SIG_FIGS + 1,
+                value: 2.5
+            })
+        );
+        // checked before the special values
+        assert!(matches!(
+            try_format_eng(f64::NAN, Some(0)),
+            Err(EngFmtError::SigFigsZero { value }) if value.is_nan()
+        ));
+    }
+    #[test]
+    fn test_try_special() {
+        assert_eq!(try_format_eng(f64::NAN, None), Ok(String::from("NaN")));
+        assert_eq!(try_format_eng(-0., Some(1)), Ok(String::from("-0")));
+    }
+    #[test]
+    fn test_try_max_sf() {
+        let formatted = try_format_eng(0.1, Some(MAX_SIG_FIGS)).unwrap();
+        assert_eq!(formatted.len(), MAX_SIG_FIGS + ".e-3".len());
+        assert!(formatted.starts_with("100.000000000000005551115123125782702118"));
+    }
+    #[test]
+    #[should_panic(expected = "cannot format 1 with 0 significant figures")]
+    fn test_zero_sf_panics() {
+        format_eng(1., Some(0));
+    }
+    #[test]
     fn test_1_000() {
         assert_eq!(1e3.format_eng(None), String::from("1.00e3"));
     }
@@ -297,6 +606,62 @@ mod tests {
         assert_eq!(1_f64.format_eng(None), String::from("1.00"));
     }
 
+    #[test]
+    fn test_999p999_carry() {
+        assert_eq!(999.999_f64.format_eng(None), String::from("1.00e3"));
+    }
+    #[test]
+    fn test_carry_999p5() {
+        assert_eq!(999.5.format_eng(Some(3)), String::from("1.00e3"));
+        assert_eq!((-999.5).format_eng(Some(3)), String::from("-1.00e3"));
+    }
+    #[test]
+    fn test_carry_999p999() {
+        assert_eq!(999.999.format_eng(None), String::from("1.00e3"));
+        assert_eq!((-999.999).format_eng(None), String::from("-1.00e3"));
+    }
+    #[test]
+    fn test_carry_999_950_4d() {
+        assert_eq!(999_950_f64.format_eng(Some(4)), String::from("1.000e6"));
+        assert_eq!((-999_950_f64).format_eng(Some(4)), String::from("-1.000e6"));
+    }
+    #[test]
+    fn test_carry_0p99999() {
+        assert_eq!(0.99999.format_eng(Some(3)), String::from("1.00"));
+        assert_eq!((-0.99999).format_eng(Some(3)), String::from("-1.00"));
+    }
+    #[test]
+    fn test_carry_0p0009995() {
+        // stored as 9.99499999999999954e-4, just below the tie
+        assert_eq!(0.0009995.format_eng(Some(3)), String::from("999e-6"));
+        assert_eq!((-0.0009995).format_eng(Some(3)), String::from("-999e-6"));
+        assert_eq!(0.00099951.format_eng(Some(3)), String::from("1.00e-3"));
+        assert_eq!((-0.00099951).format_eng(Some(3)), String::from("-1.00e-3"));
+    }
+    #[test]
+    fn test_carry_every_group_boundary() {
+        for k in (-300..=300).step_by(3) {
+            let x: f64 = format!("999.96e{k}").parse().unwrap();
+            let expected = format!("1.00e{}", k + 3).replace("e0", "");
+            assert_eq!(x.format_eng(None), expected, "k: {k}");
+            assert_eq!((-x).format_eng(None), format!("-{expected}"), "k: {k}");
+        }
+    }
+    #[test]
+    fn test_0p85_1d() {
+        // stored as 0.84999999999999997780
+        assert_eq!(0.85_f64.format_eng(Some(1)), String::from("800e-3"));
+    }
+    #[test]
+    fn test_9p96_2d_carry() {
+        assert_eq!(9.96_f64.format_eng(Some(2)), String::from("10"));
+    }
+    #[test]
+    fn test_exact_tie() {
+        assert_eq!(2.5_f64.format_eng(Some(1)), String::from("3"));
+        assert_eq!(0.125_f64.format_eng(Some(2)), String::from("130e-3"));
+    }
+
     #[test]
     fn test_pi_5d() {
         assert_eq!(
@@ -304,4 +669,47 @@ mod tests {
             String::from("3.1416")
         );
     }
+    #[test]
+    fn test_rounding_modes() {
+        use RoundingMode::*;
+        // (x, sf, half away, half even, truncate, ceil, floor)
+        let table = [
+            (0.125, 2, "130e-3", "120e-3", "120e-3", "130e-3", "120e-3"),
+            (0.375, 2, "380e-3", "380e-3", "370e-3", "380e-3", "370e-3"),
+            (
+                -0.125, 2, "-130e-3", "-120e-3", "-120e-3", "-120e-3", "-130e-3",
+            ),
+            (2.5, 1, "3", "2", "2", "3", "2"),
+            (3.5, 1, "4", "4", "3", "4", "3"),
+            // 0.85 is stored below its tie, so both nearest modes go down
+            (0.85, 1, "800e-3", "800e-3", "800e-3", "900e-3", "800e-3"),
+            // 1.35 is stored above its tie
+            (1.35, 2, "1.4", "1.4", "1.3", "1.4", "1.3"),
+            (999.5, 3, "1.00e3", "1.00e3", "999", "1.00e3", "999"),
+            (-999.5, 3, "-1.00e3", "-1.00e3", "-999", "-999", "-1.00e3"),
+            (1e3, 1, "1e3", "1e3", "1e3", "1e3", "1e3"),
+        ];
+        for (x, sf, away, even, truncate, ceil, floor) in table {
+            let modes = [HalfAwayFromZero, HalfEven, TruncateTowardZero, Ceil, Floor];
+            for (mode, expected) in modes.into_iter().zip([away, even, truncate, ceil, floor]) {
+                assert_eq!(
+                    format_eng_with(x, Some(sf), mode),
+                    expected,
+                    "x: {x}, sf: {sf}, mode: {mode:?}"
+                );
+            }
+        }
+    }
+    #[test]
+    fn test_rounding_modes_special_values() {
+        for mode in [RoundingMode::HalfEven, RoundingMode::Floor] {
+            assert_eq!(format_eng_with(-0., None, mode), "-0.00");
+            assert_eq!(format_eng_with(f64::NAN, None, mode), "NaN");
+            assert_eq!(format_eng_with(f64::INFINITY, None, mode), "inf");
+        }
+        assert_eq!(
+            format_eng_with(1.0e-3, Some(4), RoundingMode::default()),
+            format_eng(1.0e-3, Some(4))
+        );
+    }
 }
@@ -0,0 +1,320 @@
+//! Module for formatting f64 as source-code numeric literals in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use crate::exact::RoundingMode;
+use crate::{format_eng, format_eng_with};
+
+/// Target language for [format_eng_literal]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lang {
+    /// Rust `f64` literal, e.g. `4.70e3_f64`
+    Rust,
+    /// C `double` literal, e.g. `4.70e3`
+    C,
+    /// C `float` literal with `f` suffix, e.g. `4.70e3f`
+    CFloat,
+    /// Python `float` literal, e.g. `4.70e3`
+    Python,
+}
+
+impl Lang {
+    fn suffix(&self) -> &'static str {
+        match self {
+            Lang::Rust => "_f64",
+            Lang::C | Lang::Python => "",
+            Lang::CFloat => "f",
+        }
+    }
+
+    fn supports_separators(&self) -> bool {
+        matches!(self, Lang::Rust | Lang::Python)
+    }
+
+    /// Returns true if the literal text `lit`, without suffix, is too large for the type
+    fn overflows(&self, lit: &str) -> bool {
+        match self {
+            Lang::CFloat => lit.parse::<f32>().is_ok_and(f32::is_infinite),
+            Lang::Rust | Lang::C | Lang::Python => lit.parse::<f64>().is_ok_and(f64::is_infinite),
+        }
+    }
+
+    fn non_finite(&self, x: f64) -> String {
+        let (nan, inf, neg_inf) = match self {
+            Lang::Rust => ("f64::NAN", "f64::INFINITY", "f64::NEG_INFINITY"),
+            Lang::C | Lang::CFloat => ("NAN", "INFINITY", "-INFINITY"),
+            Lang::Python => ("float('nan')", "float('inf')", "float('-inf')"),
+        };
+        if x.is_nan() {
+            nan.to_string()
+        } else if x > 0. {
+            inf.to_string()
+        } else {
+            neg_inf.to_string()
+        }
+    }
+}
+
+/// Returns f64 as a numeric literal for `lang` in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation), rounded exactly as
+/// [format_eng] rounds it.  The output is ASCII only and always contains a decimal point or an
+/// exponent, so it is never read as an integer.  Non-finite values are rendered as the
+/// language's named constants (e.g. `f64::NAN`, `INFINITY`, `float('inf')`), as are values
+/// beyond the `float` range for [Lang::CFloat], since that is what they convert to.  Where
+/// rounding to nearest would overflow the type, e.g. [f64::MAX] at 1 significant figure, the
+/// digits are rounded toward zero instead so that the literal stays finite.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+/// - `lang` - target language
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_eng_literal, Lang};
+/// assert_eq!(format_eng_literal(4_700.1, None, Lang::Rust), "4.70e3_f64");
+/// assert_eq!(format_eng_literal(4_700.1, None, Lang::CFloat), "4.70e3f");
+/// assert_eq!(format_eng_literal(3.14, Some(1), Lang::C), "3.0");
+/// assert_eq!(format_eng_literal(f64::MAX, Some(1), Lang::Rust), "100e306_f64");
+/// assert_eq!(format_eng_literal(1e300, None, Lang::CFloat), "INFINITY");
+/// ```
+pub fn format_eng_literal(x: f64, sf: Option<usize>, lang: Lang) -> String {
+    if !x.is_finite() {
+        return lang.non_finite(x);
+    }
+    if lang == Lang::CFloat && (x as f32).is_infinite() {
+        return lang.non_finite(x);
+    }
+    let mut lit = format_eng(x, sf);
+    if lang.overflows(&lit) {
+        lit = format_eng_with(x, sf, RoundingMode::TruncateTowardZero);
+    }
+    if !lit.contains(['.', 'e']) {
+        lit.push_str(".0");
+    }
+    lit.push_str(lang.suffix());
+    lit
+}
+
+/// Same as [format_eng_literal] but writes the rounded value in positional notation with `_`
+/// separators every three digits left of the decimal point, e.g. `4_700.0_f64`.  Values with
+/// negative engineering exponents keep the exponent form.  C has no digit separators, so
+/// [Lang::C] and [Lang::CFloat] produce the same output as [format_eng_literal].
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+/// - `lang` - target language
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_eng_literal_grouped, Lang};
+/// assert_eq!(format_eng_literal_grouped(4_700.1, None, Lang::Rust), "4_700.0_f64");
+/// assert_eq!(format_eng_literal_grouped(-1.5e6, Some(2), Lang::Python), "-1_500_000.0");
+/// ```
+pub fn format_eng_literal_grouped(x: f64, sf: Option<usize>, lang: Lang) -> String {
+    let lit = format_eng_literal(x, sf, lang);
+    if !x.is_finite() || !lang.supports_separators() {
+        return lit;
+    }
+    let body = lit.trim_end_matches(lang.suffix());
+    let (mantissa, exp) = match body.split_once('e') {
+        Some((mantissa, exp)) => (mantissa, exp.parse::<i32>().unwrap()),
+        None => (body, 0),
+    };
+    if exp < 0 {
+        return lit;
+    }
+    let (sign, mantissa) = match mantissa.strip_prefix('-') {
+        Some(m) => ("-", m),
+        None => ("", mantissa),
+    };
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    // shift the decimal point right by `exp` digits
+    let mut digits = format!("{int_part}{frac_part}");
+    let n_int = int_part.len() + exp as usize;
+    while digits.len() < n_int {
+        digits.push('0');
+    }
+    let (int_digits, frac_digits) = digits.split_at(n_int);
+    let frac_digits = if frac_digits.is_empty() {
+        "0"
+    } else {
+        frac_digits
+    };
+
+    let mut grouped = String::new();
+    for (i, c) in int_digits.chars().enumerate() {
+        if i > 0 && (int_digits.len() - i) % 3 == 0 {
+            grouped.push('_');
+        }
+        grouped.push(c);
+    }
+    format!("{sign}{grouped}.{frac_digits}{}", lang.suffix())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a Rust literal emitted by this module via `str::parse`
+    fn parse_rust(lit: &str) -> f64 {
+        lit.trim_end_matches("_f64")
+            .replace('_', "")
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rust() {
+        assert_eq!(
+            format_eng_literal(4_700.1, None, Lang::Rust),
+            String::from("4.70e3_f64")
+        );
+    }
+    #[test]
+    fn test_c() {
+        assert_eq!(
+            format_eng_literal(4_700.1, None, Lang::C),
+            String::from("4.70e3")
+        );
+    }
+    #[test]
+    fn test_c_float() {
+        assert_eq!(
+            format_eng_literal(0.010, None, Lang::CFloat),
+            String::from("10.0e-3f")
+        );
+    }
+    #[test]
+    fn test_python() {
+        assert_eq!(
+            format_eng_literal(-6.022e-23, None, Lang::Python),
+            String::from("-60.2e-24")
+        );
+    }
+    #[test]
+    fn test_no_decimal_point() {
+        assert_eq!(
+            format_eng_literal(std::f64::consts::PI, Some(1), Lang::C),
+            String::from("3.0")
+        );
+        assert_eq!(
+            format_eng_literal(628_318.5, Some(1), Lang::C),
+            String::from("600e3")
+        );
+    }
+    #[test]
+    fn test_non_finite() {
+        assert_eq!(
+            format_eng_literal(f64::NAN, None, Lang::Rust),
+            String::from("f64::NAN")
+        );
+        assert_eq!(
+            format_eng_literal(f64::NEG_INFINITY, None, Lang::C),
+            String::from("-INFINITY")
+        );
+        assert_eq!(
+            format_eng_literal(f64::INFINITY, None, Lang::Python),
+            String::from("float('inf')")
+        );
+    }
+    #[test]
+    fn test_overflow() {
+        assert_eq!(
+            format_eng_literal(f64::MAX, Some(1), Lang::Rust),
+            String::from("100e306_f64")
+        );
+        assert_eq!(
+            format_eng_literal(-f64::MAX, Some(4), Lang::Python),
+            String::from("-179.7e306")
+        );
+        // digits that do not overflow are rounded to nearest as usual
+        assert_eq!(
+            format_eng_literal(f64::MAX, Some(17), Lang::C),
+            String::from("179.76931348623157e306")
+        );
+        for sf in 1..=17 {
+            for lang in [Lang::Rust, Lang::C, Lang::Python] {
+                let lit = format_eng_literal(f64::MAX, Some(sf), lang);
+                assert!(parse_rust(&lit).is_finite(), "{lit}");
+            }
+        }
+    }
+    #[test]
+    fn test_c_float_range() {
+        let max = f64::from(f32::MAX);
+        assert_eq!(
+            format_eng_literal(max, Some(7), Lang::CFloat),
+            String::from("340.2823e36f")
+        );
+        assert_eq!(
+            format_eng_literal(max, Some(3), Lang::CFloat),
+            String::from("340e36f")
+        );
+        for sf in 1..=9 {
+            let lit = format_eng_literal(-max, Some(sf), Lang::CFloat);
+            let value: f32 = lit.trim_end_matches('f').parse().unwrap();
+            assert!(value.is_finite(), "{lit}");
+        }
+        assert_eq!(
+            format_eng_literal(1e300, None, Lang::CFloat),
+            String::from("INFINITY")
+        );
+        assert_eq!(
+            format_eng_literal(-1e39, None, Lang::CFloat),
+            String::from("-INFINITY")
+        );
+        assert_eq!(
+            format_eng_literal(1e300, None, Lang::C),
+            String::from("1.00e300")
+        );
+    }
+    #[test]
+    fn test_grouped() {
+        assert_eq!(
+            format_eng_literal_grouped(4_700.1, None, Lang::Rust),
+            String::from("4_700.0_f64")
+        );
+        assert_eq!(
+            format_eng_literal_grouped(-1.5e6, Some(2), Lang::Python),
+            String::from("-1_500_000.0")
+        );
+        assert_eq!(
+            format_eng_literal_grouped(123_456.789, Some(9), Lang::Rust),
+            String::from("123_456.789_f64")
+        );
+        assert_eq!(
+            format_eng_literal_grouped(0.010, None, Lang::Rust),
+            String::from("10.0e-3_f64")
+        );
+        assert_eq!(
+            format_eng_literal_grouped(4_700.1, None, Lang::C),
+            String::from("4.70e3")
+        );
+    }
+    #[test]
+    fn test_rust_reparse() {
+        for x in [
+            0.010,
+            6.022e-23,
+            -std::f64::consts::PI * 2e5,
+            999.4,
+            1e-6,
+            123_456.789,
+            0.,
+        ] {
+            for sf in 1..=7 {
+                let expected: f64 = format_eng(x, Some(sf)).parse().unwrap();
+                assert_eq!(
+                    parse_rust(&format_eng_literal(x, Some(sf), Lang::Rust)),
+                    expected
+                );
+                assert_eq!(
+                    parse_rust(&format_eng_literal_grouped(x, Some(sf), Lang::Rust)),
+                    expected
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Command-line front end formatting numbers in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation), one per line.
+//!
+//! ```text
+//! eng_fmt [--sf N] [--si] [--parse] [NUMBER...]
+//! eng_fmt diff OLD NEW [--tol-sf N]
+//! eng_fmt --downsample N [--sf N] [--header]
+//! ```
+//!
+//! Without positional numbers, whitespace-separated numbers are read from stdin, e.g.
+//! `cat data.txt | eng_fmt --sf 5`.  With the `io` feature, `--downsample N` instead reads
+//! `timestamp,value` CSV rows from stdin and writes at most `N` `timestamp,min,mean,max` rows, as
+//! by [downsample_format_csv](eng_fmt::io::downsample_format_csv), and `--header` keeps its first
+//! line as a header.  Invalid numbers are reported on stderr and make the exit
+//! code 1 after the rest are formatted; usage errors exit with 2.
+
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use eng_fmt::{eng_diff, format_eng, format_si, parse_si, DiffItem, MAX_SIG_FIGS};
+
+#[cfg(not(feature = "io"))]
+const USAGE: &str = "\
+usage: eng_fmt [--sf N] [--si] [--parse] [NUMBER...]
+       eng_fmt diff OLD NEW [--tol-sf N]";
+#[cfg(feature = "io")]
+const USAGE: &str = "\
+usage: eng_fmt [--sf N] [--si] [--parse] [NUMBER...]
+       eng_fmt diff OLD NEW [--tol-sf N]
+       eng_fmt --downsample N [--sf N] [--header]";
+
+/// What each input token is turned into
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    /// Engineering notation at the given significant figures
+    Eng(usize),
+    /// SI prefixes at the given significant figures
+    Si(usize),
+    /// Value of engineering notation or SI prefix text
+    Parse,
+}
+
+/// Failure of a whole invocation, as opposed to one bad token
+#[derive(Debug)]
+enum CliError {
+    Usage(String),
+    Io(io::Error),
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        CliError::Io(err)
+    }
+}
+
+/// Returns the value of flag `name` at `args[i]`, parsed as significant figures
+fn sf_arg(args: &[String], i: usize, name: &str) -> Result<usize, CliError> {
+    let value = args
+        .get(i)
+        .ok_or_else(|| CliError::Usage(format!("{name} needs a value")))?;
+    match value.parse() {
+        Ok(sf @ 1..=MAX_SIG_FIGS) => Ok(sf),
+        _ => Err(CliError::Usage(format!(
+            "{name} must be from 1 to {MAX_SIG_FIGS}, not {value:?}"
+        ))),
+    }
+}
+
+/// Returns the value of `--downsample` at `args[i]`, parsed as a positive number of rows
+#[cfg(feature = "io")]
+fn buckets_arg(args: &[String], i: usize) -> Result<usize, CliError> {
+    let value = args
+        .get(i)
+        .ok_or_else(|| CliError::Usage(String::from("--downsample needs a value")))?;
+    match value.parse() {
+        Ok(n @ 1..) => Ok(n),
+        _ => Err(CliError::Usage(format!(
+            "--downsample must be a positive number of rows, not {value:?}"
+        ))),
+    }
+}
+
+/// Returns the output line for `token`, or the message for an invalid one
+fn convert(token: &str, mode: Mode) -> Result<String, String> {
+    let invalid = |err: &dyn std::fmt::Display| format!("invalid number {token:?}: {err}");
+    match mode {
+        Mode::Eng(sf) => {
+            let x: f64 = token.parse().map_err(|err| invalid(&err))?;
+            Ok(format_eng(x, Some(sf)))
+        }
+        Mode::Si(sf) => {
+            let x: f64 = token.parse().map_err(|err| invalid(&err))?;
+            Ok(format_si(x, Some(sf)))
+        }
+        Mode::Parse => parse_si(token)
+            .map(|x| format!("{x:?}"))
+            .map_err(|err| invalid(&err)),
+    }
+}
+
+/// Converts every token of `tokens`, writing results to `stdout` and invalid tokens to
+/// `stderr`, and returns whether all were valid
+fn convert_all<'a>(
+    tokens: impl Iterator<Item = &'a str>,
+    mode: Mode,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+) -> io::Result<bool> {
+    let mut all_valid = true;
+    for token in tokens {
+        match convert(token, mode) {
+            Ok(line) => writeln!(stdout, "{line}")?,
+            Err(message) => {
+                writeln!(stderr, "eng_fmt: {message}")?;
+                all_valid = false;
+            }
+        }
+    }
+    Ok(all_valid)
+}
+
+/// Runs `eng_fmt diff` with the arguments after `diff`, returning whether the files match
+fn diff(args: &[String], stdout: &mut impl Write) -> Result<bool, CliError> {
+    let mut tol_sf = 3;
+    let mut paths = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--tol-sf" => {
+                i += 1;
+                tol_sf = sf_arg(args, i, "--tol-sf")?;
+            }
+            path => paths.push(path),
+        }
+        i += 1;
+    }
+    let [old_path, new_path] = paths[..] else {
+        return Err(CliError::Usage(String::from(
+            "diff needs OLD and NEW files",
+        )));
+    };
+    let read = |path: &str| {
+        std::fs::read_to_string(path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{path}: {err}")))
+    };
+    let (old, new) = (read(old_path)?, read(new_path)?);
+    let items = eng_diff(&old, &new, tol_sf)
+        .map_err(|err| CliError::Io(io::Error::new(io::ErrorKind::InvalidData, err)))?;
+    for item in &items {
+        match item {
+            DiffItem::Value {
+                line,
+                column,
+                old,
+                new,
+                rel_change,
+            } => writeln!(
+                stdout,
+                "{line}:{column}: {old} -> {new} ({:+.2} %)",
+                rel_change * 100.
+            )?,
+            DiffItem::Text { line, old, new } => writeln!(stdout, "{line}: {old:?} -> {new:?}")?,
+        }
+    }
+    Ok(items.is_empty())
+}
+
+/// Runs the command line `args`, without the program name, and returns whether it succeeded
+fn run(
+    args: &[String],
+    mut stdin: impl BufRead,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+) -> Result<bool, CliError> {
+    if args.first().map(String::as_str) == Some("diff") {
+        return diff(&args[1..], stdout);
+    }
+    let (mut sf, mut si, mut parse) = (3, false, false);
+    #[cfg(feature = "io")]
+    let (mut downsample, mut header) = (None, false);
+    let mut numbers = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--sf" => {
+                i += 1;
+                sf = sf_arg(args, i, "--sf")?;
+            }
+            "--si" => si = true,
+            "--parse" => parse = true,
+            #[cfg(feature = "io")]
+            "--downsample" => {
+                i += 1;
+                downsample = Some(buckets_arg(args, i)?);
+            }
+            #[cfg(feature = "io")]
+            "--header" => header = true,
+            "-h" | "--help" => {
+                writeln!(stdout, "{USAGE}")?;
+                return Ok(true);
+            }
+            // negative numbers are values, not flags
+            flag if flag.starts_with("--") => {
+                return Err(CliError::Usage(format!("unknown option {flag:?}")));
+            }
+            number => numbers.push(number),
+        }
+        i += 1;
+    }
+    #[cfg(feature = "io")]
+    if let Some(n_buckets) = downsample {
+        if !numbers.is_empty() || si || parse {
+            return Err(CliError::Usage(String::from(
+                "--downsample reads CSV from stdin and takes only --sf and --header",
+            )));
+        }
+        let opts = eng_fmt::io::DownsampleOptions {
+            sf: Some(sf),
+            has_header: header,
+        };
+        eng_fmt::io::downsample_format_csv(stdin, stdout, n_buckets, opts)?;
+        return Ok(true);
+    }
+    let mode = match (parse, si) {
+        (true, _) => Mode::Parse,
+        (false, true) => Mode::Si(sf),
+        (false, false) => Mode::Eng(sf),
+    };
+    if !numbers.is_empty() {
+        return Ok(convert_all(numbers.into_iter(), mode, stdout, stderr)?);
+    }
+    let mut input = String::new();
+    stdin.read_to_string(&mut input)?;
+    Ok(convert_all(input.split_whitespace(), mode, stdout, stderr)?)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut stderr = io::stderr().lock();
+    match run(
+        &args,
+        io::stdin().lock(),
+        &mut io::stdout().lock(),
+        &mut stderr,
+    ) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(CliError::Usage(message)) => {
+            let _ = writeln!(stderr, "eng_fmt: {message}\n{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(CliError::Io(err)) => {
+            let _ = writeln!(stderr, "eng_fmt: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Module containing the integer exponent math shared by every code path in this crate.
+//!
+//! Rust's `/` and `%` truncate toward zero, so `-4 / 3 == -1` and `-4 % 3 == -1`, which is the
+//! wrong answer for picking the engineering exponent of a value like `1e-4`.  These helpers
+//! round toward negative infinity instead, so callers never need sign-dependent branches.
+
+/// Returns `a / b` rounded toward negative infinity.
+///
+/// # Panics
+/// If `b == 0` or if the result overflows (`i32::MIN / -1`).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::math::div_floor_i32;
+/// assert_eq!(div_floor_i32(7, 3), 2);
+/// assert_eq!(div_floor_i32(-7, 3), -3);
+/// assert_eq!(div_floor_i32(-6, 3), -2);
+/// ```
+pub fn div_floor_i32(a: i32, b: i32) -> i32 {
+    let q = a / b;
+    if a % b != 0 && ((a < 0) != (b < 0)) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+/// Returns the remainder of [div_floor_i32], which always has the sign of `b` (or is 0).
+///
+/// # Panics
+/// If `b == 0`.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::math::mod_floor_i32;
+/// assert_eq!(mod_floor_i32(7, 3), 1);
+/// assert_eq!(mod_floor_i32(-7, 3), 2);
+/// assert_eq!(mod_floor_i32(7, -3), -2);
+/// ```
+pub fn mod_floor_i32(a: i32, b: i32) -> i32 {
+    let r = a.wrapping_rem(b);
+    if r != 0 && ((r < 0) != (b < 0)) {
+        r + b
+    } else {
+        r
+    }
+}
+
+/// Returns the largest multiple of `step` that is less than or equal to `exp_sci`, i.e. the
+/// exponent that leaves between 1 and `step` digits left of the decimal point for a value whose
+/// leading digit is at power of ten `exp_sci`.
+///
+/// # Arguments
+/// - `exp_sci` - scientific notation exponent, i.e. `floor(log10(|x|))`
+/// - `step` - exponent step, 3 for engineering notation
+///
+/// # Panics
+/// If `step == 0` or if the result would be less than `i32::MIN`.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::math::eng_exponent_from_sci;
+/// assert_eq!(eng_exponent_from_sci(4, 3), 3);
+/// assert_eq!(eng_exponent_from_sci(-4, 3), -6);
+/// assert_eq!(eng_exponent_from_sci(-3, 3), -3);
+/// ```
+pub fn eng_exponent_from_sci(exp_sci: i32, step: u8) -> i32 {
+    assert!(
+        step >= 1,
+        "`eng_exponent_from_sci` arg `step` must be at least 1."
+    );
+    exp_sci - mod_floor_i32(exp_sci, step as i32)
+}
+
+include!(concat!(env!("OUT_DIR"), "/pow10_ceil.rs"));
+
+/// Smallest decade of any nonzero f64, i.e. the `k` of `POW10_CEIL[0]`
+const POW10_MIN_EXP: i32 = -324;
+
+/// Returns the smallest f64 greater than or equal to `10^k` for `k` in `-324..=309`
+fn pow10_ceil(k: i32) -> f64 {
+    POW10_CEIL[(k - POW10_MIN_EXP) as usize]
+}
+
+/// Returns the multiple-of-`step` exponent for `x` before any rounding, i.e.
+/// [eng_exponent_from_sci] of the exact decade of `|x|`.  Unlike `floor(log10(|x|))`, the
+/// decade is exact even for values within an ULP of a power of ten.  Returns 0 for zero and
+/// non-finite values.  Rounding to a number of significant figures can carry into the next
+/// step, so a formatted value may show an exponent one `step` higher.
+///
+/// # Arguments
+/// - `x` - value whose exponent is needed
+/// - `step` - exponent step, 3 for engineering notation
+///
+/// # Examples
+/// ```
+/// use eng_fmt::math::eng_exponent;
+/// assert_eq!(eng_exponent(6.022e-23, 3), -24);
+/// assert_eq!(eng_exponent(999.9, 3), 0);
+/// assert_eq!(eng_exponent(-1e3, 3), 3);
+/// ```
+pub fn eng_exponent(x: f64, step: u8) -> i32 {
+    if x == 0. || !x.is_finite() {
+        return 0;
+    }
+    eng_exponent_from_sci(decade(x), step)
+}
+
+/// Returns the exact power of ten of the leading digit of finite, nonzero `x`
+pub(crate) fn decade(x: f64) -> i32 {
+    let x = x.abs();
+    let mut exp_sci = x.log10().floor() as i32;
+    // `log10` is not correctly rounded, so it can be off by one next to a power of ten
+    if x < pow10_ceil(exp_sci) {
+        exp_sci -= 1;
+    } else if x >= pow10_ceil(exp_sci + 1) {
+        exp_sci += 1;
+    }
+    exp_sci
+}
+
+/// Fills `out` with [eng_exponent] of every element of `values`, matching it exactly.  The
+/// loop is branch-free: it takes the binary exponent from the bit pattern, approximates the
+/// decade with an integer multiply, and corrects it with one table comparison, so it
+/// vectorizes well and avoids `log10` entirely.
+///
+/// # Arguments
+/// - `values` - values whose exponents are needed
+/// - `step` - exponent step, 3 for engineering notation
+/// - `out` - destination, same length as `values`
+///
+/// # Panics
+/// If `step == 0` or `values` and `out` differ in length.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::math::eng_exponents_batch;
+/// let mut out = [0; 4];
+/// eng_exponents_batch(&[6.022e-23, 999.9, -1e3, 0.], 3, &mut out);
+/// assert_eq!(out, [-24, 0, 3, 0]);
+/// ```
+pub fn eng_exponents_batch(values: &[f64], step: u8, out: &mut [i32]) {
+    assert!(
+        step >= 1,
+        "`eng_exponents_batch` arg `step` must be at least 1."
+    );
+    assert_eq!(
+        values.len(),
+        out.len(),
+        "`eng_exponents_batch` args `values` and `out` must have equal length."
+    );
+    // engineering exponent for every decade, so the loop needs no division
+    let mut exp_eng_by_decade = [0; POW10_CEIL.len()];
+    for (i, exp_eng) in exp_eng_by_decade.iter_mut().enumerate() {
+        *exp_eng = eng_exponent_from_sci(i as i32 + POW10_MIN_EXP, step);
+    }
+    for (x, exp) in values.iter().zip(out.iter_mut()) {
+        let bits = x.to_bits() & !(1 << 63);
+        let biased = (bits >> 52) as i32;
+        // subnormals take the binary exponent from their leading significand bit
+        let exp_bin = if biased == 0 {
+            bits.max(1).ilog2() as i32 - 1074
+        } else {
+            biased - 1023
+        };
+        // floor(exp_bin * log10(2)), exact over the whole f64 exponent range
+        let approx = (exp_bin * 78_913) >> 18;
+        // |x| is in [2^exp_bin, 2^(exp_bin + 1)), so its decade is `approx` or `approx + 1`
+        let i_decade = (approx - POW10_MIN_EXP) as usize;
+        let i_decade = i_decade + usize::from(f64::from_bits(bits) >= POW10_CEIL[i_decade + 1]);
+        *exp = if bits == 0 || biased == 0x7ff {
+            0
+        } else {
+            exp_eng_by_decade[i_decade]
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RANGE: std::ops::RangeInclusive<i32> = -20_000..=20_000;
+
+    #[test]
+    fn test_div_mod_floor_exhaustive() {
+        for b in (-9..=9).filter(|b| *b != 0) {
+            for a in RANGE {
+                let q = div_floor_i32(a, b);
+                let r = mod_floor_i32(a, b);
+                assert_eq!(q, (a as f64 / b as f64).floor() as i32, "a: {a}, b: {b}");
+                assert_eq!(q * b + r, a, "a: {a}, b: {b}");
+                if b > 0 {
+                    assert!((0..b).contains(&r), "a: {a}, b: {b}");
+                } else {
+                    assert!((b + 1..=0).contains(&r), "a: {a}, b: {b}");
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_div_mod_floor_extremes() {
+        assert_eq!(div_floor_i32(i32::MIN, 3), -715_827_883);
+        assert_eq!(mod_floor_i32(i32::MIN, 3), 1);
+        assert_eq!(div_floor_i32(i32::MAX, 3), 715_827_882);
+        assert_eq!(mod_floor_i32(i32::MAX, 3), 1);
+        assert_eq!(mod_floor_i32(i32::MIN, -1), 0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_div_floor_zero() {
+        div_floor_i32(1, 0);
+    }
+    #[test]
+    fn test_eng_exponent_from_sci_exhaustive() {
+        for step in 1..=9_u8 {
+            for exp_sci in RANGE {
+                let exp_eng = eng_exponent_from_sci(exp_sci, step);
+                assert_eq!(mod_floor_i32(exp_eng, step as i32), 0);
+                assert!(
+                    (0..step as i32).contains(&(exp_sci - exp_eng)),
+                    "exp_sci: {exp_sci}, step: {step}"
+                );
+            }
+        }
+    }
+    #[test]
+    fn test_eng_exponent_from_sci_table() {
+        let table = [
+            (-7, -9),
+            (-6, -6),
+            (-5, -6),
+            (-4, -6),
+            (-3, -3),
+            (-2, -3),
+            (-1, -3),
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 3),
+            (4, 3),
+        ];
+        for (exp_sci, exp_eng) in table {
+            assert_eq!(eng_exponent_from_sci(exp_sci, 3), exp_eng);
+        }
+    }
+    #[test]
+    fn test_eng_exponent_from_sci_extremes() {
+        assert_eq!(eng_exponent_from_sci(i32::MIN + 2, 3), i32::MIN + 2);
+        assert_eq!(eng_exponent_from_sci(i32::MAX, 3), i32::MAX - 1);
+    }
+
+    /// Returns the exact decade of nonzero, finite `x` from its exact decimal expansion
+    fn exact_decade(x: f64) -> i32 {
+        format!("{:.767e}", x.abs())
+            .split_once('e')
+            .unwrap()
+            .1
+            .parse()
+            .unwrap()
+    }
+
+    /// Returns values next to every power of ten plus special and random values
+    fn exponent_test_values() -> Vec<f64> {
+        let mut values = vec![
+            0.,
+            -0.,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MAX,
+            f64::MIN,
+            f64::MIN_POSITIVE,
+            f64::from_bits(1),
+            f64::from_bits(0x000f_ffff_ffff_ffff),
+        ];
+        for k in -324..=308 {
+            let p: f64 = format!("1e{k}").parse().unwrap();
+            values.extend([p.next_down(), p, p.next_up(), -p]);
+        }
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..100_000 {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            values.push(f64::from_bits(state));
+        }
+        values
+    }
+
+    #[test]
+    fn test_eng_exponent_exact() {
+        for x in exponent_test_values() {
+            if x == 0. || !x.is_finite() {
+                assert_eq!(eng_exponent(x, 3), 0);
+            } else {
+                assert_eq!(
+                    eng_exponent(x, 3),
+                    eng_exponent_from_sci(exact_decade(x), 3),
+                    "x: {x:e}"
+                );
+            }
+        }
+    }
+    #[test]
+    fn test_eng_exponents_batch_matches_scalar() {
+        let values = exponent_test_values();
+        for step in [1, 2, 3, 4, 6] {
+            let mut out = vec![i32::MIN; values.len()];
+            eng_exponents_batch(&values, step, &mut out);
+            for (x, exp) in values.iter().zip(&out) {
+                assert_eq!(*exp, eng_exponent(*x, step), "x: {x:e}, step: {step}");
+            }
+        }
+    }
+    #[test]
+    #[should_panic(expected = "must have equal length")]
+    fn test_eng_exponents_batch_length_mismatch() {
+        eng_exponents_batch(&[1., 2.], 3, &mut [0]);
+    }
+}
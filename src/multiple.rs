@@ -0,0 +1,199 @@
+//! Module for rounding to a multiple such as "nearest 50" before formatting in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::exact::{cmp_abs_decimal, write_eng};
+use crate::format_eng;
+
+/// Largest quotient `|x / multiple|` for which rounding to the multiple is meaningful
+const MAX_QUOTIENT: f64 = (1_u64 << 53) as f64;
+
+/// Error returned by [format_eng_to_multiple]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum MultipleError {
+    /// Multiple is zero, negative, or not finite
+    InvalidMultiple(f64),
+    /// Value is 2^53 or more multiples from zero, so the multiple is below its precision
+    MultipleTooSmall { x: f64, multiple: f64 },
+}
+
+impl fmt::Display for MultipleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultipleError::InvalidMultiple(multiple) => {
+                write!(f, "multiple {multiple} is not finite and positive")
+            }
+            MultipleError::MultipleTooSmall { x, multiple } => {
+                write!(f, "multiple {multiple} is below the precision of {x}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MultipleError {}
+
+/// Returns `x` rounded half away from zero to the nearest multiple of `multiple` and formatted
+/// in [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation) with exactly
+/// enough digits to show the multiple, e.g. `1237.` to the nearest `50.` is `"1.25e3"` (tens
+/// digit shown) and `1.1` to the nearest `0.25` is `"1.00"`.  `multiple` is taken to be the
+/// decimal of its shortest representation, so `0.1` means one tenth.  Ties are decided from the
+/// exact value of `x`, so `0.35` to the nearest `0.1` is `"300e-3"` because 0.35 is stored as
+/// 0.34999999999999997780.  Non-finite `x` is formatted with [format_eng].
+///
+/// # Arguments
+/// - `x` - value to be rounded and formatted
+/// - `multiple` - positive step to round to, e.g. `50.`, `0.25`, or `2.5e3`
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_to_multiple;
+/// assert_eq!(format_eng_to_multiple(1237., 50.).unwrap(), "1.25e3");
+/// assert_eq!(format_eng_to_multiple(-0.6, 0.25).unwrap(), "-500e-3");
+/// assert!(format_eng_to_multiple(1., 0.).is_err());
+/// ```
+pub fn format_eng_to_multiple(x: f64, multiple: f64) -> Result<String, MultipleError> {
+    if !(multiple.is_finite() && multiple > 0.) {
+        return Err(MultipleError::InvalidMultiple(multiple));
+    }
+    if !x.is_finite() {
+        return Ok(format_eng(x, None));
+    }
+    // `multiple` is `m_digits * 10^place` per its shortest representation
+    let repr = format!("{multiple:e}");
+    let (mantissa, exp) = repr.split_once('e').unwrap();
+    let m_digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let place = exp.parse::<i32>().unwrap() - m_digits.len() as i32 + 1;
+    let m_digits: u128 = m_digits.parse().unwrap();
+
+    let quotient = x.abs() / multiple;
+    if quotient >= MAX_QUOTIENT {
+        return Err(MultipleError::MultipleTooSmall { x, multiple });
+    }
+    let floor = quotient.floor();
+    let n_multiples = if (quotient - floor - 0.5).abs() <= quotient.max(1.) / (1_u64 << 40) as f64 {
+        // too close to a tie for the division, so compare with `(floor + 1/2) * multiple`
+        let tie = (2 * floor as u128 + 1) * m_digits * 5;
+        match cmp_abs_decimal(x, &tie.to_string(), place - 1) {
+            Ordering::Less => floor as u128,
+            _ => floor as u128 + 1,
+        }
+    } else {
+        quotient.round() as u128
+    };
+
+    if n_multiples == 0 {
+        return Ok(format_eng(0., Some(1 + (-place).max(0) as usize)));
+    }
+    let digits = (n_multiples * m_digits).to_string();
+    let exp_sci = place + digits.len() as i32 - 1;
+    Ok(write_eng(x < 0., digits.as_bytes(), exp_sci))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_50() {
+        assert_eq!(
+            format_eng_to_multiple(1237., 50.),
+            Ok(String::from("1.25e3"))
+        );
+        assert_eq!(
+            format_eng_to_multiple(1224., 50.),
+            Ok(String::from("1.20e3"))
+        );
+    }
+    #[test]
+    fn test_nearest_250() {
+        assert_eq!(
+            format_eng_to_multiple(1374., 250.),
+            Ok(String::from("1.25e3"))
+        );
+        assert_eq!(
+            format_eng_to_multiple(1376., 250.),
+            Ok(String::from("1.50e3"))
+        );
+    }
+    #[test]
+    fn test_fractional_multiple() {
+        assert_eq!(format_eng_to_multiple(1.1, 0.25), Ok(String::from("1.00")));
+        assert_eq!(
+            format_eng_to_multiple(12.4, 0.25),
+            Ok(String::from("12.50"))
+        );
+    }
+    #[test]
+    fn test_large_multiple() {
+        assert_eq!(
+            format_eng_to_multiple(6e3, 2.5e3),
+            Ok(String::from("5.0e3"))
+        );
+        assert_eq!(
+            format_eng_to_multiple(1.3e6, 2.5e3),
+            Ok(String::from("1.3000e6"))
+        );
+    }
+    #[test]
+    fn test_crosses_engineering_decade() {
+        assert_eq!(
+            format_eng_to_multiple(998., 5.),
+            Ok(String::from("1.000e3"))
+        );
+        assert_eq!(
+            format_eng_to_multiple(999.9, 0.25),
+            Ok(String::from("1.00000e3"))
+        );
+        assert_eq!(
+            format_eng_to_multiple(0.0996, 0.002),
+            Ok(String::from("100e-3"))
+        );
+    }
+    #[test]
+    fn test_exact_ties() {
+        assert_eq!(
+            format_eng_to_multiple(1225., 50.),
+            Ok(String::from("1.25e3"))
+        );
+        assert_eq!(
+            format_eng_to_multiple(0.375, 0.25),
+            Ok(String::from("500e-3"))
+        );
+        assert_eq!(
+            format_eng_to_multiple(-0.375, 0.25),
+            Ok(String::from("-500e-3"))
+        );
+        // stored just below the tie
+        assert_eq!(
+            format_eng_to_multiple(0.35, 0.1),
+            Ok(String::from("300e-3"))
+        );
+    }
+    #[test]
+    fn test_rounds_to_zero() {
+        assert_eq!(format_eng_to_multiple(0.1, 0.25), Ok(String::from("0.00")));
+        assert_eq!(format_eng_to_multiple(-20., 50.), Ok(String::from("0")));
+    }
+    #[test]
+    fn test_invalid_multiple() {
+        for multiple in [0., -50., f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                format_eng_to_multiple(1., multiple),
+                Err(MultipleError::InvalidMultiple(_))
+            ));
+        }
+    }
+    #[test]
+    fn test_multiple_too_small() {
+        assert_eq!(
+            format_eng_to_multiple(1e20, 1e-3),
+            Err(MultipleError::MultipleTooSmall {
+                x: 1e20,
+                multiple: 1e-3
+            })
+        );
+    }
+}
@@ -0,0 +1,855 @@
+//! Module for parsing text in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::eng::pad_eng;
+use crate::error::MAX_SIG_FIGS;
+use crate::formatter::DecimalMark;
+use crate::partial::{validate_partial_eng, Partial, PartialOptions};
+use crate::prefix::{iec_power, si_exponent};
+
+/// Error returned when parsing engineering notation
+///
+/// Like every error enum in this crate, it is `#[non_exhaustive]` so that variants can be added
+/// without a breaking release, and matches on it outside this crate need a wildcard arm:
+/// ```compile_fail
+/// use eng_fmt::ParseEngError;
+/// fn describe(e: ParseEngError) -> &'static str {
+///     match e {
+///         ParseEngError::Empty => "empty",
+///         ParseEngError::Incomplete => "incomplete",
+///         ParseEngError::InvalidChar { .. } => "invalid",
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseEngError {
+    /// Text is empty or whitespace
+    Empty,
+    /// Text ends before the number is complete, e.g. `"1.0e"` or `"-"`
+    Incomplete,
+    /// Text has a character that cannot appear at byte offset `at`
+    InvalidChar { at: usize, found: char },
+    /// Exponent, including any SI prefix, does not fit in an `i32`
+    ExponentOutOfRange,
+    /// Character at byte offset `at` after the number is not an SI prefix, e.g. `'K'` or `'c'`
+    UnknownPrefix { at: usize, found: char },
+}
+
+impl fmt::Display for ParseEngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseEngError::Empty => write!(f, "cannot parse number from empty string"),
+            ParseEngError::Incomplete => write!(f, "number ends before it is complete"),
+            ParseEngError::InvalidChar { at, found } => {
+                write!(f, "invalid character {found:?} at byte {at}")
+            }
+            ParseEngError::ExponentOutOfRange => write!(f, "exponent is out of range"),
+            ParseEngError::UnknownPrefix { at, found } => {
+                write!(f, "unknown SI prefix {found:?} at byte {at}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseEngError {}
+
+/// Rule for whether trailing zeros of a number without a decimal point, like the zeros in
+/// `"100"`, are significant
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TrailingZeros {
+    /// Every digit shown is significant, so `"100"` has 3 significant figures.  This matches
+    /// what [format_eng](crate::format_eng) emits whenever there are at least as many
+    /// significant figures as digits left of the decimal point, e.g. `"250e-3"` at 3 but not
+    /// `"30e-3"` at 1.
+    #[default]
+    Significant,
+    /// Trailing zeros are only significant after a decimal point, so `"100"` has 1 significant
+    /// figure and `"100."` has 3
+    Insignificant,
+}
+
+/// Parses text in engineering or plain notation, accepting everything
+/// [format_eng](crate::format_eng) emits, such as `"10.0e-3"`, `"-314e-3"`, `"3.14"`, `"NaN"`,
+/// and `"-inf"`, plus an uppercase `E`, a leading `+`, and surrounding whitespace.  Malformed text gives an error saying where it
+/// goes wrong, e.g. [ParseEngError::Incomplete] for `"1.0e"` and [ParseEngError::InvalidChar]
+/// for `"e3"` or `"--1"`.
+///
+/// # Arguments
+/// - `s` - text to be parsed
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{parse_eng, ParseEngError};
+/// assert_eq!(parse_eng("10.0e-3"), Ok(0.01));
+/// assert_eq!(parse_eng(" +2.5E3 "), Ok(2500.));
+/// assert_eq!(parse_eng("1.0e"), Err(ParseEngError::Incomplete));
+/// ```
+pub fn parse_eng(s: &str) -> Result<f64, ParseEngError> {
+    match s.trim() {
+        "NaN" => Ok(f64::NAN),
+        "inf" | "+inf" => Ok(f64::INFINITY),
+        "-inf" => Ok(f64::NEG_INFINITY),
+        _ => parse_number(s),
+    }
+}
+
+/// Same as [parse_eng] but reads the decimal point as `decimal_mark`, so with
+/// [DecimalMark::Comma] `"10,0e-3"` is 0.01, as written by an [EngFormatter](crate::EngFormatter)
+/// with that mark.  The other mark is then an invalid character, so `"1.5"` is an error rather
+/// than being misread.
+///
+/// # Arguments
+/// - `s` - text to be parsed
+/// - `decimal_mark` - character separating the integer and fractional digits
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{parse_eng_with_mark, DecimalMark, ParseEngError};
+/// assert_eq!(parse_eng_with_mark("-4,70e-6", DecimalMark::Comma), Ok(-4.7e-6));
+/// assert_eq!(
+///     parse_eng_with_mark("4.70e-6", DecimalMark::Comma),
+///     Err(ParseEngError::InvalidChar { at: 1, found: '.' })
+/// );
+/// ```
+pub fn parse_eng_with_mark(s: &str, decimal_mark: DecimalMark) -> Result<f64, ParseEngError> {
+    match decimal_mark {
+        DecimalMark::Point => parse_eng(s),
+        DecimalMark::Comma => {
+            if let Some(at) = s.find('.') {
+                return Err(ParseEngError::InvalidChar { at, found: '.' });
+            }
+            // every `.` parsed came from a `,`, so errors point at that
+            parse_eng(&s.replace(',', ".")).map_err(|err| match err {
+                ParseEngError::InvalidChar { at, found: '.' } => {
+                    ParseEngError::InvalidChar { at, found: ',' }
+                }
+                err => err,
+            })
+        }
+    }
+}
+
+/// Same as [parse_eng_with_precision_rule] with [TrailingZeros::Significant].
+///
+/// # Examples
+/// ```
+/// use eng_fmt::parse_eng_with_precision;
+/// assert_eq!(parse_eng_with_precision("2.50 k"), Ok((2.5e3, 3)));
+/// assert_eq!(parse_eng_with_precision("2.5k"), Ok((2.5e3, 2)));
+/// assert_eq!(parse_eng_with_precision("10.0e-3"), Ok((0.01, 3)));
+/// ```
+pub fn parse_eng_with_precision(s: &str) -> Result<(f64, usize), ParseEngError> {
+    parse_eng_with_precision_rule(s, TrailingZeros::default())
+}
+
+/// Parses a number in engineering or plain notation with an optional SI prefix symbol (e.g.
+/// `"2.50 k"`, `"100n"`, with `u` accepted for micro), returning the value and the number of
+/// significant figures the text communicates.  Leading zeros are never significant.  Zero has as
+/// many significant figures as digits shown, so `"0.00"` has 3 like
+/// [format_eng](crate::format_eng) emits it.
+///
+/// # Arguments
+/// - `s` - text to be parsed
+/// - `rule` - whether trailing zeros without a decimal point are significant
+pub fn parse_eng_with_precision_rule(
+    s: &str,
+    rule: TrailingZeros,
+) -> Result<(f64, usize), ParseEngError> {
+    let (number, prefix_exp) = split_prefix(s);
+    let value = parse_number(number)?;
+    let mantissa = number.trim().split(['e', 'E']).next().unwrap();
+    Ok((
+        apply_si_exponent(number, value, prefix_exp)?,
+        significant_figures(mantissa, rule),
+    ))
+}
+
+/// Decimal number text broken into the parts that determine its value and precision
+pub(crate) struct DecimalText {
+    /// True if the text has a leading `-`
+    pub(crate) negative: bool,
+    /// Digits shown, without leading zeros, so empty for zero
+    pub(crate) digits: String,
+    /// Power of ten of the last digit shown, including exponent and SI prefix
+    pub(crate) place: i32,
+    /// True if the mantissa has a decimal point
+    pub(crate) has_point: bool,
+}
+
+/// Breaks number text accepted by [parse_eng_with_precision] into a [DecimalText]
+pub(crate) fn decimal_text(s: &str) -> Result<DecimalText, ParseEngError> {
+    let (number, prefix_exp) = split_prefix(s);
+    parse_number(number)?;
+    let number = number.trim();
+    let (mantissa, exp) = match number.split_once(['e', 'E']) {
+        Some((mantissa, exp)) => (
+            mantissa,
+            exp.parse::<i32>()
+                .map_err(|_| ParseEngError::ExponentOutOfRange)?,
+        ),
+        None => (number, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let int = int.trim_start_matches(['+', '-']);
+    let place = i32::try_from(frac.len())
+        .ok()
+        .and_then(|n_frac| exp.checked_sub(n_frac)?.checked_add(prefix_exp))
+        .ok_or(ParseEngError::ExponentOutOfRange)?;
+    Ok(DecimalText {
+        negative: mantissa.starts_with('-'),
+        digits: format!("{int}{frac}").trim_start_matches('0').to_string(),
+        place,
+        has_point: mantissa.contains('.'),
+    })
+}
+
+/// Returns `value`, parsed from valid number text `number`, times `10^prefix_exp`, rounded once,
+/// or [ParseEngError::ExponentOutOfRange] if the exponent of `number`, or that plus
+/// `prefix_exp`, does not fit in an `i32`
+fn apply_si_exponent(number: &str, value: f64, prefix_exp: i32) -> Result<f64, ParseEngError> {
+    if prefix_exp == 0 {
+        return Ok(value);
+    }
+    let number = number.trim();
+    let (mantissa, exp) = match number.split_once(['e', 'E']) {
+        Some((mantissa, exp)) => (
+            mantissa,
+            exp.parse::<i32>()
+                .map_err(|_| ParseEngError::ExponentOutOfRange)?,
+        ),
+        None => (number, 0),
+    };
+    let exp = exp
+        .checked_add(prefix_exp)
+        .ok_or(ParseEngError::ExponentOutOfRange)?;
+    // reparse so the prefix does not add a second rounding
+    parse_number(&format!("{mantissa}e{exp}"))
+}
+
+/// Parses a number with an optional [SI prefix](https://en.wikipedia.org/wiki/Metric_prefix)
+/// symbol from yocto to yotta, e.g. `"4.7k"`, `"100n"`, `"2.2M"`, or `"10 µ"`, accepting
+/// everything [format_si](crate::format_si) emits.  Whitespace may separate the number from the
+/// prefix, prefixes are case-sensitive so `m` is milli and `M` mega, and `u` and `μ` (U+03BC
+/// GREEK SMALL LETTER MU) are accepted for micro.  Any other character after the number is
+/// [ParseEngError::UnknownPrefix], and anything after the prefix is
+/// [ParseEngError::InvalidChar]; use [parse_si_unit] for text with a unit.
+///
+/// # Arguments
+/// - `s` - text to be parsed
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{parse_si, ParseEngError};
+/// assert_eq!(parse_si("4.7k"), Ok(4700.));
+/// assert_eq!(parse_si("100n"), Ok(1e-7));
+/// assert_eq!(parse_si("2.2 M"), Ok(2.2e6));
+/// assert_eq!(parse_si("10u"), Ok(1e-5));
+/// assert_eq!(parse_si("4.7K"), Err(ParseEngError::UnknownPrefix { at: 3, found: 'K' }));
+/// ```
+pub fn parse_si(s: &str) -> Result<f64, ParseEngError> {
+    let (value, has_prefix, unit) = split_si(s)?;
+    let at = unit.as_ptr() as usize - s.as_ptr() as usize;
+    match unit.chars().next() {
+        None => Ok(value),
+        Some(found) if has_prefix => Err(ParseEngError::InvalidChar { at, found }),
+        Some(found) => Err(ParseEngError::UnknownPrefix { at, found }),
+    }
+}
+
+/// Same as [parse_si] but also accepts a unit after the prefix, e.g. `"4.7 kΩ"`, and returns
+/// it, with surrounding whitespace removed, along with the value in the unprefixed unit.  The
+/// first character after the number is taken as a prefix whenever it is one, so a unit that
+/// starts with a prefix symbol needs a prefix of its own: `"1 mm"` is 0.001 m, `"5 m"` is
+/// 0.005 with no unit, and `"2 Pa"` is read as 2 peta-`a`.
+///
+/// # Arguments
+/// - `s` - text to be parsed
+///
+/// # Examples
+/// ```
+/// use eng_fmt::parse_si_unit;
+/// assert_eq!(parse_si_unit("4.70 kV"), Ok((4700., "V")));
+/// assert_eq!(parse_si_unit("1 mm"), Ok((1e-3, "m")));
+/// assert_eq!(parse_si_unit("3.25 Hz"), Ok((3.25, "Hz")));
+/// assert_eq!(parse_si_unit("1.50e27 V"), Ok((1.5e27, "V")));
+/// ```
+pub fn parse_si_unit(s: &str) -> Result<(f64, &str), ParseEngError> {
+    let (value, _, unit) = split_si(s)?;
+    Ok((value, unit))
+}
+
+/// Parses the number and optional SI prefix at the start of `s` for [parse_si_unit], returning
+/// the value, whether there was a prefix, and the trimmed rest of `s`
+fn split_si(s: &str) -> Result<(f64, bool, &str), ParseEngError> {
+    if let Ok(value) = parse_eng(s) {
+        return Ok((value, false, &s[s.len()..]));
+    }
+    let lead = s.len() - s.trim_start().len();
+    let number = &s[..lead + number_len(&s[lead..])];
+    let rest = s[number.len()..].trim();
+    if number.trim().is_empty() {
+        let at = rest.as_ptr() as usize - s.as_ptr() as usize;
+        return match rest.chars().next() {
+            Some(found) => Err(ParseEngError::InvalidChar { at, found }),
+            None => Err(ParseEngError::Empty),
+        };
+    }
+    let value = parse_number(number)?;
+    let n_first = rest.chars().next().map_or(0, char::len_utf8);
+    match si_exponent(&rest[..n_first]) {
+        Some(exp) => Ok((
+            apply_si_exponent(number, value, exp)?,
+            true,
+            rest[n_first..].trim_start(),
+        )),
+        None => Ok((value, false, rest)),
+    }
+}
+
+/// Returns the byte length of the longest leading run of `s` that can be number text: sign,
+/// digits, and decimal point, then an exponent only if `e` or `E` is followed by digits, since
+/// `E` alone is the exa prefix
+fn number_len(s: &str) -> usize {
+    let mantissa_len = s
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '+' | '-' | '.')))
+        .unwrap_or(s.len());
+    let rest = &s[mantissa_len..];
+    let Some(exp) = rest.strip_prefix(['e', 'E']) else {
+        return mantissa_len;
+    };
+    let unsigned = exp.trim_start_matches(['+', '-']);
+    let n_sign = exp.len() - unsigned.len();
+    let n_digits = unsigned
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unsigned.len());
+    match (n_sign, n_digits) {
+        (0..=1, 1..) => mantissa_len + 1 + n_sign + n_digits,
+        _ => mantissa_len,
+    }
+}
+
+/// Unit in which [parse_bin] returns data sizes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataUnit {
+    /// Bytes, so `"1Kib"` is 128
+    Bytes,
+    /// Bits, so `"1KiB"` is 8192
+    Bits,
+}
+
+/// Parses a data size with an optional IEC binary prefix (`Ki` through `Qi`, with the `i` in
+/// either case) or SI decimal prefix, followed by an optional `B` for bytes or `b` for bits, and
+/// returns it in `unit`.  The `i` is what selects powers of 1024, so `"1GiB"` is 2^30 bytes and
+/// `"1GB"` is 10^9 bytes.  Text without `B` or `b` is taken to be in `unit` already.
+///
+/// # Arguments
+/// - `s` - text to be parsed, e.g. `"1.5GiB"`
+/// - `unit` - unit of the returned value
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{parse_bin, DataUnit};
+/// assert_eq!(parse_bin("1.5GiB", DataUnit::Bytes), Ok(1_610_612_736.));
+/// assert_eq!(parse_bin("1.5 GB", DataUnit::Bytes), Ok(1.5e9));
+/// assert_eq!(parse_bin("512Ki", DataUnit::Bits), Ok(524_288.));
+/// assert_eq!(parse_bin("1KiB", DataUnit::Bits), Ok(8192.));
+/// ```
+pub fn parse_bin(s: &str, unit: DataUnit) -> Result<f64, ParseEngError> {
+    let trimmed = s.trim_end();
+    let (rest, given_unit) = match trimmed.strip_suffix('B') {
+        Some(rest) => (rest, Some(DataUnit::Bytes)),
+        None => match trimmed.strip_suffix('b') {
+            Some(rest) => (rest, Some(DataUnit::Bits)),
+            None => (trimmed, None),
+        },
+    };
+    let value = match parse_bin_prefixed(rest) {
+        // a lone unit letter is reported as invalid rather than as an empty number
+        Err(ParseEngError::Empty) if given_unit.is_some() => parse_number(s)?,
+        result => result?,
+    };
+    Ok(match (given_unit, unit) {
+        (Some(DataUnit::Bytes), DataUnit::Bits) => value * 8.,
+        (Some(DataUnit::Bits), DataUnit::Bytes) => value / 8.,
+        _ => value,
+    })
+}
+
+/// Parses a number with an optional binary or decimal prefix and no unit
+fn parse_bin_prefixed(s: &str) -> Result<f64, ParseEngError> {
+    let mut chars = s.char_indices().rev();
+    let last = chars.next().map(|(i, _)| i).unwrap_or(0);
+    let second_last = chars.next().map(|(i, _)| i).unwrap_or(0);
+    if let Some(power) = iec_power(&s[second_last..]) {
+        if let Partial::Valid(value) =
+            validate_partial_eng(&s[..second_last], PartialOptions::default())
+        {
+            // exact, since it only changes the binary exponent
+            return Ok(value * 2_f64.powi(10 * power));
+        }
+    }
+    if let Some(exp) = si_exponent(&s[last..]) {
+        if let Partial::Valid(value) = validate_partial_eng(&s[..last], PartialOptions::default()) {
+            return apply_si_exponent(&s[..last], value, exp);
+        }
+    }
+    parse_number(s)
+}
+
+/// Parses a number in engineering or plain notation with no prefix
+fn parse_number(s: &str) -> Result<f64, ParseEngError> {
+    match validate_partial_eng(s, PartialOptions::default()) {
+        Partial::Valid(value) => Ok(value),
+        _ if s.trim().is_empty() => Err(ParseEngError::Empty),
+        Partial::Incomplete => Err(ParseEngError::Incomplete),
+        Partial::Invalid { at } => {
+            let found = s[at..].chars().next().unwrap();
+            Err(ParseEngError::InvalidChar { at, found })
+        }
+    }
+}
+
+/// Splits a trailing SI prefix symbol off `s` if what precedes it is a complete number,
+/// returning the number text and the prefix exponent (0 without a prefix)
+fn split_prefix(s: &str) -> (&str, i32) {
+    let trimmed = s.trim_end();
+    if let Some(last) = trimmed.chars().last() {
+        let number = &trimmed[..trimmed.len() - last.len_utf8()];
+        if let Some(exp) = si_exponent(&trimmed[number.len()..]) {
+            if matches!(
+                validate_partial_eng(number, PartialOptions::default()),
+                Partial::Valid(_)
+            ) {
+                return (number, exp);
+            }
+        }
+    }
+    (s, 0)
+}
+
+/// Returns the number of significant figures shown by mantissa text like `"-0.0250"`
+fn significant_figures(mantissa: &str, rule: TrailingZeros) -> usize {
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let significant = digits.trim_start_matches('0');
+    if significant.is_empty() {
+        return digits.len().max(1);
+    }
+    match rule {
+        TrailingZeros::Insignificant if !mantissa.contains('.') => {
+            significant.trim_end_matches('0').len()
+        }
+        _ => significant.len(),
+    }
+}
+
+/// f64 that remembers the number of significant figures it was written with, and displays
+/// itself as [format_eng](crate::format_eng) does at that precision, honoring `format!` width,
+/// fill, alignment, and the `+` and `0` flags as [Eng](crate::Eng) does.  Parsing clamps the precision to
+/// [MAX_SIG_FIGS], which is enough digits for the exact value of any f64, so that every parsed
+/// value can be displayed.
+///
+/// # Panics
+/// When displayed with `sf` 0 or more than [MAX_SIG_FIGS].
+///
+/// # Examples
+/// ```
+/// use eng_fmt::PrecisionF64;
+/// let x: PrecisionF64 = "2.50 k".parse().unwrap();
+/// assert_eq!(x.to_string(), "2.50e3");
+/// assert_eq!(format!("{x:>8}|{x:<8}|{x:^8}"), "  2.50e3|2.50e3  | 2.50e3 ");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PrecisionF64 {
+    /// Parsed value
+    pub value: f64,
+    /// Number of significant figures the value was written with, at most [MAX_SIG_FIGS]
+    pub sf: usize,
+}
+
+impl FromStr for PrecisionF64 {
+    type Err = ParseEngError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, sf) = parse_eng_with_precision(s)?;
+        Ok(Self {
+            value,
+            sf: sf.min(MAX_SIG_FIGS),
+        })
+    }
+}
+
+impl fmt::Display for PrecisionF64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad_eng(f, self.value, Some(self.sf), 'e')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{format_eng, Eng};
+
+    #[test]
+    fn test_table() {
+        let table = [
+            ("2.50 k", (2.5e3, 3)),
+            ("2.5k", (2.5e3, 2)),
+            ("2.5", (2.5, 2)),
+            ("-2.50", (-2.5, 3)),
+            ("007", (7., 1)),
+            ("0.0250", (0.025, 3)),
+            ("25.0e-3", (0.025, 3)),
+            ("10.0e-3", (0.01, 3)),
+            ("-314e-3", (-0.314, 3)),
+            ("100", (100., 3)),
+            ("100.", (100., 3)),
+            ("1.00e3", (1e3, 3)),
+            ("0", (0., 1)),
+            ("0.00", (0., 3)),
+            ("-0.0", (-0., 2)),
+            (".5", (0.5, 1)),
+            ("4.7 u", (4.7e-6, 2)),
+            ("4.7µ", (4.7e-6, 2)),
+            ("100n", (100e-9, 3)),
+            ("2.2M", (2.2e6, 2)),
+            ("2.2m", (2.2e-3, 2)),
+            ("1.5e3k", (1.5e6, 2)),
+            ("  60.2e-24 ", (60.2e-24, 3)),
+        ];
+        for (s, expected) in table {
+            assert_eq!(parse_eng_with_precision(s), Ok(expected), "input: {s:?}");
+        }
+    }
+    #[test]
+    fn test_insignificant_trailing_zeros() {
+        let table = [
+            ("100", (100., 1)),
+            ("100.", (100., 3)),
+            ("1200 k", (1.2e6, 2)),
+            ("250e-3", (0.25, 2)),
+            ("2.50", (2.5, 3)),
+            ("0", (0., 1)),
+        ];
+        for (s, expected) in table {
+            assert_eq!(
+                parse_eng_with_precision_rule(s, TrailingZeros::Insignificant),
+                Ok(expected),
+                "input: {s:?}"
+            );
+        }
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_eng_with_precision(""), Err(ParseEngError::Empty));
+        assert_eq!(parse_eng_with_precision("  "), Err(ParseEngError::Empty));
+        assert_eq!(
+            parse_eng_with_precision("1.0e"),
+            Err(ParseEngError::Incomplete)
+        );
+        assert_eq!(
+            parse_eng_with_precision("e3"),
+            Err(ParseEngError::InvalidChar { at: 0, found: 'e' })
+        );
+        assert_eq!(
+            parse_eng_with_precision("2.5 q"),
+            Err(ParseEngError::InvalidChar { at: 4, found: 'q' })
+        );
+        assert_eq!(
+            parse_eng_with_precision("k"),
+            Err(ParseEngError::InvalidChar { at: 0, found: 'k' })
+        );
+        assert_eq!(
+            parse_eng_with_precision("1e99999999999k"),
+            Err(ParseEngError::ExponentOutOfRange)
+        );
+        assert_eq!(
+            parse_eng_with_precision("1e-99999999999 k"),
+            Err(ParseEngError::ExponentOutOfRange)
+        );
+    }
+    #[test]
+    fn test_parse_si() {
+        let table = [
+            ("4.7k", 4700.),
+            ("100n", 1e-7),
+            ("2.2M", 2.2e6),
+            ("2.2m", 2.2e-3),
+            ("10µ", 1e-5),
+            ("10u", 1e-5),
+            ("10μ", 1e-5),
+            (" -4.70 k ", -4700.),
+            ("1.5E", 1.5e18),
+            ("1.5e3k", 1.5e6),
+            ("1.5E-3 M", 1.5e3),
+            ("6.022e-23", 6.022e-23),
+            ("1y", 1e-24),
+            ("42", 42.),
+            ("-inf", f64::NEG_INFINITY),
+        ];
+        for (s, expected) in table {
+            assert_eq!(parse_si(s), Ok(expected), "{s}");
+        }
+        assert!(parse_si("NaN").unwrap().is_nan());
+    }
+    #[test]
+    fn test_parse_si_errors() {
+        use ParseEngError::*;
+        let table = [
+            ("", Empty),
+            ("  ", Empty),
+            ("4.7K", UnknownPrefix { at: 3, found: 'K' }),
+            ("4.7 c", UnknownPrefix { at: 4, found: 'c' }),
+            ("1 kV", InvalidChar { at: 3, found: 'V' }),
+            ("1µF", InvalidChar { at: 3, found: 'F' }),
+            ("k", InvalidChar { at: 0, found: 'k' }),
+            (" 1.2.3k", InvalidChar { at: 4, found: '.' }),
+            ("1e", UnknownPrefix { at: 1, found: 'e' }),
+            ("-", Incomplete),
+            ("1e99999999999k", ExponentOutOfRange),
+            ("1e-99999999999k", ExponentOutOfRange),
+            ("1e2147483647k", ExponentOutOfRange),
+            ("1e-2147483648m", ExponentOutOfRange),
+        ];
+        for (s, expected) in table {
+            assert_eq!(parse_si(s), Err(expected), "{s}");
+        }
+        // without a prefix the exponent is left to the float parser
+        assert_eq!(parse_si("1e99999999999"), Ok(f64::INFINITY));
+        assert_eq!(parse_si("1e-99999999999"), Ok(0.));
+        assert_eq!(
+            parse_si("4.7K").unwrap_err().to_string(),
+            "unknown SI prefix 'K' at byte 3"
+        );
+    }
+    #[test]
+    fn test_parse_si_unit() {
+        let table = [
+            ("4.70 kV", 4700., "V"),
+            ("4.7kΩ", 4700., "Ω"),
+            ("1 mm", 1e-3, "m"),
+            ("5 m", 5e-3, ""),
+            ("3.25 Hz", 3.25, "Hz"),
+            // documented misreading of a unit starting with a prefix
+            ("60.2e-24 kg", 6.02e-20, "g"),
+            ("1.50e27 V ", 1.5e27, "V"),
+            ("12 cd", 12., "cd"),
+            ("2.5", 2.5, ""),
+        ];
+        for (s, value, unit) in table {
+            assert_eq!(parse_si_unit(s), Ok((value, unit)), "{s}");
+        }
+    }
+    #[test]
+    fn test_parse_si_round_trip() {
+        for k in -30..=30 {
+            for x in [1.234_567, -9.999_6, 5.5] {
+                let x = x * 10_f64.powi(k);
+                for sf in [1, 3, 6] {
+                    let expected = parse_eng(&format_eng(x, Some(sf))).unwrap();
+                    assert_eq!(parse_si(&crate::format_si(x, Some(sf))), Ok(expected));
+                    assert_eq!(
+                        parse_si_unit(&crate::format_quantity(x, "V", Some(sf))),
+                        Ok((expected, "V"))
+                    );
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_parse_bin_mixed_prefixes() {
+        let file = "cache 512KiB\nram 16 GiB\ndisk 2TB\nlink 1Gb\nbuffer 1.5GiB\nflash 1.5GB\n";
+        let sizes: Vec<f64> = file
+            .lines()
+            .map(|line| parse_bin(line.split_once(' ').unwrap().1, DataUnit::Bytes).unwrap())
+            .collect();
+        assert_eq!(
+            sizes,
+            [
+                524_288.,
+                17_179_869_184.,
+                2e12,
+                125e6,
+                1_610_612_736.,
+                1.5e9
+            ]
+        );
+    }
+    #[test]
+    fn test_parse_bin_units() {
+        let table = [
+            ("1.5GiB", DataUnit::Bytes, 1_610_612_736.),
+            ("1.5GIB", DataUnit::Bytes, 1_610_612_736.),
+            ("1.5GiB", DataUnit::Bits, 12_884_901_888.),
+            ("8Kib", DataUnit::Bytes, 1024.),
+            ("8Kib", DataUnit::Bits, 8192.),
+            ("512Ki", DataUnit::Bytes, 524_288.),
+            ("512Ki", DataUnit::Bits, 524_288.),
+            ("100", DataUnit::Bytes, 100.),
+            ("100 B", DataUnit::Bytes, 100.),
+            ("1kB", DataUnit::Bytes, 1e3),
+            ("1MB", DataUnit::Bytes, 1e6),
+            ("1mb", DataUnit::Bits, 1e-3),
+            ("1EiB", DataUnit::Bytes, 1_152_921_504_606_846_976.),
+            ("1E", DataUnit::Bytes, 1e18),
+            ("1e3B", DataUnit::Bytes, 1e3),
+            ("1QiB", DataUnit::Bytes, 2_f64.powi(100)),
+        ];
+        for (s, unit, expected) in table {
+            assert_eq!(parse_bin(s, unit), Ok(expected), "input: {s:?}");
+        }
+    }
+    #[test]
+    fn test_parse_bin_errors() {
+        assert_eq!(
+            parse_bin("1.5giB", DataUnit::Bytes),
+            Err(ParseEngError::InvalidChar { at: 3, found: 'g' })
+        );
+        assert_eq!(
+            parse_bin("1.5kiB", DataUnit::Bytes),
+            Err(ParseEngError::InvalidChar { at: 3, found: 'k' })
+        );
+        assert_eq!(
+            parse_bin("1.5XB", DataUnit::Bytes),
+            Err(ParseEngError::InvalidChar { at: 3, found: 'X' })
+        );
+        assert_eq!(
+            parse_bin("B", DataUnit::Bytes),
+            Err(ParseEngError::InvalidChar { at: 0, found: 'B' })
+        );
+        assert_eq!(parse_bin("", DataUnit::Bytes), Err(ParseEngError::Empty));
+        assert_eq!(
+            parse_bin("1e99999999999kB", DataUnit::Bytes),
+            Err(ParseEngError::ExponentOutOfRange)
+        );
+        assert_eq!(
+            parse_bin("1e-99999999999M", DataUnit::Bits),
+            Err(ParseEngError::ExponentOutOfRange)
+        );
+    }
+    #[test]
+    fn test_precision_clamped() {
+        let text = format!("1.{}", "0".repeat(900));
+        assert_eq!(parse_eng_with_precision(&text), Ok((1., 901)));
+        let parsed: PrecisionF64 = text.parse().unwrap();
+        assert_eq!(parsed.sf, MAX_SIG_FIGS);
+        assert_eq!(parsed.to_string(), format!("1.{}", "0".repeat(766)));
+        let zero: PrecisionF64 = "0".repeat(800).parse().unwrap();
+        assert_eq!(zero.sf, MAX_SIG_FIGS);
+        assert_eq!(zero.to_string(), format!("0.{}", "0".repeat(766)));
+    }
+    #[test]
+    fn test_precision_display_padding() {
+        let x: PrecisionF64 = "-47.0µ".parse().unwrap();
+        assert_eq!(format!("{x}"), "-47.0e-6");
+        assert_eq!(format!("[{x:>10}]"), "[  -47.0e-6]");
+        assert_eq!(format!("[{x:*<10}]"), "[-47.0e-6**]");
+        assert_eq!(format!("[{x:^10}]"), "[ -47.0e-6 ]");
+        assert_eq!(format!("[{x:010}]"), "[-0047.0e-6]");
+        let y = PrecisionF64 { value: 2.5, sf: 2 };
+        assert_eq!(format!("[{y:+6}]"), "[  +2.5]");
+        let nan = PrecisionF64 {
+            value: f64::NAN,
+            sf: 3,
+        };
+        assert_eq!(format!("[{nan:>5}]"), "[  NaN]");
+    }
+    #[test]
+    fn test_round_trip() {
+        for x in [0.025, 6.022e-23, -999.5, 1e3, 0.] {
+            // below 3 significant figures `format_eng` may pad with insignificant zeros
+            for sf in 3..=6 {
+                let formatted = format_eng(x, Some(sf));
+                let parsed: PrecisionF64 = formatted.parse().unwrap();
+                assert_eq!(parsed.sf, sf, "formatted: {formatted}");
+                assert_eq!(parsed.to_string(), formatted);
+            }
+        }
+    }
+    #[test]
+    fn test_parse_eng_accepts() {
+        let table = [
+            ("10.0e-3", 0.01),
+            ("-314e-3", -0.314),
+            ("3.25", 3.25),
+            ("1.00E3", 1e3),
+            ("+2.5", 2.5),
+            ("  -60.2e-24\t", -60.2e-24),
+            ("0.00", 0.),
+            ("inf", f64::INFINITY),
+            (" -inf", f64::NEG_INFINITY),
+        ];
+        for (s, expected) in table {
+            assert_eq!(parse_eng(s), Ok(expected), "s: {s:?}");
+        }
+        assert!(parse_eng("NaN").unwrap().is_nan());
+        assert!(parse_eng("-0.00").unwrap().is_sign_negative());
+    }
+    #[test]
+    fn test_parse_eng_rejects() {
+        let table = [
+            ("", ParseEngError::Empty),
+            ("  ", ParseEngError::Empty),
+            ("1.0e", ParseEngError::Incomplete),
+            ("-", ParseEngError::Incomplete),
+            ("e3", ParseEngError::InvalidChar { at: 0, found: 'e' }),
+            ("--1", ParseEngError::InvalidChar { at: 1, found: '-' }),
+            ("+-1", ParseEngError::InvalidChar { at: 1, found: '-' }),
+            ("1e--3", ParseEngError::InvalidChar { at: 3, found: '-' }),
+            ("1.2.3", ParseEngError::InvalidChar { at: 3, found: '.' }),
+            ("2.5k", ParseEngError::InvalidChar { at: 3, found: 'k' }),
+            ("nan", ParseEngError::InvalidChar { at: 0, found: 'n' }),
+        ];
+        for (s, expected) in table {
+            assert_eq!(parse_eng(s), Err(expected), "s: {s:?}");
+        }
+        assert_eq!(
+            ParseEngError::InvalidChar { at: 1, found: '-' }.to_string(),
+            "invalid character '-' at byte 1"
+        );
+    }
+    #[test]
+    fn test_parse_eng_with_mark() {
+        let comma = DecimalMark::Comma;
+        assert_eq!(parse_eng_with_mark("3,25", comma), Ok(3.25));
+        assert_eq!(parse_eng_with_mark(" -0,00 ", comma), Ok(-0.));
+        assert_eq!(parse_eng_with_mark("10,0e-3", comma), Ok(0.01));
+        assert_eq!(parse_eng_with_mark("123e6", comma), Ok(123e6));
+        assert_eq!(parse_eng_with_mark("-inf", comma), Ok(f64::NEG_INFINITY));
+        assert_eq!(
+            parse_eng_with_mark("1,2,3", comma),
+            Err(ParseEngError::InvalidChar { at: 3, found: ',' })
+        );
+        assert_eq!(
+            parse_eng_with_mark("1,5", DecimalMark::Point),
+            Err(ParseEngError::InvalidChar { at: 1, found: ',' })
+        );
+        let fmt = crate::EngFormatter::new().sig_figs(17).decimal_mark(comma);
+        for x in [0.1, -6.022e23, 5e-324, 1.] {
+            assert_eq!(parse_eng_with_mark(&fmt.format(x), comma), Ok(x));
+        }
+    }
+    #[test]
+    fn test_parse_eng_round_trip() {
+        for k in -300..=300 {
+            for mantissa in [1., 1.234_567_890_123_456_7, 5.5, 9.999_999_999_999_99] {
+                for x in [mantissa * 10_f64.powi(k), -mantissa * 10_f64.powi(k)] {
+                    let parsed = parse_eng(&format_eng(x, Some(15))).unwrap();
+                    // within half a unit in the 15th significant figure
+                    assert!(
+                        (parsed - x).abs() <= 5.01e-15 * x.abs(),
+                        "x: {x:e}, parsed: {parsed:e}"
+                    );
+                    assert_eq!(parse_eng(&format_eng(x, Some(17))).unwrap(), x);
+                }
+            }
+        }
+        for x in [0., -0., f64::INFINITY, f64::NEG_INFINITY, f64::MAX, 5e-324] {
+            let parsed: Eng = format_eng(x, Some(17)).parse().unwrap();
+            assert_eq!(parsed.0.to_bits(), x.to_bits(), "x: {x:e}");
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! Module for as-you-type validation of [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) text entry.
+
+use crate::parse::parse_si;
+use crate::prefix::si_exponent;
+
+/// Classification returned by [validate_partial_eng]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Partial {
+    /// Text is a complete number
+    Valid(f64),
+    /// Text is not a number yet but more typing could make it one, e.g. `"3.1e"` or `"-"`
+    Incomplete,
+    /// Text can never become a number; `at` is the byte offset of the first offending `char`
+    Invalid { at: usize },
+}
+
+/// Options for [validate_partial_eng]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PartialOptions {
+    /// True to also accept an [SI prefix](https://en.wikipedia.org/wiki/Metric_prefix) after
+    /// the number, as by [parse_si](crate::parse_si), e.g. `"4.7k"` or `"10 µ"`
+    pub si_prefix: bool,
+}
+
+/// Position within the grammar after consuming a `char`
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    /// Nothing but leading whitespace so far
+    Start,
+    /// Mantissa sign
+    Sign,
+    /// Integer digits
+    Int,
+    /// Decimal point with no integer digits before it
+    LoneDot,
+    /// Decimal point or fractional digits after at least one digit
+    Frac,
+    /// Exponent marker
+    Exp,
+    /// Exponent sign
+    ExpSign,
+    /// Exponent digits
+    ExpDigits,
+    /// `E` after the mantissa with SI prefixes accepted, either exa or an exponent marker
+    ExpOrExa,
+    /// SI prefix symbol
+    Prefix,
+    /// Whitespace after an SI prefix
+    AfterPrefix,
+    /// Whitespace after a complete number
+    Trailing,
+}
+
+impl State {
+    fn is_complete(self) -> bool {
+        use State::*;
+        matches!(
+            self,
+            Int | Frac | ExpDigits | ExpOrExa | Prefix | AfterPrefix | Trailing
+        )
+    }
+
+    /// Returns true if the number ends in an SI prefix
+    fn is_prefixed(self) -> bool {
+        matches!(self, State::ExpOrExa | State::Prefix | State::AfterPrefix)
+    }
+
+    fn next(self, c: char, opts: PartialOptions) -> Option<State> {
+        use State::*;
+        let is_prefix =
+            |c: char| opts.si_prefix && si_exponent(c.encode_utf8(&mut [0; 4])).is_some();
+        match (self, c) {
+            (Start, c) if c.is_whitespace() => Some(Start),
+            (Start, '+' | '-') => Some(Sign),
+            (Start | Sign | Int, '0'..='9') => Some(Int),
+            (Start | Sign, '.') => Some(LoneDot),
+            (Int, '.') => Some(Frac),
+            (LoneDot | Frac, '0'..='9') => Some(Frac),
+            (Int | Frac, 'E') if opts.si_prefix => Some(ExpOrExa),
+            (Int | Frac, 'e' | 'E') => Some(Exp),
+            (Exp | ExpOrExa, '+' | '-') => Some(ExpSign),
+            (Exp | ExpOrExa | ExpSign | ExpDigits, '0'..='9') => Some(ExpDigits),
+            (Int | Frac | ExpDigits | Trailing, c) if is_prefix(c) => Some(Prefix),
+            (ExpOrExa | Prefix | AfterPrefix, c) if c.is_whitespace() => Some(AfterPrefix),
+            (s, c) if s.is_complete() && c.is_whitespace() => Some(Trailing),
+            _ => None,
+        }
+    }
+}
+
+/// Classifies partially typed text as a [Partial::Valid] number, an [Partial::Incomplete] prefix
+/// of a number, or [Partial::Invalid] text.  Unlike a parser, which rejects anything that is not
+/// yet a number, this treats every prefix of a valid input (e.g. `""`, `"-"`, `"."`, `"3.1e"`,
+/// `"3.1e-"`) as incomplete.  Accepts the forms emitted by [format_eng](crate::format_eng) plus
+/// an optional `+`, uppercase `E`, and surrounding whitespace.  With
+/// [si_prefix](PartialOptions::si_prefix), a single SI prefix symbol may follow the number, with
+/// or without whitespace between, accepting the forms emitted by [format_si](crate::format_si).
+/// `E` is then exa until digits or a sign after it make it an exponent, so `"3.1E"` is valid.
+///
+/// # Arguments
+/// - `s` - text typed so far
+/// - `opts` - forms accepted besides plain engineering notation
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{validate_partial_eng, Partial, PartialOptions};
+/// let opts = PartialOptions::default();
+/// assert_eq!(validate_partial_eng("10.0e-3", opts), Partial::Valid(0.010));
+/// assert_eq!(validate_partial_eng("10.0e-", opts), Partial::Incomplete);
+/// assert_eq!(validate_partial_eng("10.0x", opts), Partial::Invalid { at: 4 });
+/// assert_eq!(validate_partial_eng("4.7k", opts), Partial::Invalid { at: 3 });
+/// let opts = PartialOptions { si_prefix: true };
+/// assert_eq!(validate_partial_eng("4.7k", opts), Partial::Valid(4700.));
+/// assert_eq!(validate_partial_eng("4.7kx", opts), Partial::Invalid { at: 4 });
+/// ```
+pub fn validate_partial_eng(s: &str, opts: PartialOptions) -> Partial {
+    let mut state = State::Start;
+    let mut prefix_at = 0;
+    for (at, c) in s.char_indices() {
+        match state.next(c, opts) {
+            Some(next) => {
+                if matches!(next, State::ExpOrExa | State::Prefix) {
+                    prefix_at = at;
+                }
+                state = next;
+            }
+            None => return Partial::Invalid { at },
+        }
+    }
+    if state.is_prefixed() {
+        // only fails if the prefix takes the exponent past the `i32` limits
+        match parse_si(s) {
+            Ok(value) => Partial::Valid(value),
+            Err(_) => Partial::Invalid { at: prefix_at },
+        }
+    } else if state.is_complete() {
+        // the grammar above is otherwise a subset of what `str::parse` accepts
+        Partial::Valid(s.trim().parse().unwrap())
+    } else {
+        Partial::Incomplete
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        use Partial::*;
+        let table = [
+            // empty and whitespace
+            ("", Incomplete),
+            (" ", Incomplete),
+            ("  \t", Incomplete),
+            // lone sign and dot
+            ("-", Incomplete),
+            ("+", Incomplete),
+            (".", Incomplete),
+            ("-.", Incomplete),
+            ("+.", Incomplete),
+            // plain numbers
+            ("0", Valid(0.)),
+            ("3", Valid(3.)),
+            ("-3", Valid(-3.)),
+            ("+3", Valid(3.)),
+            ("3.", Valid(3.)),
+            ("3.1", Valid(3.1)),
+            (".5", Valid(0.5)),
+            ("-.5", Valid(-0.5)),
+            ("007", Valid(7.)),
+            (" 2.00 ", Valid(2.)),
+            // trailing `e`
+            ("3e", Incomplete),
+            ("3.1e", Incomplete),
+            ("3.1E", Incomplete),
+            ("3.e", Incomplete),
+            ("3.1e-", Incomplete),
+            ("3.1e+", Incomplete),
+            // complete exponents
+            ("3.1e3", Valid(3.1e3)),
+            ("3.1E3", Valid(3.1e3)),
+            ("10.0e-3", Valid(0.010)),
+            ("-314e-3", Valid(-0.314)),
+            ("60.2e-24", Valid(60.2e-24)),
+            ("1e+3", Valid(1e3)),
+            ("3.e3", Valid(3e3)),
+            ("1.00e3 ", Valid(1e3)),
+            // invalid characters
+            ("x", Invalid { at: 0 }),
+            ("3x", Invalid { at: 1 }),
+            ("--3", Invalid { at: 1 }),
+            ("+-3", Invalid { at: 1 }),
+            ("3.1.", Invalid { at: 3 }),
+            ("..", Invalid { at: 1 }),
+            ("e3", Invalid { at: 0 }),
+            ("-e3", Invalid { at: 1 }),
+            (".e3", Invalid { at: 1 }),
+            ("3e3.", Invalid { at: 3 }),
+            ("3e3e", Invalid { at: 3 }),
+            ("3e--3", Invalid { at: 3 }),
+            ("3e+-3", Invalid { at: 3 }),
+            ("3 4", Invalid { at: 2 }),
+            ("3e ", Invalid { at: 2 }),
+            ("- 3", Invalid { at: 1 }),
+            ("3,1", Invalid { at: 1 }),
+            // byte offsets, not char offsets
+            ("µ3", Invalid { at: 0 }),
+            ("3µ", Invalid { at: 1 }),
+            ("\u{2003}3x", Invalid { at: 4 }),
+        ];
+        for (s, expected) in table {
+            assert_eq!(
+                validate_partial_eng(s, PartialOptions::default()),
+                expected,
+                "input: {s:?}"
+            );
+        }
+    }
+    #[test]
+    fn test_si_prefix_table() {
+        use Partial::*;
+        let opts = PartialOptions { si_prefix: true };
+        let table = [
+            // plain forms are unchanged
+            ("", Incomplete),
+            ("-", Incomplete),
+            ("4.7", Valid(4.7)),
+            ("3.1e", Incomplete),
+            ("3.1e-", Incomplete),
+            ("10.0e-3", Valid(0.010)),
+            // trailing prefix
+            ("4.7k", Valid(4.7e3)),
+            ("4.7µ", Valid(4.7e-6)),
+            ("4.7u", Valid(4.7e-6)),
+            ("4.7\u{3bc}", Valid(4.7e-6)),
+            ("-47.0µ", Valid(-47e-6)),
+            ("100n", Valid(100e-9)),
+            ("2.2M", Valid(2.2e6)),
+            ("4.7 k", Valid(4.7e3)),
+            ("4.7k ", Valid(4.7e3)),
+            (" 10 µ ", Valid(10e-6)),
+            ("3.k", Valid(3e3)),
+            ("1e3k", Valid(1e6)),
+            // `E` is exa until it becomes an exponent
+            ("3.1E", Valid(3.1e18)),
+            ("3.1E ", Valid(3.1e18)),
+            ("3.1E3", Valid(3.1e3)),
+            ("3.1E-", Incomplete),
+            ("3.1 E", Valid(3.1e18)),
+            // invalid characters
+            ("4.7kx", Invalid { at: 4 }),
+            ("4.7kk", Invalid { at: 4 }),
+            ("4.7k 3", Invalid { at: 5 }),
+            ("4.7K", Invalid { at: 3 }),
+            ("4.7x", Invalid { at: 3 }),
+            ("k", Invalid { at: 0 }),
+            ("-k", Invalid { at: 1 }),
+            (".k", Invalid { at: 1 }),
+            ("3e-k", Invalid { at: 3 }),
+            ("3Ek", Invalid { at: 2 }),
+            ("µ3", Invalid { at: 0 }),
+            ("1e2147483647k", Invalid { at: 12 }),
+        ];
+        for (s, expected) in table {
+            assert_eq!(validate_partial_eng(s, opts), expected, "input: {s:?}");
+        }
+    }
+    #[test]
+    fn test_every_prefix_of_valid_is_not_invalid() {
+        let plain = PartialOptions::default();
+        let si = PartialOptions { si_prefix: true };
+        let cases = [
+            ("-60.2e-24", plain),
+            ("+1.00E+3", plain),
+            (".5e1", plain),
+            ("3.14159", plain),
+            ("-60.2e-24", si),
+            ("+1.00E+3", si),
+            ("4.70 k", si),
+            ("-47.0µ ", si),
+        ];
+        for (s, opts) in cases {
+            for (i, _) in s.char_indices() {
+                assert!(
+                    !matches!(validate_partial_eng(&s[..i], opts), Partial::Invalid { .. }),
+                    "prefix: {:?}",
+                    &s[..i]
+                );
+            }
+        }
+    }
+}
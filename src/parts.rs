@@ -0,0 +1,130 @@
+//! Module exposing the pieces of [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) output that
+//! [format_eng](crate::format_eng) is built from.
+
+use std::fmt;
+
+use crate::error::check_sf;
+use crate::exact::with_eng_parts;
+
+/// Sign of a formatted value
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sign {
+    /// Positive values, positive zero, and NaN
+    Positive,
+    /// Negative values and negative zero
+    Negative,
+}
+
+/// Sign, mantissa, and exponent of a value in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation), as returned by [eng_parts],
+/// e.g. for styling the exponent as a superscript.  [Display](fmt::Display) gives exactly the
+/// output of [format_eng](crate::format_eng).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EngParts {
+    /// Sign of the value, kept for negative zero and infinity
+    pub sign: Sign,
+    /// Mantissa without sign, rounded to the requested significant figures, e.g. `"4.70"`, or
+    /// `"NaN"` or `"inf"`
+    pub mantissa: String,
+    /// Engineering exponent, a multiple of 3, and 0 for zero, NaN, and infinities
+    pub exponent: i32,
+}
+
+impl fmt::Display for EngParts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_parts(f, self.sign, &self.mantissa, self.exponent)
+    }
+}
+
+/// Returns the sign, mantissa, and engineering exponent that [format_eng](crate::format_eng)
+/// prints for `x`, which it builds its output from, so that the [Display](fmt::Display) of the
+/// result always equals it.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{eng_parts, EngParts, Sign};
+/// let parts = eng_parts(-4.7e-6, None);
+/// assert_eq!(
+///     parts,
+///     EngParts {
+///         sign: Sign::Negative,
+///         mantissa: String::from("4.70"),
+///         exponent: -6,
+///     }
+/// );
+/// assert_eq!(parts.to_string(), "-4.70e-6");
+/// ```
+pub fn eng_parts(x: f64, sf: Option<usize>) -> EngParts {
+    let sf = check_sf(sf, x).unwrap_or_else(|err| panic!("{err}"));
+    with_eng_parts(x, sf, |sign, mantissa, exponent| EngParts {
+        sign,
+        mantissa: mantissa.to_string(),
+        exponent,
+    })
+}
+
+/// Writes `mantissa` with a leading `-` for [Sign::Negative] and an `e` exponent unless
+/// `exponent` is 0
+pub(crate) fn write_parts<W: fmt::Write>(
+    out: &mut W,
+    sign: Sign,
+    mantissa: &str,
+    exponent: i32,
+) -> fmt::Result {
+    if sign == Sign::Negative {
+        out.write_char('-')?;
+    }
+    out.write_str(mantissa)?;
+    if exponent != 0 {
+        write!(out, "e{exponent}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_eng;
+
+    #[test]
+    fn test_parts() {
+        let table = [
+            (2., 3, Sign::Positive, "2.00", 0),
+            (-std::f64::consts::PI / 10., 3, Sign::Negative, "314", -3),
+            (6.022e-23, 3, Sign::Positive, "60.2", -24),
+            (999.96, 3, Sign::Positive, "1.00", 3),
+            (33.333, 7, Sign::Positive, "33.33300", 0),
+            (0., 5, Sign::Positive, "0.0000", 0),
+            (-0., 3, Sign::Negative, "0.00", 0),
+            (f64::NAN, 3, Sign::Positive, "NaN", 0),
+            (-f64::NAN, 3, Sign::Positive, "NaN", 0),
+            (f64::NEG_INFINITY, 3, Sign::Negative, "inf", 0),
+            (f64::MAX, 17, Sign::Positive, "179.76931348623157", 306),
+        ];
+        for (x, sf, sign, mantissa, exponent) in table {
+            let parts = eng_parts(x, Some(sf));
+            assert_eq!(
+                parts,
+                EngParts {
+                    sign,
+                    mantissa: String::from(mantissa),
+                    exponent,
+                }
+            );
+            assert_eq!(parts.to_string(), format_eng(x, Some(sf)));
+        }
+    }
+    #[test]
+    #[should_panic]
+    fn test_sf_zero() {
+        eng_parts(1., Some(0));
+    }
+}
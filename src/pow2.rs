@@ -0,0 +1,140 @@
+//! Module for formatting exact powers of two, e.g. for buffer-size sanity checks.
+
+use crate::error::check_sf;
+use crate::exact::{format_rounded, round_digits, RoundingMode};
+use crate::prefix::IEC_PREFIXES;
+
+include!(concat!(env!("OUT_DIR"), "/pow2_eng_3sf.rs"));
+
+/// Output form for [format_pow2]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pow2Style {
+    /// Decimal [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation) with
+    /// the given number of significant figures, defaulting to 3, e.g. `"1.05e6"` for `2^20`
+    Eng(Option<usize>),
+    /// Exact integer times the largest IEC binary prefix that fits, e.g. `"512Ki"` for `2^19`
+    Iec,
+}
+
+/// Returns `2^n` formatted per `style`, computed exactly for every `n` rather than through
+/// f64, with the same half-away-from-zero rounding as [format_eng](crate::format_eng).  For
+/// `n < 64` at 3 significant figures, [POW2_ENG_3SF] gives the same strings without allocating.
+///
+/// # Arguments
+/// - `n` - power of two
+/// - `style` - decimal engineering or IEC binary output
+///
+/// # Panics
+/// If `style` is [Pow2Style::Eng] with 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS)
+/// significant figures.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_pow2, Pow2Style};
+/// assert_eq!(format_pow2(32, Pow2Style::Eng(None)), "4.29e9");
+/// assert_eq!(format_pow2(32, Pow2Style::Iec), "4Gi");
+/// ```
+pub fn format_pow2(n: u32, style: Pow2Style) -> String {
+    match style {
+        Pow2Style::Eng(sf) => {
+            let sf = check_sf(sf, f64::from(n).exp2()).unwrap_or_else(|err| panic!("{err}"));
+            let digits = pow2_digits(n);
+            let exp_sci = digits.len() as i32 - 1;
+            let rounded = round_digits(&digits, exp_sci, sf, RoundingMode::HalfAwayFromZero, false);
+            format_rounded(false, &rounded)
+        }
+        Pow2Style::Iec => {
+            let i_prefix = (n / 10).min(IEC_PREFIXES.len() as u32 - 1);
+            let mantissa: String = pow2_digits(n - 10 * i_prefix)
+                .iter()
+                .map(|d| char::from(b'0' + d))
+                .collect();
+            format!("{mantissa}{}", IEC_PREFIXES[i_prefix as usize])
+        }
+    }
+}
+
+/// Returns the decimal digits of `2^n`, most significant first
+fn pow2_digits(n: u32) -> Vec<u8> {
+    let small = n.min(127);
+    let mut digits: Vec<u8> = (1_u128 << small)
+        .to_string()
+        .bytes()
+        .rev()
+        .map(|b| b - b'0')
+        .collect();
+    // double the least-significant-first digits for the remaining powers
+    for _ in small..n {
+        let mut carry = 0;
+        for d in digits.iter_mut() {
+            let doubled = *d * 2 + carry;
+            *d = doubled % 10;
+            carry = doubled / 10;
+        }
+        if carry > 0 {
+            digits.push(carry);
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_matches_runtime() {
+        for (n, expected) in POW2_ENG_3SF.iter().enumerate() {
+            assert_eq!(format_pow2(n as u32, Pow2Style::Eng(None)), *expected);
+        }
+    }
+    #[test]
+    fn test_table_matches_format_eng() {
+        for (n, expected) in POW2_ENG_3SF.iter().enumerate() {
+            assert_eq!(crate::format_eng(2_f64.powi(n as i32), None), *expected);
+        }
+    }
+    #[test]
+    fn test_eng() {
+        let table = [
+            (0, "1.00"),
+            (10, "1.02e3"),
+            (20, "1.05e6"),
+            (63, "9.22e18"),
+            (127, "170e36"),
+            (200, "1.61e60"),
+            (1100, "13.6e330"),
+        ];
+        for (n, expected) in table {
+            assert_eq!(format_pow2(n, Pow2Style::Eng(None)), expected, "n: {n}");
+        }
+    }
+    #[test]
+    fn test_eng_exact_digits() {
+        assert_eq!(
+            format_pow2(63, Pow2Style::Eng(Some(19))),
+            String::from("9.223372036854775808e18")
+        );
+        assert_eq!(
+            format_pow2(200, Pow2Style::Eng(Some(8))),
+            String::from("1.6069380e60")
+        );
+    }
+    #[test]
+    fn test_iec() {
+        let table = [
+            (0, "1"),
+            (9, "512"),
+            (10, "1Ki"),
+            (19, "512Ki"),
+            (20, "1Mi"),
+            (63, "8Ei"),
+            (127, "134217728Qi"),
+            (200, "1267650600228229401496703205376Qi"),
+        ];
+        for (n, expected) in table {
+            assert_eq!(format_pow2(n, Pow2Style::Iec), expected, "n: {n}");
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! Module containing the table of SI prefixes shared by every prefix-aware code path.
+
+/// SI prefixes from yocto to yotta as `(exponent, symbol, English word)`, in ascending order of
+/// exponent, with micro as `µ` (U+00B5 MICRO SIGN) and the empty prefix for exponent 0.
+pub(crate) const SI_PREFIXES: [(i32, &str, &str); 17] = [
+    (-24, "y", "yocto"),
+    (-21, "z", "zepto"),
+    (-18, "a", "atto"),
+    (-15, "f", "femto"),
+    (-12, "p", "pico"),
+    (-9, "n", "nano"),
+    (-6, "µ", "micro"),
+    (-3, "m", "milli"),
+    (0, "", ""),
+    (3, "k", "kilo"),
+    (6, "M", "mega"),
+    (9, "G", "giga"),
+    (12, "T", "tera"),
+    (15, "P", "peta"),
+    (18, "E", "exa"),
+    (21, "Z", "zetta"),
+    (24, "Y", "yotta"),
+];
+
+/// IEC binary prefixes for powers of 1024, starting with no prefix
+pub(crate) const IEC_PREFIXES: [&str; 11] = [
+    "", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi", "Ri", "Qi",
+];
+
+/// Returns the `(exponent, symbol, English word)` entry for engineering exponent `exp`, or
+/// `None` if `exp` is outside the prefix range or not a multiple of 3
+pub(crate) fn si_prefix(exp: i32) -> Option<(i32, &'static str, &'static str)> {
+    SI_PREFIXES.iter().find(|(e, _, _)| *e == exp).copied()
+}
+
+/// Returns the exponent of SI prefix `symbol`, accepting `u` and `μ` (U+03BC GREEK SMALL LETTER
+/// MU) as aliases for micro, or `None` if `symbol` is not a prefix
+pub(crate) fn si_exponent(symbol: &str) -> Option<i32> {
+    match symbol {
+        "" => None,
+        "u" | "μ" => Some(-6),
+        _ => SI_PREFIXES
+            .iter()
+            .find(|(_, s, _)| *s == symbol)
+            .map(|(e, _, _)| *e),
+    }
+}
+
+/// Returns the power of 1024 of IEC binary prefix `symbol`, matching the leading letter exactly
+/// and the `i` in either case, or `None` if `symbol` is not a binary prefix
+pub(crate) fn iec_power(symbol: &str) -> Option<i32> {
+    let letter = symbol.strip_suffix(['i', 'I'])?;
+    IEC_PREFIXES[1..]
+        .iter()
+        .position(|p| p[..1] == *letter)
+        .map(|i| i as i32 + 1)
+}
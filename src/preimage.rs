@@ -0,0 +1,175 @@
+//! Module for finding which values display identically in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation), for debugging values that sit
+//! near rounding boundaries.
+
+use std::cmp::Ordering;
+
+use crate::error::check_sf;
+use crate::exact::{cmp_abs_decimal, round_exact, RoundingMode};
+
+/// Returns the smallest and largest f64 that [format_eng](crate::format_eng) formats to the same
+/// string as `x` at `sf` significant figures, i.e. the preimage of the displayed value.  The
+/// bounds come from the decimal half-way points around the displayed digits, so e.g. `1.00`
+/// covers 0.9995 through just below 1.005.  Half-way points are compared exactly with the binary
+/// values, and a tie belongs to the interval of the digits it rounds away from zero to.
+/// Zero, non-finite values, and NaN give `(x, x)` since no other value displays the same.
+///
+/// # Arguments
+/// - `x` - value whose displayed preimage is wanted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_eng, next_display_boundary};
+/// let (lo, hi) = next_display_boundary(1.0, None);
+/// assert_eq!((lo, format_eng(lo, None)), (0.9995, "1.00".to_string()));
+/// assert_eq!(format_eng(hi, None), "1.00");
+/// assert_eq!(format_eng(hi.next_up(), None), "1.01");
+/// ```
+pub fn next_display_boundary(x: f64, sf: Option<usize>) -> (f64, f64) {
+    let sf = check_sf(sf, x).unwrap_or_else(|err| panic!("{err}"));
+    if x == 0. || !x.is_finite() {
+        return (x, x);
+    }
+    let rounded = round_exact(x, sf, RoundingMode::HalfAwayFromZero);
+    let place = rounded.exp_sci - sf as i32 + 1;
+    let digits: String = rounded
+        .digits
+        .iter()
+        .map(|d| char::from(b'0' + d))
+        .collect();
+
+    // magnitudes half a unit in the last place below and above the displayed value; below a
+    // leading 1 followed by zeros, the last place is a decade finer
+    let (lower, lower_place) = if digits.starts_with('1') && digits[1..].bytes().all(|d| d == b'0')
+    {
+        (format!("{}5", "9".repeat(sf)), place - 2)
+    } else {
+        (format!("{}5", decrement(&digits)), place - 1)
+    };
+    let upper = format!("{digits}5");
+
+    // smallest magnitude at or above the lower half-way point
+    let mut lo: f64 = format!("{lower}e{lower_place}").parse().unwrap();
+    if cmp_abs_decimal(lo, &lower, lower_place) == Ordering::Less {
+        lo = lo.next_up();
+    }
+    // largest magnitude strictly below the upper half-way point
+    let mut hi: f64 = format!("{upper}e{}", place - 1).parse().unwrap();
+    if hi.is_infinite() || cmp_abs_decimal(hi, &upper, place - 1) != Ordering::Less {
+        hi = hi.next_down();
+    }
+
+    if x < 0. {
+        (-hi, -lo)
+    } else {
+        (lo, hi)
+    }
+}
+
+/// Returns the decimal digits `digits` minus one, keeping the same length
+fn decrement(digits: &str) -> String {
+    let mut bytes = digits.as_bytes().to_vec();
+    let i = bytes.iter().rposition(|d| *d != b'0').unwrap();
+    bytes[i] -= 1;
+    bytes[i + 1..].fill(b'9');
+    String::from_utf8(bytes).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_eng;
+
+    /// Asserts that `lo` and `hi` display like `x` while their outer neighbors do not
+    fn assert_tight(x: f64, sf: usize) {
+        let (lo, hi) = next_display_boundary(x, Some(sf));
+        let displayed = format_eng(x, Some(sf));
+        assert!(lo <= x && x <= hi, "x: {x:e}, sf: {sf}");
+        for inside in [lo, hi, lo.next_up(), hi.next_down(), lo + (hi - lo) / 2.] {
+            if !(lo..=hi).contains(&inside) {
+                // interval is a single value
+                continue;
+            }
+            assert_eq!(
+                format_eng(inside, Some(sf)),
+                displayed,
+                "x: {x:e}, sf: {sf}"
+            );
+        }
+        for outside in [lo.next_down(), hi.next_up()] {
+            if outside.is_finite() {
+                assert_ne!(
+                    format_eng(outside, Some(sf)),
+                    displayed,
+                    "x: {x:e}, sf: {sf}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_simple() {
+        let (lo, hi) = next_display_boundary(1.25, None);
+        assert_eq!(lo, 1.245);
+        assert_eq!(hi, 1.255);
+        assert_eq!(next_display_boundary(-1.25, None), (-hi, -lo));
+    }
+    #[test]
+    fn test_decade_boundary() {
+        // "1.00e3" is reached from 999.5 up
+        let (lo, hi) = next_display_boundary(1e3, None);
+        assert_eq!(lo, 999.5);
+        assert_eq!(hi, 1005_f64.next_down());
+        let (lo, hi) = next_display_boundary(999., None);
+        assert_eq!(lo, 998.5);
+        assert_eq!(hi, 999.5_f64.next_down());
+        assert_tight(1e-3, 1);
+        assert_tight(0.0995, 2);
+    }
+    #[test]
+    fn test_inexact_ties() {
+        // 1.245 is stored above the tie and 1.255 below it, so both display as 1.25
+        let (lo, hi) = next_display_boundary(1.25, Some(3));
+        assert_eq!(
+            (format_eng(lo, None), format_eng(hi, None)),
+            ("1.25".into(), "1.25".into())
+        );
+        assert_eq!(format_eng(lo.next_down(), None), "1.24");
+        assert_eq!(format_eng(hi.next_up(), None), "1.26");
+        assert_tight(0.35, 1);
+    }
+    #[test]
+    fn test_extremes() {
+        let (lo, hi) = next_display_boundary(f64::MAX, None);
+        assert_eq!(hi, f64::MAX);
+        assert_eq!(format_eng(lo, None), format_eng(f64::MAX, None));
+        let (lo, _) = next_display_boundary(5e-324, Some(1));
+        assert_eq!(lo, 5e-324);
+        assert_tight(f64::MIN_POSITIVE, 3);
+        assert_tight(f64::MIN, 5);
+    }
+    #[test]
+    fn test_special() {
+        assert_eq!(next_display_boundary(0., None), (0., 0.));
+        assert_eq!(next_display_boundary(f64::INFINITY, None).1, f64::INFINITY);
+        assert!(next_display_boundary(f64::NAN, None).0.is_nan());
+    }
+    #[test]
+    fn test_bounds_tight_random() {
+        let mut state: u64 = 0x853c_49e6_748f_ea9b;
+        for _ in 0..2_000 {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let x = f64::from_bits(state);
+            if x.is_finite() && x != 0. {
+                assert_tight(x, 1 + (state >> 60) as usize);
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Module implementing [FormatEng] for the numeric primitives other than f64.
+
+use crate::error::check_sf;
+use crate::exact::{format_rounded, round_digits, RoundingMode};
+use crate::iec::format_bin_int;
+use crate::{try_format_eng, EngFmtError, FormatEng};
+
+impl FormatEng for f32 {
+    /// Returns f32 as string in [engineering
+    /// notation](https://en.wikipedia.org/wiki/Engineering_notation), rounded from its exact
+    /// value, which every f64 holds exactly, so e.g. `0.1_f32` at 9 significant figures is
+    /// `"100.000001e-3"`.
+    ///
+    /// # Arguments
+    /// - `sf` - Number of significant figures, defaults to 3
+    fn try_format_eng(&self, sf: Option<usize>) -> Result<String, EngFmtError> {
+        try_format_eng(f64::from(*self), sf)
+    }
+}
+
+/// Formats the integer with sign `negative` and magnitude `magnitude` in engineering notation
+/// by rounding its decimal digits directly, so that integers beyond 2^53 are not first rounded
+/// to an f64, which is only used as the value reported in errors
+fn try_format_eng_int(
+    negative: bool,
+    magnitude: u128,
+    sf: Option<usize>,
+) -> Result<String, EngFmtError> {
+    let value = if negative {
+        -(magnitude as f64)
+    } else {
+        magnitude as f64
+    };
+    let sf = check_sf(sf, value)?;
+    if magnitude == 0 {
+        return try_format_eng(0., Some(sf));
+    }
+    let digits: Vec<u8> = magnitude.to_string().bytes().map(|b| b - b'0').collect();
+    let exp_sci = digits.len() as i32 - 1;
+    Ok(format_rounded(
+        negative,
+        &round_digits(
+            &digits,
+            exp_sci,
+            sf,
+            RoundingMode::HalfAwayFromZero,
+            negative,
+        ),
+    ))
+}
+
+macro_rules! impl_format_eng_unsigned {
+    ($($t:ty),*) => {$(
+        impl FormatEng for $t {
+            /// Returns the integer as string in [engineering
+            /// notation](https://en.wikipedia.org/wiki/Engineering_notation), rounded half away
+            /// from zero from its exact digits.
+            ///
+            /// # Arguments
+            /// - `sf` - Number of significant figures, defaults to 3
+            fn try_format_eng(&self, sf: Option<usize>) -> Result<String, EngFmtError> {
+                try_format_eng_int(false, *self as u128, sf)
+            }
+
+            /// Returns the integer scaled by the largest power of 1024 not exceeding it, with its
+            /// [IEC prefix](https://en.wikipedia.org/wiki/Binary_prefix), rounded half away from
+            /// zero from its exact value.
+            ///
+            /// # Arguments
+            /// - `sf` - Number of significant figures, defaults to 3
+            fn format_bin(&self, sf: Option<usize>) -> String {
+                format_bin_int(false, *self as u128, sf)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_format_eng_signed {
+    ($($t:ty),*) => {$(
+        impl FormatEng for $t {
+            /// Returns the integer as string in [engineering
+            /// notation](https://en.wikipedia.org/wiki/Engineering_notation), rounded half away
+            /// from zero from its exact digits.
+            ///
+            /// # Arguments
+            /// - `sf` - Number of significant figures, defaults to 3
+            fn try_format_eng(&self, sf: Option<usize>) -> Result<String, EngFmtError> {
+                try_format_eng_int(*self < 0, (*self as i128).unsigned_abs(), sf)
+            }
+
+            /// Returns the integer scaled by the largest power of 1024 not exceeding it, with its
+            /// [IEC prefix](https://en.wikipedia.org/wiki/Binary_prefix), rounded half away from
+            /// zero from its exact value.
+            ///
+            /// # Arguments
+            /// - `sf` - Number of significant figures, defaults to 3
+            fn format_bin(&self, sf: Option<usize>) -> String {
+                format_bin_int(*self < 0, (*self as i128).unsigned_abs(), sf)
+            }
+        }
+    )*};
+}
+
+impl_format_eng_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_format_eng_signed!(i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f32() {
+        assert_eq!(0.010_f32.format_eng(None), String::from("10.0e-3"));
+        assert_eq!(0.1_f32.format_eng(Some(9)), String::from("100.000001e-3"));
+        assert_eq!((-6.022e23_f32).format_eng(None), String::from("-602e21"));
+        assert_eq!(f32::NAN.format_eng(None), String::from("NaN"));
+        assert_eq!(4.7e3_f32.format_si(Some(2)), String::from("4.7k"));
+        assert_eq!(0.1_f32.format_eng_dp(8), String::from("100.00000149e-3"));
+        assert_eq!(f32::NAN.format_eng_dp(2), String::from("NaN"));
+    }
+    #[test]
+    fn test_group_boundaries() {
+        assert_eq!(999_u32.format_eng(None), String::from("999"));
+        assert_eq!(1000_u32.format_eng(None), String::from("1.00e3"));
+        assert_eq!(999_999_u32.format_eng(None), String::from("1.00e6"));
+        assert_eq!(999_999_u32.format_eng(Some(6)), String::from("999.999e3"));
+        assert_eq!(1_000_000_i32.format_eng(None), String::from("1.00e6"));
+        assert_eq!((-999_i16).format_eng(Some(2)), String::from("-1.0e3"));
+        assert_eq!(999_499_usize.format_eng(None), String::from("999e3"));
+        assert_eq!(999_500_isize.format_eng(None), String::from("1.00e6"));
+        assert_eq!(999_999_u32.format_eng_dp(2), String::from("1.00e6"));
+        assert_eq!((-123_456_i64).format_eng_dp(0), String::from("-123e3"));
+        assert_eq!(0_u8.format_eng_dp(1), String::from("0.0"));
+        assert_eq!(u128::MAX.format_eng_dp(3), String::from("340.282e36"));
+    }
+    #[test]
+    fn test_small_types() {
+        assert_eq!(0_u8.format_eng(None), String::from("0.00"));
+        assert_eq!(255_u8.format_eng(Some(1)), String::from("300"));
+        assert_eq!(i8::MIN.format_eng(None), String::from("-128"));
+        assert_eq!(u16::MAX.format_eng(None), String::from("65.5e3"));
+        assert_eq!(42_i64.format_eng(Some(4)), String::from("42.00"));
+    }
+    #[test]
+    fn test_format_bin() {
+        assert_eq!(1536_u64.format_bin(None), String::from("1.50Ki"));
+        assert_eq!(3_221_225_472_u64.format_bin(None), String::from("3.00Gi"));
+        assert_eq!(u64::MAX.format_bin(None), String::from("16.0Ei"));
+        assert_eq!((-1536_i32).format_bin(None), String::from("-1.50Ki"));
+        assert_eq!(1023_u16.format_bin(None), String::from("0.999Ki"));
+        assert_eq!(1536_f32.format_bin(None), String::from("1.50Ki"));
+    }
+    #[test]
+    fn test_large_integers() {
+        assert_eq!(u64::MAX.format_eng(None), String::from("18.4e18"));
+        // 2^53 + 1 is not an f64
+        assert_eq!(
+            9_007_199_254_740_993_u64.format_eng(Some(16)),
+            String::from("9.007199254740993e15")
+        );
+        assert_eq!(
+            u128::MAX.format_eng(Some(10)),
+            String::from("340.2823669e36")
+        );
+        assert_eq!(i128::MIN.format_eng(None), String::from("-170e36"));
+        assert_eq!(
+            i64::MIN.format_eng(Some(19)),
+            String::from("-9.223372036854775808e18")
+        );
+    }
+    #[test]
+    fn test_invalid_sf() {
+        assert_eq!(
+            7_u8.try_format_eng(Some(0)),
+            Err(EngFmtError::SigFigsZero { value: 7. })
+        );
+        assert_eq!(
+            1.5_f32.try_format_eng(Some(1000)),
+            Err(EngFmtError::SigFigsTooLarge {
+                sf: 1000,
+                value: 1.5
+            })
+        );
+    }
+    #[test]
+    fn test_si() {
+        assert_eq!(4_700_u32.format_si(None), String::from("4.70k"));
+        assert_eq!((-999_600_i64).format_si(None), String::from("-1.00M"));
+        assert_eq!(u128::MAX.format_si(None), String::from("340e36"));
+    }
+}
@@ -0,0 +1,155 @@
+//! Module for formatting probability-like values in [0, 1] in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use std::fmt;
+
+use crate::format_eng;
+
+/// Default threshold at or above which [format_prob] switches to one-minus form
+pub const PROB_THRESHOLD: f64 = 0.99;
+
+/// Error returned by [try_format_prob]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ProbError {
+    /// Value is NaN or outside [0, 1]
+    OutOfRange(f64),
+    /// Threshold is NaN or outside (0.5, 1)
+    InvalidThreshold(f64),
+}
+
+impl fmt::Display for ProbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProbError::OutOfRange(p) => write!(f, "probability {p} is outside [0, 1]"),
+            ProbError::InvalidThreshold(t) => {
+                write!(f, "one-minus threshold {t} is outside (0.5, 1)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProbError {}
+
+/// Returns probability `p` as string in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation), switching to one-minus form
+/// (e.g. `"1 - 320e-9"`) when `p` is at or above `threshold` so that the distance from 1 keeps
+/// its significant figures.  Values near 0 need no special form because engineering notation
+/// already keeps their significant figures.  Exactly 0 and exactly 1 are formatted plainly.
+///
+/// # Arguments
+/// - `p` - probability in [0, 1]
+/// - `sf` - number of significant figures, defaults to 3
+/// - `threshold` - value in (0.5, 1) at which one-minus form starts, defaults to
+///   [PROB_THRESHOLD]
+///
+/// # Examples
+/// ```
+/// use eng_fmt::try_format_prob;
+/// assert_eq!(try_format_prob(0.25, None, None).unwrap(), "250e-3");
+/// assert_eq!(try_format_prob(0.99999968, Some(2), None).unwrap(), "1 - 320e-9");
+/// assert!(try_format_prob(1.2, None, None).is_err());
+/// ```
+pub fn try_format_prob(
+    p: f64,
+    sf: Option<usize>,
+    threshold: Option<f64>,
+) -> Result<String, ProbError> {
+    let threshold = threshold.unwrap_or(PROB_THRESHOLD);
+    if !(threshold > 0.5 && threshold < 1.) {
+        return Err(ProbError::InvalidThreshold(threshold));
+    }
+    if !(0. ..=1.).contains(&p) {
+        return Err(ProbError::OutOfRange(p));
+    }
+    if p >= threshold && p < 1. {
+        // exact for p in [0.5, 1] (Sterbenz lemma)
+        Ok(format!("1 - {}", format_eng(1. - p, sf)))
+    } else {
+        Ok(format_eng(p, sf))
+    }
+}
+
+/// Same as [try_format_prob] with the default threshold, but panics if `p` is outside [0, 1].
+///
+/// # Arguments
+/// - `p` - probability in [0, 1]
+/// - `sf` - number of significant figures, defaults to 3
+pub fn format_prob(p: f64, sf: Option<usize>) -> String {
+    try_format_prob(p, sf, None).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Returns bit error rate `ber` in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation) without ever switching to
+/// one-minus form, since error rates live on the small side.  Panics if `ber` is outside
+/// [0, 1].
+///
+/// # Arguments
+/// - `ber` - bit error rate in [0, 1]
+/// - `sf` - number of significant figures, defaults to 3
+pub fn format_ber(ber: f64, sf: Option<usize>) -> String {
+    if !(0. ..=1.).contains(&ber) {
+        panic!("{}", ProbError::OutOfRange(ber));
+    }
+    format_eng(ber, sf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_half() {
+        assert_eq!(format_prob(0.5, None), String::from("500e-3"));
+    }
+    #[test]
+    fn test_1_minus_1e_12() {
+        assert_eq!(format_prob(1. - 1e-12, None), String::from("1 - 1.00e-12"));
+    }
+    #[test]
+    fn test_exactly_1() {
+        assert_eq!(format_prob(1., None), String::from("1.00"));
+    }
+    #[test]
+    fn test_0() {
+        assert_eq!(format_prob(0., None), String::from("0.00"));
+    }
+    #[test]
+    fn test_near_0() {
+        assert_eq!(format_prob(3.2e-7, Some(2)), String::from("320e-9"));
+    }
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(
+            try_format_prob(1.2, None, None),
+            Err(ProbError::OutOfRange(1.2))
+        );
+        assert!(try_format_prob(f64::NAN, None, None).is_err());
+        assert_eq!(
+            try_format_prob(-0.1, None, None),
+            Err(ProbError::OutOfRange(-0.1))
+        );
+    }
+    #[test]
+    #[should_panic(expected = "probability 1.2 is outside [0, 1]")]
+    fn test_out_of_range_panics() {
+        format_prob(1.2, None);
+    }
+    #[test]
+    fn test_threshold() {
+        assert_eq!(
+            try_format_prob(0.95, None, Some(0.9)),
+            Ok(String::from("1 - 50.0e-3"))
+        );
+        assert_eq!(format_prob(0.95, None), String::from("950e-3"));
+        assert_eq!(
+            try_format_prob(0.95, None, Some(1.)),
+            Err(ProbError::InvalidThreshold(1.))
+        );
+    }
+    #[test]
+    fn test_ber() {
+        assert_eq!(format_ber(2.5e-11, None), String::from("25.0e-12"));
+        assert_eq!(format_ber(0.999, None), String::from("999e-3"));
+    }
+}
@@ -0,0 +1,213 @@
+//! Module for reformatting the numbers embedded in prose, such as Markdown reports, in
+//! [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use crate::format_eng;
+use crate::prefix::si_exponent;
+
+/// Characters stripped from the start of a word before looking for a number
+const OPENING: [char; 6] = ['(', '[', '"', '\'', '“', '‘'];
+/// Characters stripped from the end of a word before looking for a number
+const CLOSING: [char; 11] = [')', ']', '"', '\'', ',', ';', '.', '!', '?', '”', '’'];
+
+/// Returns `text` with the numbers in it reformatted with [format_eng], leaving every other byte
+/// unchanged.  Numbers are only recognized conservatively, and anything unsure is left alone:
+/// - a number is a whole whitespace-separated word, apart from opening brackets or quotes
+///   before it and closing brackets, quotes, or sentence punctuation after it
+/// - it is an optional `-`, digits, and an optional fraction and exponent, e.g. `-0.01234` or
+///   `6.022e23`
+/// - it may be followed with no space by one of `units`, optionally behind an SI prefix, e.g.
+///   `4.7kΩ` with `units` containing `"Ω"`, which is rewritten in the base unit as `4.70e3Ω`;
+///   an exponent and a unit together are not recognized
+/// - bare integers without a fraction, exponent, or unit are skipped, since they are as likely
+///   to be years, counts, or identifiers as measurements
+///
+/// So version numbers like `1.2.3` or `v2.0`, dates like `2024-01-15` or `15/01/2024`, times,
+/// issue numbers like `#123`, hex strings like `0x1f` or `deadbeef`, grouped digits like
+/// `1,234.5`, and Markdown code or emphasis like `` `0.5` `` are never touched.
+///
+/// # Arguments
+/// - `text` - prose to be reformatted
+/// - `sf` - number of significant figures, defaults to 3
+/// - `units` - unit symbols that may directly follow a number
+///
+/// # Examples
+/// ```
+/// use eng_fmt::reformat_prose;
+/// assert_eq!(
+///     reformat_prose("Drift was 0.012345 V (see #123) after 2.2kΩ in 2024.", None, &["Ω"]),
+///     "Drift was 12.3e-3 V (see #123) after 2.20e3Ω in 2024."
+/// );
+/// ```
+pub fn reformat_prose(text: &str, sf: Option<usize>, units: &[&str]) -> String {
+    let mut reformatted = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        // alternate between whitespace and the word after it
+        let n_space = rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(rest.len());
+        reformatted.push_str(&rest[..n_space]);
+        rest = &rest[n_space..];
+        let n_word = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        reformat_word(&rest[..n_word], sf, units, &mut reformatted);
+        rest = &rest[n_word..];
+    }
+    reformatted
+}
+
+/// Pushes `word` onto `out`, with its number reformatted if it is recognized
+fn reformat_word(word: &str, sf: Option<usize>, units: &[&str], out: &mut String) {
+    let core = word.trim_start_matches(OPENING);
+    let start = word.len() - core.len();
+    let core = core.trim_end_matches(CLOSING);
+    match parse_quantity(core, units) {
+        Some((value, unit)) => {
+            out.push_str(&word[..start]);
+            out.push_str(&format_eng(value, sf));
+            out.push_str(unit);
+            out.push_str(&word[start + core.len()..]);
+        }
+        None => out.push_str(word),
+    }
+}
+
+/// Returns the value in the base unit and the unit of `core` if the whole of it is a number
+/// as described for [reformat_prose]
+fn parse_quantity<'a>(core: &str, units: &[&'a str]) -> Option<(f64, &'a str)> {
+    let bytes = core.as_bytes();
+    let mut i = usize::from(bytes.first() == Some(&b'-'));
+    let n_int = count_digits(&bytes[i..]);
+    if n_int == 0 {
+        return None;
+    }
+    i += n_int;
+    let mut plain_integer = true;
+    if bytes.get(i) == Some(&b'.') {
+        let n_frac = count_digits(&bytes[i + 1..]);
+        if n_frac == 0 {
+            return None;
+        }
+        i += 1 + n_frac;
+        plain_integer = false;
+    }
+    let number = &core[..i];
+    let suffix = &core[i..];
+    if suffix.is_empty() {
+        if plain_integer {
+            return None;
+        }
+        return Some((number.parse().ok()?, ""));
+    }
+    if let Some(exp) = suffix.strip_prefix(['e', 'E']) {
+        let digits = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+        if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Some((core.parse().ok()?, ""));
+        }
+    }
+    // the unit itself first, so e.g. `m` is metres rather than milli
+    if let Some(unit) = units.iter().find(|u| !u.is_empty() && **u == suffix) {
+        return Some((number.parse().ok()?, unit));
+    }
+    let prefix_len = suffix.chars().next()?.len_utf8();
+    let exp = si_exponent(&suffix[..prefix_len])?;
+    let unit = units
+        .iter()
+        .find(|u| !u.is_empty() && **u == &suffix[prefix_len..])?;
+    let value = format!("{number}e{exp}").parse().ok()?;
+    Some((value, unit))
+}
+
+/// Returns the number of leading ASCII digits in `bytes`
+fn count_digits(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNITS: [&str; 5] = ["V", "Ω", "Hz", "m", "s"];
+
+    #[test]
+    fn test_reformats() {
+        let table = [
+            ("x 0.012345 y", "x 12.3e-3 y"),
+            ("x -1234.5 y", "x -1.23e3 y"),
+            ("x 6.022e23 y", "x 602e21 y"),
+            ("x 1E-9 y", "x 1.00e-9 y"),
+            ("x 2.2kΩ y", "x 2.20e3Ω y"),
+            ("x 47uV y", "x 47.0e-6V y"),
+            ("x 3.3MHz y", "x 3.30e6Hz y"),
+            ("x 5m y", "x 5.00m y"),
+            ("x 12ms y", "x 12.0e-3s y"),
+            ("ends with 0.5.", "ends with 500e-3."),
+            ("(0.25) and [1.5],", "(250e-3) and [1.50],"),
+            ("\"0.125\"", "\"125e-3\""),
+            ("“1.0”", "“1.00”"),
+            ("0.1 0.2\n0.3\t0.4", "100e-3 200e-3\n300e-3\t400e-3"),
+            ("really 1.23456?", "really 1.23?"),
+        ];
+        for (text, expected) in table {
+            assert_eq!(
+                reformat_prose(text, None, &UNITS),
+                String::from(expected),
+                "text: {text}"
+            );
+        }
+    }
+    #[test]
+    fn test_leaves_alone() {
+        let table = [
+            "version 1.2.3 released",
+            "see v2.0 notes",
+            "on 2024-01-15 we",
+            "on 15/01/2024 we",
+            "at 12:30 sharp",
+            "fixes #123 and #4.5",
+            "hex 0x1f and deadbeef and 1e5a",
+            "about 1,234.5 units",
+            "code `0.5` here",
+            "bold **0.5** here",
+            "in 2024 there were 1500 samples",
+            "ip 192.168.1.1 here",
+            "the 1st and 3D cases",
+            "ratio 5-10 or 0.5-1.0",
+            "50% or 1.5x faster",
+            "2.2kX has an unknown unit",
+            "1e3V mixes an exponent and a unit",
+            "+0.5 has an explicit plus",
+            "trailing 3. and .5 forms",
+            "path/0.5 and 0.5/s",
+        ];
+        for text in table {
+            assert_eq!(
+                reformat_prose(text, None, &UNITS),
+                String::from(text),
+                "text: {text}"
+            );
+        }
+    }
+    #[test]
+    fn test_document() {
+        let document = "\
+# Results for v1.4.2 (2024-03-07)
+
+Run #42 measured 0.0047123 A through the 2.2kΩ shunt, i.e. 10.367 mV.
+The 3 boards (IDs 0x3f, 0x40) settled in 0.00125s; see `calib_0.5.json`.
+";
+        let expected = "\
+# Results for v1.4.2 (2024-03-07)
+
+Run #42 measured 4.71e-3 A through the 2.20e3Ω shunt, i.e. 10.4 mV.
+The 3 boards (IDs 0x3f, 0x40) settled in 1.25e-3s; see `calib_0.5.json`.
+";
+        assert_eq!(reformat_prose(document, None, &UNITS), expected);
+    }
+    #[test]
+    fn test_sf() {
+        assert_eq!(
+            reformat_prose("got 3.14159", Some(5), &[]),
+            String::from("got 3.1416")
+        );
+    }
+}
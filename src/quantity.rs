@@ -0,0 +1,186 @@
+//! Module for formatting physical quantities with a unit after the [SI
+//! prefix](https://en.wikipedia.org/wiki/Metric_prefix), e.g. `"4.70 kV"`.
+
+use crate::format_eng;
+use crate::si::format_si_tagged;
+
+/// Text between the number and the prefixed unit in [format_quantity_with]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UnitSeparator {
+    /// Space, e.g. `"4.70 kV"`
+    #[default]
+    Space,
+    /// Thin space (U+2009), e.g. `"4.70\u{2009}kV"`, as typeset in print
+    ThinSpace,
+    /// Nothing, e.g. `"4.70kV"`
+    NoSpace,
+}
+
+impl UnitSeparator {
+    /// Returns the separator text
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            UnitSeparator::Space => " ",
+            UnitSeparator::ThinSpace => "\u{2009}",
+            UnitSeparator::NoSpace => "",
+        }
+    }
+}
+
+/// Scale notation of [format_quantity_with]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum QuantityNotation {
+    /// SI prefix glued to the unit, e.g. `"4.70 kV"`, falling back to `e` notation, e.g.
+    /// `"1.50e27 V"`, outside yocto to yotta
+    #[default]
+    Prefix,
+    /// `e` notation of [format_eng] for every value, e.g. `"4.70e3 V"`
+    Exponent,
+}
+
+/// Options for [format_quantity_with]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QuantityOptions {
+    /// Text between the number and the prefixed unit
+    pub separator: UnitSeparator,
+    /// SI prefix or `e` notation
+    pub notation: QuantityNotation,
+}
+
+/// Same as [format_quantity_with] with [QuantityOptions::default], i.e. a space before the SI
+/// prefix and unit.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_quantity;
+/// assert_eq!(format_quantity(4.7e3, "V", None), "4.70 kV");
+/// assert_eq!(format_quantity(0.314, "A", None), "314 mA");
+/// assert_eq!(format_quantity(3.14, "Hz", None), "3.14 Hz");
+/// assert_eq!(format_quantity(6.022e-23, "kg", None), "60.2 ykg");
+/// ```
+pub fn format_quantity(x: f64, unit: &str, sf: Option<usize>) -> String {
+    format_quantity_with(x, unit, sf, QuantityOptions::default())
+}
+
+/// Returns `x` in [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation)
+/// followed by `unit`, with the SI prefix of the exponent, if any, written directly before the
+/// unit, e.g. `"4.70 kV"`, and an `e` exponent left on the number otherwise, e.g.
+/// `"1.50e27 V"`.  The digits are those of [format_eng].  An empty `unit` gives the plain
+/// output of [format_si](crate::format_si), or [format_eng] with
+/// [QuantityNotation::Exponent], with no separator.
+///
+/// The prefix is applied to `unit` as given, so a unit that already has one is prefixed again,
+/// e.g. 1000 with `"kg"` is `"1.00 kkg"`; pass masses in grams with `"g"` to get `"1.00 kg"`.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `unit` - unit symbol, e.g. `"V"`, or empty for a dimensionless value
+/// - `sf` - number of significant figures, defaults to 3
+/// - `opts` - separator and notation
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_quantity_with, QuantityNotation, QuantityOptions, UnitSeparator};
+/// let opts = QuantityOptions {
+///     separator: UnitSeparator::NoSpace,
+///     notation: QuantityNotation::Prefix,
+/// };
+/// assert_eq!(format_quantity_with(4.7e-6, "F", Some(2), opts), "4.7µF");
+/// let opts = QuantityOptions {
+///     separator: UnitSeparator::Space,
+///     notation: QuantityNotation::Exponent,
+/// };
+/// assert_eq!(format_quantity_with(6.022e-23, "kg", None, opts), "60.2e-24 kg");
+/// ```
+pub fn format_quantity_with(
+    x: f64,
+    unit: &str,
+    sf: Option<usize>,
+    opts: QuantityOptions,
+) -> String {
+    let separator = opts.separator.as_str();
+    match opts.notation {
+        QuantityNotation::Prefix => {
+            let tagged = format_si_tagged(x, sf);
+            if unit.is_empty() {
+                return tagged.text;
+            }
+            let (number, prefix) = match tagged.prefix {
+                Some(prefix) => (&tagged.text[tagged.mantissa_text_range], prefix.symbol()),
+                None => (tagged.text.as_str(), ""),
+            };
+            format!("{number}{separator}{prefix}{unit}")
+        }
+        QuantityNotation::Exponent => {
+            let number = format_eng(x, sf);
+            match unit.is_empty() {
+                true => number,
+                false => format!("{number}{separator}{unit}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_si;
+
+    #[test]
+    fn test_prefix() {
+        let table = [
+            (4.7e3, "V", None, "4.70 kV"),
+            (0.314, "A", None, "314 mA"),
+            (3.25, "Hz", None, "3.25 Hz"),
+            (-4.7e-6, "F", Some(2), "-4.7 µF"),
+            (999.7, "Ω", None, "1.00 kΩ"),
+            (0., "V", None, "0.00 V"),
+            (1.5e27, "V", None, "1.50e27 V"),
+            (6.022e-28, "kg", None, "602e-30 kg"),
+            (1e3, "kg", None, "1.00 kkg"),
+            (f64::NAN, "V", None, "NaN V"),
+            (f64::NEG_INFINITY, "W", None, "-inf W"),
+        ];
+        for (x, unit, sf, expected) in table {
+            assert_eq!(format_quantity(x, unit, sf), expected);
+        }
+    }
+    #[test]
+    fn test_separators() {
+        let table = [
+            (UnitSeparator::Space, "4.70 kV", "4.70e3 V"),
+            (
+                UnitSeparator::ThinSpace,
+                "4.70\u{2009}kV",
+                "4.70e3\u{2009}V",
+            ),
+            (UnitSeparator::NoSpace, "4.70kV", "4.70e3V"),
+        ];
+        for (separator, prefix, exponent) in table {
+            let opts = QuantityOptions {
+                separator,
+                notation: QuantityNotation::Prefix,
+            };
+            assert_eq!(format_quantity_with(4.7e3, "V", None, opts), prefix);
+            let opts = QuantityOptions {
+                separator,
+                notation: QuantityNotation::Exponent,
+            };
+            assert_eq!(format_quantity_with(4.7e3, "V", None, opts), exponent);
+        }
+    }
+    #[test]
+    fn test_dimensionless() {
+        for x in [4.7e3, -0.314, 3.25, 1.5e27, 0., f64::NAN] {
+            assert_eq!(format_quantity(x, "", None), format_si(x, None));
+            let opts = QuantityOptions {
+                separator: UnitSeparator::Space,
+                notation: QuantityNotation::Exponent,
+            };
+            assert_eq!(format_quantity_with(x, "", None, opts), format_eng(x, None));
+        }
+    }
+}
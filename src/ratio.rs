@@ -0,0 +1,192 @@
+//! Module for formatting ratios as logarithmic spans, such as decades and octaves, in
+//! [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use std::fmt;
+
+use crate::format_eng;
+
+/// Error returned when formatting ratios
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum RatioError {
+    /// Ratio is zero, negative, NaN, or infinite, so its logarithm is not a finite number
+    InvalidRatio(f64),
+    /// Frequency ratio is exactly 1, so a slope per decade is undefined
+    ZeroFrequencySpan,
+}
+
+impl fmt::Display for RatioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RatioError::InvalidRatio(ratio) => {
+                write!(f, "ratio {ratio} is not finite and positive")
+            }
+            RatioError::ZeroFrequencySpan => {
+                write!(f, "frequency ratio of 1 spans no decades")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RatioError {}
+
+/// Returns `ratio` unchanged, or an error if it has no finite logarithm
+fn check_ratio(ratio: f64) -> Result<f64, RatioError> {
+    if ratio.is_finite() && ratio > 0. {
+        Ok(ratio)
+    } else {
+        Err(RatioError::InvalidRatio(ratio))
+    }
+}
+
+/// Returns `log` formatted with [format_eng] followed by `label`, with a zero logarithm always
+/// positive
+fn format_log(log: f64, sf: Option<usize>, label: &str) -> String {
+    // adding 0. turns -0. into 0.
+    format!("{} {label}", format_eng(log + 0., sf))
+}
+
+/// Returns the number of decades spanned by `ratio`, i.e. its base-10 logarithm, in
+/// [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation), e.g.
+/// `"2.30 decades"` for 200.  Ratios below 1 give negative spans, and exactly 1 gives
+/// `"0.00 decades"`.
+///
+/// # Arguments
+/// - `ratio` - positive ratio, e.g. of two frequencies
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_decades;
+/// assert_eq!(format_decades(200., None).unwrap(), "2.30 decades");
+/// assert_eq!(format_decades(1e-3, None).unwrap(), "-3.00 decades");
+/// assert!(format_decades(0., None).is_err());
+/// ```
+pub fn format_decades(ratio: f64, sf: Option<usize>) -> Result<String, RatioError> {
+    Ok(format_log(check_ratio(ratio)?.log10(), sf, "decades"))
+}
+
+/// Returns the number of octaves spanned by `ratio`, i.e. its base-2 logarithm, in
+/// [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation), e.g.
+/// `"7.64 octaves"` for 200.  Ratios below 1 give negative spans, and exactly 1 gives
+/// `"0.00 octaves"`.
+///
+/// # Arguments
+/// - `ratio` - positive ratio, e.g. of two frequencies
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_octaves;
+/// assert_eq!(format_octaves(200., None).unwrap(), "7.64 octaves");
+/// assert_eq!(format_octaves(2., Some(4)).unwrap(), "1.000 octaves");
+/// ```
+pub fn format_octaves(ratio: f64, sf: Option<usize>) -> Result<String, RatioError> {
+    Ok(format_log(check_ratio(ratio)?.log2(), sf, "octaves"))
+}
+
+/// Returns the slope of an amplitude response in dB per decade at 3 significant figures, i.e.
+/// `20 log10(gain_ratio) / log10(freq_ratio)`, e.g. `"-20.0 dB/decade"` for a gain ratio of
+/// 0.1 over a frequency ratio of 10.
+///
+/// # Arguments
+/// - `gain_ratio` - positive ratio of output amplitudes at the two frequencies
+/// - `freq_ratio` - positive ratio of the two frequencies, other than 1
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_slope_db_per_decade;
+/// assert_eq!(format_slope_db_per_decade(0.1, 10.).unwrap(), "-20.0 dB/decade");
+/// assert_eq!(format_slope_db_per_decade(0.5, 4.).unwrap(), "-10.0 dB/decade");
+/// ```
+pub fn format_slope_db_per_decade(gain_ratio: f64, freq_ratio: f64) -> Result<String, RatioError> {
+    let gain_db = 20. * check_ratio(gain_ratio)?.log10();
+    let decades = check_ratio(freq_ratio)?.log10();
+    if decades == 0. {
+        return Err(RatioError::ZeroFrequencySpan);
+    }
+    Ok(format_log(gain_db / decades, None, "dB/decade"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decades() {
+        assert_eq!(format_decades(10., None), Ok(String::from("1.00 decades")));
+        assert_eq!(format_decades(2., None), Ok(String::from("301e-3 decades")));
+        assert_eq!(
+            format_decades(1. / 1000., None),
+            Ok(String::from("-3.00 decades"))
+        );
+        assert_eq!(format_decades(1., None), Ok(String::from("0.00 decades")));
+    }
+    #[test]
+    fn test_octaves() {
+        assert_eq!(
+            format_octaves(10., Some(4)),
+            Ok(String::from("3.322 octaves"))
+        );
+        assert_eq!(format_octaves(2., None), Ok(String::from("1.00 octaves")));
+        assert_eq!(
+            format_octaves(1. / 1000., None),
+            Ok(String::from("-9.97 octaves"))
+        );
+        assert_eq!(format_octaves(1., None), Ok(String::from("0.00 octaves")));
+    }
+    #[test]
+    fn test_slope() {
+        assert_eq!(
+            format_slope_db_per_decade(0.1, 10.),
+            Ok(String::from("-20.0 dB/decade"))
+        );
+        // 40 dB of gain over the three decades below
+        assert_eq!(
+            format_slope_db_per_decade(100., 1. / 1000.),
+            Ok(String::from("-13.3 dB/decade"))
+        );
+        // halving per octave is -20 dB/decade, and per two octaves half that
+        assert_eq!(
+            format_slope_db_per_decade(0.5, 2.),
+            Ok(String::from("-20.0 dB/decade"))
+        );
+        assert_eq!(
+            format_slope_db_per_decade(0.5, 4.),
+            Ok(String::from("-10.0 dB/decade"))
+        );
+        assert_eq!(
+            format_slope_db_per_decade(1., 10.),
+            Ok(String::from("0.00 dB/decade"))
+        );
+        assert_eq!(
+            format_slope_db_per_decade(1., 0.1),
+            Ok(String::from("0.00 dB/decade"))
+        );
+    }
+    #[test]
+    fn test_errors() {
+        for ratio in [0., -0., -2., f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                format_decades(ratio, None),
+                Err(RatioError::InvalidRatio(_))
+            ));
+            assert!(matches!(
+                format_octaves(ratio, None),
+                Err(RatioError::InvalidRatio(_))
+            ));
+            assert!(matches!(
+                format_slope_db_per_decade(ratio, 10.),
+                Err(RatioError::InvalidRatio(_))
+            ));
+        }
+        assert_eq!(
+            format_slope_db_per_decade(2., 1.),
+            Err(RatioError::ZeroFrequencySpan)
+        );
+        assert_eq!(
+            RatioError::InvalidRatio(-2.).to_string(),
+            "ratio -2 is not finite and positive"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Module for reporting how [format_eng](crate::format_eng) rounded a value.
+
+use crate::error::check_sf;
+use crate::exact::{format_rounded, round_exact, RoundingMode};
+use crate::format_eng;
+
+/// Prefix that [format_eng_approx] puts in front of inexact values
+pub const APPROX: &str = "≈";
+
+/// Description of the rounding behind a string returned by [format_eng_report]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundingReport {
+    /// Displayed value minus input value, correctly rounded to f64; NaN for non-finite input
+    pub error: f64,
+    /// True if rounding carried into the next power of ten, e.g. 999.7 → `"1.00e3"`
+    pub carried: bool,
+    /// True if the displayed value equals the input exactly, i.e. `error == 0`
+    pub exact: bool,
+}
+
+/// Returns `x` in [engineering notation](https://en.wikipedia.org/wiki/Engineering_notation)
+/// along with a [RoundingReport] computed from the exact decimal expansion of `x`, so that
+/// e.g. 0.1 is reported as inexact even though `"100e-3"` parses back to the same f64.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_report;
+/// let (formatted, report) = format_eng_report(999.7, None);
+/// assert_eq!(formatted, "1.00e3");
+/// assert!(report.carried && !report.exact);
+/// assert!((report.error - 0.3).abs() < 1e-12);
+/// ```
+pub fn format_eng_report(x: f64, sf: Option<usize>) -> (String, RoundingReport) {
+    let sf = check_sf(sf, x).unwrap_or_else(|err| panic!("{err}"));
+    if x == 0. || !x.is_finite() {
+        let exact = x == 0.;
+        let report = RoundingReport {
+            error: if exact { 0. } else { f64::NAN },
+            carried: false,
+            exact,
+        };
+        return (format_eng(x, Some(sf)), report);
+    }
+
+    let rounded = round_exact(x, sf, RoundingMode::HalfAwayFromZero);
+    let error = rounded.magnitude_error();
+    let report = RoundingReport {
+        error: if x < 0. { -error } else { error },
+        carried: rounded.carried,
+        exact: rounded.is_exact(),
+    };
+    (format_rounded(x < 0., &rounded), report)
+}
+
+/// Same as [format_eng_report] but returns only the string, prefixed with [APPROX] if it
+/// differs from `x`, e.g. `"≈333e-3"` for 1/3 and `"500e-3"` for 0.5.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+pub fn format_eng_approx(x: f64, sf: Option<usize>) -> String {
+    match format_eng_report(x, sf) {
+        (formatted, RoundingReport { exact: true, .. }) => formatted,
+        (formatted, _) => format!("{APPROX}{formatted}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_half() {
+        let (formatted, report) = format_eng_report(0.5, None);
+        assert_eq!(formatted, String::from("500e-3"));
+        assert_eq!(
+            report,
+            RoundingReport {
+                error: 0.,
+                carried: false,
+                exact: true
+            }
+        );
+    }
+    #[test]
+    fn test_exact_two() {
+        let (formatted, report) = format_eng_report(-2., Some(5));
+        assert_eq!(formatted, String::from("-2.0000"));
+        assert!(report.exact);
+        assert_eq!(report.error, 0.);
+    }
+    #[test]
+    fn test_exact_zero() {
+        assert_eq!(format_eng_report(0., None).0, String::from("0.00"));
+        assert!(format_eng_report(0., None).1.exact);
+    }
+    #[test]
+    fn test_carried() {
+        let (formatted, report) = format_eng_report(999.7, None);
+        assert_eq!(formatted, String::from("1.00e3"));
+        assert!(report.carried);
+        assert!(!report.exact);
+        // 999.7 is slightly below its decimal value, so the error is slightly above 0.3
+        assert_eq!(report.error, 1000. - 999.7);
+    }
+    #[test]
+    fn test_carried_negative() {
+        let (formatted, report) = format_eng_report(-9.9999e-6, Some(2));
+        assert_eq!(formatted, String::from("-10e-6"));
+        assert!(report.carried);
+        assert!(report.error < 0.);
+    }
+    #[test]
+    fn test_no_carry_within_group() {
+        let (formatted, report) = format_eng_report(1.5, Some(1));
+        assert_eq!(formatted, String::from("2"));
+        assert!(!report.carried);
+        assert_eq!(report.error, 0.5);
+    }
+    #[test]
+    fn test_rounded_down() {
+        let (formatted, report) = format_eng_report(std::f64::consts::PI, None);
+        assert_eq!(formatted, String::from("3.14"));
+        // exact error is -0.00159265358979311599796...
+        assert_eq!(report.error, -1.592653589793116e-3);
+    }
+    #[test]
+    fn test_tiny_error() {
+        // 0.1 round trips through "100e-3" but is 0.1000000000000000055511151231257827...
+        let (formatted, report) = format_eng_report(0.1, None);
+        assert_eq!(formatted, String::from("100e-3"));
+        assert!(!report.exact);
+        assert_eq!(report.error, -5.551115123125783e-18);
+    }
+    #[test]
+    fn test_non_finite() {
+        let (_, report) = format_eng_report(f64::INFINITY, None);
+        assert!(report.error.is_nan());
+        assert!(!report.exact);
+    }
+    #[test]
+    fn test_approx() {
+        assert_eq!(format_eng_approx(0.5, None), String::from("500e-3"));
+        assert_eq!(format_eng_approx(1. / 3., None), String::from("≈333e-3"));
+        assert_eq!(format_eng_approx(-999.7, None), String::from("≈-1.00e3"));
+        assert_eq!(format_eng_approx(0.1, None), String::from("≈100e-3"));
+    }
+}
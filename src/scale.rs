@@ -0,0 +1,200 @@
+//! Module for choosing round scale-bar lengths for images and plots.
+
+use std::fmt;
+
+use crate::format_si_tagged;
+
+/// Reference length chosen by [scale_bar]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleBar {
+    /// Length of the bar, 1, 2, or 5 times a power of ten
+    pub value: f64,
+    /// `value` with an SI prefix and the unit, if any, e.g. `"500 µm"`
+    pub label: String,
+}
+
+/// Error returned by [scale_bar]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ScaleBarError {
+    /// Span is zero, negative, or not finite
+    InvalidSpan(f64),
+    /// Target fraction is zero, negative, or not finite
+    InvalidFraction(f64),
+    /// Target bar length, `span * target_fraction`, or the bar nearest it overflows or is
+    /// subnormal, e.g. for a span of 1e308 with a target fraction of 10
+    TargetOutOfRange(f64),
+}
+
+impl fmt::Display for ScaleBarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaleBarError::InvalidSpan(span) => {
+                write!(f, "span {span} is not finite and positive")
+            }
+            ScaleBarError::InvalidFraction(fraction) => {
+                write!(f, "target fraction {fraction} is not finite and positive")
+            }
+            ScaleBarError::TargetOutOfRange(target) => {
+                write!(
+                    f,
+                    "target bar length {target:e} is out of the normal f64 range"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaleBarError {}
+
+/// Returns the 1, 2, or 5 times a power of ten nearest, on a log scale, to `span *
+/// target_fraction`, labeled with an SI prefix, e.g. a 2.7 mm wide micrograph with a target of
+/// 20 % gets a 500 µm bar.  The bar is always within a factor of √2.5 ≈ 1.58 of the target, so
+/// with a 20 % target it covers between 12.6 % and 31.7 % of the span.  Lengths outside yocto
+/// to yotta are labeled in `e` notation like [format_si](crate::format_si).  Targets or bars
+/// that overflow or are subnormal give [ScaleBarError::TargetOutOfRange] rather than a bar
+/// that is not 1, 2, or 5 times a power of ten.
+///
+/// # Arguments
+/// - `span` - full length of the axis or image
+/// - `target_fraction` - desired bar length as a fraction of `span`, e.g. `0.2`
+/// - `unit` - unit symbol appended after the prefix, e.g. `Some("m")`
+///
+/// # Examples
+/// ```
+/// use eng_fmt::scale_bar;
+/// let bar = scale_bar(2.7e-3, 0.2, Some("m")).unwrap();
+/// assert_eq!(bar.value, 500e-6);
+/// assert_eq!(bar.label, "500 µm");
+/// assert_eq!(scale_bar(27., 0.2, None).unwrap().label, "5");
+/// assert!(scale_bar(0., 0.2, None).is_err());
+/// ```
+pub fn scale_bar(
+    span: f64,
+    target_fraction: f64,
+    unit: Option<&str>,
+) -> Result<ScaleBar, ScaleBarError> {
+    if !(span.is_finite() && span > 0.) {
+        return Err(ScaleBarError::InvalidSpan(span));
+    }
+    if !(target_fraction.is_finite() && target_fraction > 0.) {
+        return Err(ScaleBarError::InvalidFraction(target_fraction));
+    }
+    let target = span * target_fraction;
+    if !target.is_normal() {
+        return Err(ScaleBarError::TargetOutOfRange(target));
+    }
+    let decade = target.log10().floor() as i32;
+    // nearest on a log scale among 1, 2, 5, and 10 times the decade
+    let (mantissa, exp) = [(1, decade), (2, decade), (5, decade), (1, decade + 1)]
+        .into_iter()
+        .min_by(|(m_a, exp_a), (m_b, exp_b)| {
+            let log_dist =
+                |m: u8, exp: i32| (f64::from(m).log10() + f64::from(exp) - target.log10()).abs();
+            log_dist(*m_a, *exp_a).total_cmp(&log_dist(*m_b, *exp_b))
+        })
+        .unwrap();
+    // parsed rather than scaled by a power of ten so the value is the nearest f64
+    let value: f64 = format!("{mantissa}e{exp}").parse().unwrap();
+    if !value.is_normal() {
+        return Err(ScaleBarError::TargetOutOfRange(target));
+    }
+    let tagged = format_si_tagged(value, Some(1));
+    let unit = unit.unwrap_or_default();
+    let label = match tagged.prefix {
+        Some(prefix) => format!(
+            "{} {}{unit}",
+            &tagged.text[tagged.mantissa_text_range],
+            prefix.symbol()
+        ),
+        None if unit.is_empty() => tagged.text,
+        None => format!("{} {unit}", tagged.text),
+    };
+    Ok(ScaleBar { value, label })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_eng_with_precision;
+
+    #[test]
+    fn test_scale_bar() {
+        let table = [
+            (2.7e-3, 0.2, "500 µm"),
+            (1., 0.2, "200 mm"),
+            (1.6, 0.2, "500 mm"),
+            (7.5, 0.2, "2 m"),
+            (10., 0.1, "1 m"),
+            (42e3, 0.25, "10 km"),
+            (3e-25, 0.2, "50e-27 m"),
+        ];
+        for (span, fraction, label) in table {
+            assert_eq!(
+                scale_bar(span, fraction, Some("m")).unwrap().label,
+                label,
+                "span: {span:e}"
+            );
+        }
+    }
+    #[test]
+    fn test_sweep_across_12_decades() {
+        // 50 spans per decade from 1 nm to 1 km
+        for i in 0..=600 {
+            let span = 10_f64.powf(-9. + f64::from(i) / 50.);
+            let bar = scale_bar(span, 0.2, None).unwrap();
+            let fraction = bar.value / span;
+            assert!(
+                (0.1..=0.4).contains(&fraction),
+                "span: {span:e}, bar: {bar:?}"
+            );
+            let mantissa = bar.label.split(' ').next().unwrap();
+            assert!(matches!(mantissa.trim_end_matches('0'), "1" | "2" | "5"));
+            let parsed = parse_eng_with_precision(&bar.label).map(|(value, _)| value);
+            assert_eq!(parsed, Ok(bar.value), "span: {span:e}");
+        }
+    }
+    #[test]
+    fn test_errors() {
+        for span in [0., -1., f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                scale_bar(span, 0.2, None).map_err(|err| err.to_string()),
+                Err(format!("span {span} is not finite and positive"))
+            );
+        }
+        assert_eq!(
+            scale_bar(1., -0.2, None),
+            Err(ScaleBarError::InvalidFraction(-0.2))
+        );
+        assert_eq!(
+            scale_bar(1., f64::INFINITY, None),
+            Err(ScaleBarError::InvalidFraction(f64::INFINITY))
+        );
+    }
+    #[test]
+    fn test_target_out_of_range() {
+        assert_eq!(
+            scale_bar(1e308, 10., None),
+            Err(ScaleBarError::TargetOutOfRange(f64::INFINITY))
+        );
+        assert_eq!(
+            scale_bar(5e-324, 0.1, None),
+            Err(ScaleBarError::TargetOutOfRange(0.))
+        );
+        assert_eq!(
+            scale_bar(1e-310, 1., None),
+            Err(ScaleBarError::TargetOutOfRange(1e-310))
+        );
+        // nearest bar, 2e308, overflows
+        assert_eq!(
+            scale_bar(1.5e308, 1., None),
+            Err(ScaleBarError::TargetOutOfRange(1.5e308))
+        );
+        assert_eq!(scale_bar(1e308, 1., None).unwrap().value, 1e308);
+        assert_eq!(scale_bar(1e-307, 1., None).unwrap().value, 1e-307);
+        assert_eq!(
+            ScaleBarError::TargetOutOfRange(f64::INFINITY).to_string(),
+            "target bar length inf is out of the normal f64 range"
+        );
+    }
+}
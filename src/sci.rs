@@ -0,0 +1,179 @@
+//! Module providing a drop-in replacement for `{:e}` and `{:E}` that rounds significant figures
+//! the way [format_eng](crate::format_eng) does.
+
+use std::fmt::{self, Write};
+
+use crate::exact::{round_exact, RoundingMode};
+
+/// Default number of significant figures when no precision is given, as for
+/// [format_eng](crate::format_eng)
+const DEFAULT_SF: usize = 3;
+
+/// Wrapper that formats an f64 in scientific notation shaped exactly like std's
+/// [LowerExp](fmt::LowerExp) and [UpperExp](fmt::UpperExp) output, e.g. `1.23e-7` (mantissa in
+/// [1, 10), no `+`, no exponent padding), so `{:e}` call sites can switch mechanically.
+/// Precision `p` gives `p + 1` significant figures like std.  It intentionally differs from
+/// std in two ways:
+/// - digits are rounded half away from zero from the exact binary value, so `{:.0e}` of 2.5 is
+///   `3e0` where std gives `2e0`
+/// - without a precision, 3 significant figures are shown rather than the shortest
+///   round-trip digits, so `{:e}` of 1.2345 is `1.23e0`
+///
+/// Width, fill, alignment, `+`, and `0` flags behave as for numbers.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::FormatSci;
+/// assert_eq!(format!("{:e}", 0.000123456.sci()), "1.23e-4");
+/// assert_eq!(format!("{:.4E}", (-6.02214076e23).sci()), "-6.0221E23");
+/// assert_eq!(format!("{:>10.1e}", 2.25.sci()), "     2.3e0");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sci(pub f64);
+
+/// Trait providing [Sci] adapters for use with `{:e}` and `{:E}`
+pub trait FormatSci {
+    /// Wraps the value for formatting with [LowerExp](fmt::LowerExp) or
+    /// [UpperExp](fmt::UpperExp)
+    fn sci(&self) -> Sci;
+}
+
+impl FormatSci for f64 {
+    fn sci(&self) -> Sci {
+        Sci(*self)
+    }
+}
+
+impl Sci {
+    /// Writes the value with exponent marker `e`, padding per the formatter's flags
+    fn fmt_with(&self, f: &mut fmt::Formatter<'_>, e: char) -> fmt::Result {
+        let x = self.0;
+        if x.is_nan() {
+            return pad_nan(f);
+        }
+        let nonnegative = !x.is_sign_negative();
+        if x.is_infinite() {
+            return f.pad_integral(nonnegative, "", "inf");
+        }
+        let sf = f.precision().map_or(DEFAULT_SF, |p| p + 1);
+        let (digits, exp_sci) = if x == 0. {
+            (vec![0; sf], 0)
+        } else {
+            let rounded = round_exact(x, sf, RoundingMode::HalfAwayFromZero);
+            (rounded.digits, rounded.exp_sci)
+        };
+        let mut magnitude = String::with_capacity(sf + 8);
+        magnitude.push(char::from(b'0' + digits[0]));
+        if sf > 1 {
+            magnitude.push('.');
+            magnitude.extend(digits[1..].iter().map(|d| char::from(b'0' + d)));
+        }
+        magnitude.push(e);
+        magnitude.push_str(&exp_sci.to_string());
+        f.pad_integral(nonnegative, "", &magnitude)
+    }
+}
+
+/// Writes `NaN` padded per the formatter's flags, which like std ignores `+` since NaN has no
+/// sign
+pub(crate) fn pad_nan(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let padding = f.width().unwrap_or(0).saturating_sub(3);
+    let (fill, align) = if f.sign_aware_zero_pad() {
+        ('0', fmt::Alignment::Right)
+    } else {
+        (f.fill(), f.align().unwrap_or(fmt::Alignment::Right))
+    };
+    let (before, after) = match align {
+        fmt::Alignment::Left => (0, padding),
+        fmt::Alignment::Right => (padding, 0),
+        fmt::Alignment::Center => (padding / 2, padding - padding / 2),
+    };
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str("NaN")?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+impl fmt::LowerExp for Sci {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, 'e')
+    }
+}
+
+impl fmt::UpperExp for Sci {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, 'E')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_std_with_precision() {
+        let values = [
+            1.,
+            -1.,
+            0.,
+            -0.,
+            123456.789,
+            6.02214076e23,
+            -1.602176634e-19,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+            999.96,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        for x in values {
+            for p in 0..=6 {
+                assert_eq!(
+                    format!("{:.*e}", p, x.sci()),
+                    format!("{:.*e}", p, x),
+                    "x: {x:e}"
+                );
+                assert_eq!(
+                    format!("{:.*E}", p, x.sci()),
+                    format!("{:.*E}", p, x),
+                    "x: {x:e}"
+                );
+            }
+        }
+    }
+    #[test]
+    fn test_matches_std_flags() {
+        for x in [1.5, -1.5, 0., f64::INFINITY, f64::NAN] {
+            assert_eq!(format!("{:+.2e}", x.sci()), format!("{:+.2e}", x));
+            assert_eq!(format!("{:012.2e}", x.sci()), format!("{:012.2e}", x));
+            assert_eq!(format!("{:*^12.2E}", x.sci()), format!("{:*^12.2E}", x));
+            assert_eq!(format!("{:<9.1e}|", x.sci()), format!("{:<9.1e}|", x));
+        }
+    }
+    #[test]
+    fn test_default_precision() {
+        // std gives the shortest round-trip digits instead
+        assert_eq!(format!("{:e}", 1.2345.sci()), String::from("1.23e0"));
+        assert_eq!(format!("{:e}", 1e-3.sci()), String::from("1.00e-3"));
+        assert_eq!(format!("{:E}", 0_f64.sci()), String::from("0.00E0"));
+    }
+    #[test]
+    fn test_ties_away_from_zero() {
+        // std rounds these exact ties to even
+        assert_eq!(format!("{:.0e}", 2.5.sci()), String::from("3e0"));
+        assert_eq!(format!("{:.0e}", 2.5), String::from("2e0"));
+        assert_eq!(format!("{:.1e}", (-0.125).sci()), String::from("-1.3e-1"));
+        assert_eq!(format!("{:.1e}", -0.125), String::from("-1.2e-1"));
+    }
+    #[test]
+    fn test_carry() {
+        assert_eq!(format!("{:.2e}", 9.996.sci()), String::from("1.00e1"));
+        assert_eq!(format!("{:.0e}", (-9.5).sci()), String::from("-1e1"));
+    }
+}
@@ -0,0 +1,273 @@
+//! Module for formatting values with [SI prefixes](https://en.wikipedia.org/wiki/Metric_prefix)
+//! in place of the engineering exponent.
+
+use std::ops::Range;
+
+use crate::format_eng;
+use crate::prefix::si_prefix;
+
+/// [SI prefix](https://en.wikipedia.org/wiki/Metric_prefix) from yocto to yotta
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SiPrefix {
+    Yocto,
+    Zepto,
+    Atto,
+    Femto,
+    Pico,
+    Nano,
+    Micro,
+    Milli,
+    Kilo,
+    Mega,
+    Giga,
+    Tera,
+    Peta,
+    Exa,
+    Zetta,
+    Yotta,
+}
+
+impl SiPrefix {
+    /// Returns the prefix for engineering exponent `exp`, or `None` for 0 and exponents that
+    /// have no prefix
+    pub fn from_exponent(exp: i32) -> Option<Self> {
+        use SiPrefix::*;
+        Some(match exp {
+            -24 => Yocto,
+            -21 => Zepto,
+            -18 => Atto,
+            -15 => Femto,
+            -12 => Pico,
+            -9 => Nano,
+            -6 => Micro,
+            -3 => Milli,
+            3 => Kilo,
+            6 => Mega,
+            9 => Giga,
+            12 => Tera,
+            15 => Peta,
+            18 => Exa,
+            21 => Zetta,
+            24 => Yotta,
+            _ => return None,
+        })
+    }
+
+    /// Returns the power of ten of the prefix, e.g. -3 for milli
+    pub fn exponent(self) -> i32 {
+        use SiPrefix::*;
+        match self {
+            Yocto => -24,
+            Zepto => -21,
+            Atto => -18,
+            Femto => -15,
+            Pico => -12,
+            Nano => -9,
+            Micro => -6,
+            Milli => -3,
+            Kilo => 3,
+            Mega => 6,
+            Giga => 9,
+            Tera => 12,
+            Peta => 15,
+            Exa => 18,
+            Zetta => 21,
+            Yotta => 24,
+        }
+    }
+
+    /// Returns the symbol of the prefix, with micro as `µ` (U+00B5 MICRO SIGN)
+    pub fn symbol(self) -> &'static str {
+        si_prefix(self.exponent()).unwrap().1
+    }
+}
+
+/// Output of [format_si_tagged]: the text of [format_si] with the parts a UI may want to style
+/// or label separately
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SiTagged {
+    /// Same string as [format_si]
+    pub text: String,
+    /// Prefix used in `text`, `None` for no prefix or `e` notation
+    pub prefix: Option<SiPrefix>,
+    /// Engineering exponent of the value, whether shown as a prefix or in `e` notation
+    pub exponent: i32,
+    /// Byte range of the mantissa, including any `-` sign, in `text`
+    pub mantissa_text_range: Range<usize>,
+}
+
+/// Returns f64 as string in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation) with the exponent replaced by
+/// its [SI prefix](https://en.wikipedia.org/wiki/Metric_prefix), e.g. `"4.70k"` rather than
+/// `"4.70e3"`, with micro as `µ` (U+00B5 MICRO SIGN).  The mantissa is exactly that of
+/// [format_eng], including carries such as 999.6 becoming `"1.00k"`.  Exponents outside yocto
+/// to yotta, i.e. below -24 or above 24, keep the `e` notation of [format_eng].
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_si;
+/// assert_eq!(format_si(0.010, None), "10.0m");
+/// assert_eq!(format_si(6.022e-23, None), "60.2y");
+/// assert_eq!(format_si(4.7e3, Some(2)), "4.7k");
+/// assert_eq!(format_si(1.5e27, None), "1.50e27");
+/// ```
+pub fn format_si(x: f64, sf: Option<usize>) -> String {
+    si_from_eng(format_eng(x, sf))
+}
+
+/// Returns [format_si] of `x` together with the prefix, engineering exponent, and mantissa
+/// byte range within the text, e.g. `"-4.70k"` with [SiPrefix::Kilo], 3, and `0..5`.
+/// Zero and non-finite values have no prefix, exponent 0, and a mantissa spanning the text.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_si_tagged, SiPrefix};
+/// let tagged = format_si_tagged(-4.7e3, None);
+/// assert_eq!(tagged.text, "-4.70k");
+/// assert_eq!(tagged.prefix, Some(SiPrefix::Kilo));
+/// assert_eq!(tagged.exponent, 3);
+/// assert_eq!(&tagged.text[tagged.mantissa_text_range], "-4.70");
+/// ```
+pub fn format_si_tagged(x: f64, sf: Option<usize>) -> SiTagged {
+    let formatted = format_eng(x, sf);
+    let (n_mantissa, exponent) = match formatted.split_once('e') {
+        Some((mantissa, exp)) => (mantissa.len(), exp.parse().unwrap()),
+        None => (formatted.len(), 0),
+    };
+    SiTagged {
+        text: si_from_eng(formatted),
+        prefix: SiPrefix::from_exponent(exponent),
+        exponent,
+        mantissa_text_range: 0..n_mantissa,
+    }
+}
+
+/// Replaces the exponent of `formatted`, as returned by [format_eng], with its SI prefix if it
+/// has one
+pub(crate) fn si_from_eng(formatted: String) -> String {
+    let Some((mantissa, exp)) = formatted.split_once('e') else {
+        return formatted;
+    };
+    match si_prefix(exp.parse().unwrap()) {
+        Some((_, symbol, _)) => format!("{mantissa}{symbol}"),
+        None => formatted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefixes() {
+        let table = [
+            (1.23e-24, "1.23y"),
+            (1.23e-21, "1.23z"),
+            (1.23e-18, "1.23a"),
+            (1.23e-15, "1.23f"),
+            (1.23e-12, "1.23p"),
+            (1.23e-9, "1.23n"),
+            (1.23e-6, "1.23µ"),
+            (1.23e-3, "1.23m"),
+            (1.23, "1.23"),
+            (1.23e3, "1.23k"),
+            (1.23e6, "1.23M"),
+            (1.23e9, "1.23G"),
+            (1.23e12, "1.23T"),
+            (1.23e15, "1.23P"),
+            (1.23e18, "1.23E"),
+            (1.23e21, "1.23Z"),
+            (1.23e24, "1.23Y"),
+        ];
+        for (x, expected) in table {
+            assert_eq!(format_si(x, None), String::from(expected));
+        }
+    }
+    #[test]
+    fn test_carry() {
+        assert_eq!(format_si(999.6, None), String::from("1.00k"));
+        assert_eq!(format_si(-999_999.7, Some(6)), String::from("-1.00000M"));
+        assert_eq!(format_si(999.6e24, None), String::from("1.00e27"));
+    }
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(format_si(1e-25, None), String::from("100e-27"));
+        assert_eq!(format_si(-2.5e30, None), String::from("-2.50e30"));
+    }
+    #[test]
+    fn test_tagged_matches_format_si() {
+        for k in -30..=30 {
+            for x in [1.234, -9.996, 5.5] {
+                let x = x * 10_f64.powi(k);
+                let tagged = format_si_tagged(x, None);
+                assert_eq!(tagged.text, format_si(x, None));
+                assert_eq!(
+                    tagged.text[tagged.mantissa_text_range.clone()].parse::<f64>(),
+                    format_eng(x, None)
+                        .split('e')
+                        .next()
+                        .unwrap()
+                        .parse::<f64>()
+                );
+                match tagged.prefix {
+                    Some(prefix) => assert!(tagged.text.ends_with(prefix.symbol())),
+                    None => assert!(tagged.exponent == 0 || tagged.exponent.abs() > 24),
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_tagged() {
+        assert_eq!(
+            format_si_tagged(0., None),
+            SiTagged {
+                text: String::from("0.00"),
+                prefix: None,
+                exponent: 0,
+                mantissa_text_range: 0..4,
+            }
+        );
+        assert_eq!(
+            format_si_tagged(-2.5e30, None),
+            SiTagged {
+                text: String::from("-2.50e30"),
+                prefix: None,
+                exponent: 30,
+                mantissa_text_range: 0..5,
+            }
+        );
+        assert_eq!(
+            format_si_tagged(-999.6e-9, None),
+            SiTagged {
+                text: String::from("-1.00µ"),
+                prefix: Some(SiPrefix::Micro),
+                exponent: -6,
+                mantissa_text_range: 0..5,
+            }
+        );
+    }
+    #[test]
+    fn test_prefix_round_trip() {
+        for exp in -30..=30 {
+            if let Some(prefix) = SiPrefix::from_exponent(exp) {
+                assert_eq!(prefix.exponent(), exp);
+            }
+        }
+        assert_eq!(SiPrefix::from_exponent(0), None);
+        assert_eq!(SiPrefix::Micro.symbol(), "µ");
+    }
+    #[test]
+    fn test_special() {
+        assert_eq!(format_si(0., None), String::from("0.00"));
+        assert_eq!(format_si(f64::INFINITY, None), String::from("inf"));
+        assert_eq!(format_si(f64::NAN, None), String::from("NaN"));
+    }
+}
@@ -0,0 +1,144 @@
+//! Module for formatting in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) into caller-supplied sinks
+//! without allocating.
+
+use std::fmt;
+
+use crate::error::check_sf;
+use crate::exact::write_eng_checked;
+
+/// Writes `x` formatted exactly as by [format_eng](crate::format_eng) into `w` without any heap
+/// allocation, e.g. straight into a log line or a stack buffer.
+///
+/// # Arguments
+/// - `w` - sink to write into
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use std::fmt::Write;
+/// use eng_fmt::format_eng_into;
+/// let mut line = String::from("I = ");
+/// format_eng_into(&mut line, 0.0123456, None).unwrap();
+/// write!(line, " A").unwrap();
+/// assert_eq!(line, "I = 12.3e-3 A");
+/// ```
+pub fn format_eng_into<W: fmt::Write>(w: &mut W, x: f64, sf: Option<usize>) -> fmt::Result {
+    let sf = check_sf(sf, x).unwrap_or_else(|err| panic!("{err}"));
+    write_eng_checked(w, x, sf)
+}
+
+/// Writes `x` formatted exactly as by [format_eng](crate::format_eng) to the start of `buf`
+/// without any heap allocation and returns the written text, or [fmt::Error] if `buf` is too
+/// short.  `sf + 8` bytes always suffice, as for `"-100e-324"` at 1 significant figure.
+///
+/// # Arguments
+/// - `buf` - buffer to write into
+/// - `x` - value to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_buf;
+/// let mut buf = [0_u8; 16];
+/// assert_eq!(format_eng_buf(&mut buf, -6.022e-23, Some(4)), Ok("-60.22e-24"));
+/// assert!(format_eng_buf(&mut buf[..4], -6.022e-23, Some(4)).is_err());
+/// ```
+pub fn format_eng_buf(buf: &mut [u8], x: f64, sf: Option<usize>) -> Result<&str, fmt::Error> {
+    let mut writer = SliceWriter { buf, len: 0 };
+    format_eng_into(&mut writer, x, sf)?;
+    let SliceWriter { buf, len } = writer;
+    // only whole `str`s are ever copied in
+    Ok(std::str::from_utf8(&buf[..len]).unwrap())
+}
+
+/// [fmt::Write] over a byte slice that fails rather than growing
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        let dest = self.buf.get_mut(self.len..end).ok_or(fmt::Error)?;
+        dest.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_eng;
+
+    #[test]
+    fn test_matches_format_eng() {
+        let mut values = vec![
+            0.,
+            -0.,
+            1.,
+            -1.,
+            0.85,
+            2.5,
+            999.5,
+            9.9994e-6,
+            5e-324,
+            f64::MIN_POSITIVE,
+            f64::MAX,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..2_000 {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            values.push(f64::from_bits(state));
+        }
+        let mut buf = [0_u8; 40];
+        for x in values {
+            for sf in [1, 2, 3, 4, 7, 15, 16, 17, 30] {
+                let expected = format_eng(x, Some(sf));
+                let mut written = String::new();
+                format_eng_into(&mut written, x, Some(sf)).unwrap();
+                assert_eq!(written, expected, "x: {x:e}, sf: {sf}");
+                assert_eq!(format_eng_buf(&mut buf, x, Some(sf)), Ok(expected.as_str()));
+            }
+        }
+    }
+    #[test]
+    fn test_max_sig_figs() {
+        let mut buf = [0_u8; crate::MAX_SIG_FIGS + 8];
+        for x in [5e-324, -f64::MAX, 1. / 3.] {
+            let expected = format_eng(x, Some(crate::MAX_SIG_FIGS));
+            let written = format_eng_buf(&mut buf, x, Some(crate::MAX_SIG_FIGS));
+            assert_eq!(written, Ok(expected.as_str()));
+        }
+    }
+    #[test]
+    fn test_buf_too_short() {
+        let mut buf = [0_u8; 6];
+        assert_eq!(format_eng_buf(&mut buf, 1.23e3, None), Ok("1.23e3"));
+        assert_eq!(format_eng_buf(&mut buf, -1.23e3, None), Err(fmt::Error));
+        assert_eq!(format_eng_buf(&mut [], 0., Some(1)), Err(fmt::Error));
+        // longest output at 1 significant figure is `sf + 8` bytes
+        let mut buf = [0_u8; 9];
+        assert_eq!(format_eng_buf(&mut buf, -1e-322, Some(1)), Ok("-100e-324"));
+    }
+    #[test]
+    #[should_panic(expected = "0 significant figures")]
+    fn test_zero_sf_panics() {
+        let _ = format_eng_into(&mut String::new(), 1., Some(0));
+    }
+}
@@ -0,0 +1,135 @@
+//! Module for formatting slices in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) as aligned columns.
+
+use crate::fixed::{common_eng_exp, format_eng_fixed};
+use crate::format_eng;
+
+/// Exponent choice for [format_eng_slice]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColumnExponent {
+    /// Each element with its own engineering exponent, as by [format_eng]
+    #[default]
+    PerElement,
+    /// Every element on the exponent of the largest, as by [format_eng_fixed] with
+    /// [common_eng_exp]
+    Shared,
+}
+
+/// Returns every element of `xs` in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation), padded with spaces to one
+/// width so that, printed one per line, the decimal points line up and so do the `e` markers.
+/// Integer parts are right-aligned, and fractions and exponents left-aligned, so a value
+/// without a decimal point or exponent gets blanks in their place.  NaN and infinities are
+/// right-aligned with the integer parts.
+///
+/// # Arguments
+/// - `xs` - values to be formatted
+/// - `sf` - number of significant figures, defaults to 3
+/// - `exponent` - per-element or shared exponent
+///
+/// # Panics
+/// If `sf` is 0 or more than [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_eng_slice, ColumnExponent};
+/// let column = format_eng_slice(&[1.0, 22.5e3, -0.003], None, ColumnExponent::PerElement);
+/// assert_eq!(column, [" 1.00   ", "22.5 e3 ", "-3.00e-3"]);
+/// let column = format_eng_slice(&[150e-6, 12e-3, 450e-3], None, ColumnExponent::Shared);
+/// assert_eq!(column, ["  0.150e-3", " 12.0  e-3", "450    e-3"]);
+/// ```
+pub fn format_eng_slice(xs: &[f64], sf: Option<usize>, exponent: ColumnExponent) -> Vec<String> {
+    let formatted: Vec<String> = match exponent {
+        ColumnExponent::PerElement => xs.iter().map(|x| format_eng(*x, sf)).collect(),
+        ColumnExponent::Shared => {
+            let exp = common_eng_exp(xs);
+            xs.iter()
+                .map(|x| format_eng_fixed(*x, exp, sf).unwrap())
+                .collect()
+        }
+    };
+    let parts: Vec<[&str; 3]> = formatted.iter().map(|s| split_parts(s)).collect();
+    let widths: [usize; 3] = std::array::from_fn(|i| {
+        parts
+            .iter()
+            .map(|p| p[i].chars().count())
+            .max()
+            .unwrap_or(0)
+    });
+    parts
+        .iter()
+        .map(|[int, frac, exp]| {
+            format!(
+                "{int:>w_int$}{frac:<w_frac$}{exp:<w_exp$}",
+                w_int = widths[0],
+                w_frac = widths[1],
+                w_exp = widths[2]
+            )
+        })
+        .collect()
+}
+
+/// Splits `formatted` into its signed integer part, its fraction from the decimal point, and
+/// its exponent from the `e`, each possibly empty
+fn split_parts(formatted: &str) -> [&str; 3] {
+    let (mantissa, exp) = match formatted.find('e') {
+        Some(i) if formatted != "NaN" => formatted.split_at(i),
+        _ => (formatted, ""),
+    };
+    let (int, frac) = mantissa.split_at(mantissa.find('.').unwrap_or(mantissa.len()));
+    [int, frac, exp]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_element() {
+        let column = format_eng_slice(
+            &[1.0, 22.5e3, -0.003, 150e-6, 7e6],
+            Some(2),
+            ColumnExponent::PerElement,
+        );
+        assert_eq!(
+            column,
+            ["  1.0   ", " 23  e3 ", " -3.0e-3", "150  e-6", "  7.0e6 "]
+        );
+    }
+    #[test]
+    fn test_shared() {
+        let column = format_eng_slice(&[150e-6, 12e-3, -450e-3], None, ColumnExponent::Shared);
+        assert_eq!(column, ["   0.150e-3", "  12.0  e-3", "-450    e-3"]);
+    }
+    #[test]
+    fn test_equal_widths() {
+        let xs = [1.0, 22.5e3, -0.003, 0., -1e-300, 999.7, f64::MAX];
+        for exponent in [ColumnExponent::PerElement, ColumnExponent::Shared] {
+            let column = format_eng_slice(&xs, Some(4), exponent);
+            let width = column[0].chars().count();
+            assert!(column.iter().all(|s| s.chars().count() == width));
+            let points: Vec<Option<usize>> = column.iter().map(|s| s.find('.')).collect();
+            assert!(points.iter().flatten().all(|i| Some(*i) == points[0]));
+        }
+    }
+    #[test]
+    fn test_non_finite() {
+        let column = format_eng_slice(
+            &[f64::NAN, -2.5e-3, f64::NEG_INFINITY, f64::INFINITY],
+            None,
+            ColumnExponent::PerElement,
+        );
+        assert_eq!(
+            column,
+            [" NaN      ", "  -2.50e-3", "-inf      ", " inf      "]
+        );
+        let column = format_eng_slice(&[f64::NAN, 1.5], None, ColumnExponent::Shared);
+        assert_eq!(column, ["NaN   ", "  1.50"]);
+    }
+    #[test]
+    fn test_empty() {
+        for exponent in [ColumnExponent::PerElement, ColumnExponent::Shared] {
+            assert!(format_eng_slice(&[], None, exponent).is_empty());
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Module for long-form, screen-reader friendly output such as `"4.7 microfarads"`.
+
+use crate::format_eng;
+use crate::prefix::si_prefix;
+
+/// Hooks for turning units into English words, used by [format_eng_spoken_with]
+#[derive(Clone, Copy, Debug)]
+pub struct SpokenWords {
+    /// Returns the singular word for a unit symbol, e.g. `"F"` → `"farad"`, or `None` to use
+    /// the unit text as given
+    pub unit_word: fn(&str) -> Option<&'static str>,
+    /// Returns the plural form of a singular unit word, e.g. `"henry"` → `"henries"`
+    pub pluralize: fn(&str) -> String,
+}
+
+impl Default for SpokenWords {
+    fn default() -> Self {
+        Self {
+            unit_word: english_unit_word,
+            pluralize: english_plural,
+        }
+    }
+}
+
+/// Returns the singular English word for common SI unit symbols
+pub fn english_unit_word(symbol: &str) -> Option<&'static str> {
+    Some(match symbol {
+        "A" => "ampere",
+        "C" => "coulomb",
+        "F" => "farad",
+        "g" => "gram",
+        "H" => "henry",
+        "Hz" => "hertz",
+        "J" => "joule",
+        "K" => "kelvin",
+        "m" => "meter",
+        "mol" => "mole",
+        "N" => "newton",
+        "Pa" => "pascal",
+        "s" => "second",
+        "S" => "siemens",
+        "T" => "tesla",
+        "V" => "volt",
+        "W" => "watt",
+        "Wb" => "weber",
+        "Ω" | "ohm" => "ohm",
+        _ => return None,
+    })
+}
+
+/// Returns the English plural of `word`, leaving units such as hertz, siemens, and lux
+/// unchanged
+pub fn english_plural(word: &str) -> String {
+    let consonant_y = word.len() > 1
+        && word.ends_with('y')
+        && !word[..word.len() - 1].ends_with(['a', 'e', 'i', 'o', 'u']);
+    if word.is_empty() || word.ends_with(['s', 'x', 'z']) {
+        word.to_string()
+    } else if consonant_y {
+        format!("{}ies", &word[..word.len() - 1])
+    } else {
+        format!("{word}s")
+    }
+}
+
+/// Same as [format_eng_spoken_with] with 3 significant figures and [SpokenWords::default].
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_spoken;
+/// assert_eq!(format_eng_spoken(4.7e-6, "F"), "4.7 microfarads");
+/// assert_eq!(format_eng_spoken(-1e3, "V"), "minus 1 kilovolt");
+/// ```
+pub fn format_eng_spoken(x: f64, unit: &str) -> String {
+    format_eng_spoken_with(x, unit, None, &SpokenWords::default())
+}
+
+/// Returns `x` with `unit` as words suitable for an `aria-label`, e.g. `"4.7 microfarads"`:
+/// the number is read without an exponent or trailing zeros, the SI prefix and unit are
+/// spelled out, and the unit is plural unless the number read before it is exactly 1.  Values
+/// outside the yocto–yotta range, and values with an empty `unit`, use
+/// `"times ten to the N"` instead of a prefix.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `unit` - unit symbol or word, e.g. `"F"` or `"sample"`
+/// - `sf` - number of significant figures, defaults to 3
+/// - `words` - unit word lookup and pluralization hooks
+pub fn format_eng_spoken_with(
+    x: f64,
+    unit: &str,
+    sf: Option<usize>,
+    words: &SpokenWords,
+) -> String {
+    let unit_word = (words.unit_word)(unit).unwrap_or(unit);
+    if x.is_nan() {
+        return format!("not a number {}", (words.pluralize)(unit_word));
+    }
+    let sign = if x.is_sign_negative() && x != 0. {
+        "minus "
+    } else {
+        ""
+    };
+    if x.is_infinite() {
+        return format!("{sign}infinity {}", (words.pluralize)(unit_word));
+    }
+
+    let formatted = format_eng(x.abs(), sf);
+    let (mantissa, exp) = match formatted.split_once('e') {
+        Some((mantissa, exp)) => (mantissa, exp.parse().unwrap()),
+        None => (formatted.as_str(), 0),
+    };
+    let mantissa = if mantissa.contains('.') {
+        mantissa.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        mantissa
+    };
+    let prefix = si_prefix(exp).filter(|_| !unit.is_empty() || exp == 0);
+    let quantity = if mantissa == "1" && prefix.is_some() {
+        unit_word.to_string()
+    } else {
+        (words.pluralize)(unit_word)
+    };
+
+    match prefix {
+        Some((_, _, prefix)) => format!("{sign}{mantissa} {prefix}{quantity}")
+            .trim_end()
+            .to_string(),
+        None => {
+            let power = match exp {
+                0 => String::new(),
+                exp if exp < 0 => format!(" times ten to the minus {}", -exp),
+                exp => format!(" times ten to the {exp}"),
+            };
+            format!("{sign}{mantissa}{power} {quantity}")
+                .trim_end()
+                .to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plural() {
+        assert_eq!(
+            format_eng_spoken(4.7e-6, "F"),
+            String::from("4.7 microfarads")
+        );
+    }
+    #[test]
+    fn test_singular() {
+        assert_eq!(format_eng_spoken(1., "V"), String::from("1 volt"));
+        assert_eq!(format_eng_spoken(1.0004, "V"), String::from("1 volt"));
+    }
+    #[test]
+    fn test_singular_mantissa_with_prefix() {
+        assert_eq!(format_eng_spoken(1e3, "V"), String::from("1 kilovolt"));
+    }
+    #[test]
+    fn test_negative() {
+        assert_eq!(
+            format_eng_spoken(-2.5e-3, "A"),
+            String::from("minus 2.5 milliamperes")
+        );
+    }
+    #[test]
+    fn test_zero() {
+        assert_eq!(format_eng_spoken(0., "Hz"), String::from("0 hertz"));
+    }
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(
+            format_eng_spoken(6.022e-28, "g"),
+            String::from("602 times ten to the minus 30 grams")
+        );
+        assert_eq!(
+            format_eng_spoken(3.3e27, "H"),
+            String::from("3.3 times ten to the 27 henries")
+        );
+    }
+    #[test]
+    fn test_unknown_unit_word() {
+        assert_eq!(
+            format_eng_spoken(4.2e3, "sample"),
+            String::from("4.2 kilosamples")
+        );
+    }
+    #[test]
+    fn test_dimensionless() {
+        assert_eq!(
+            format_eng_spoken(4.7e-6, ""),
+            String::from("4.7 times ten to the minus 6")
+        );
+        assert_eq!(format_eng_spoken(12., ""), String::from("12"));
+    }
+    #[test]
+    fn test_overrides() {
+        let words = SpokenWords {
+            unit_word: |unit| match unit {
+                "F" => Some("Farad"),
+                _ => english_unit_word(unit),
+            },
+            pluralize: |word| format!("{word}(s)"),
+        };
+        assert_eq!(
+            format_eng_spoken_with(4.70e-6, "F", Some(2), &words),
+            String::from("4.7 microFarad(s)")
+        );
+    }
+    #[test]
+    fn test_english_plural() {
+        assert_eq!(english_plural("henry"), String::from("henries"));
+        assert_eq!(english_plural("day"), String::from("days"));
+        assert_eq!(english_plural("siemens"), String::from("siemens"));
+        assert_eq!(english_plural("lux"), String::from("lux"));
+        assert_eq!(english_plural("ohm"), String::from("ohms"));
+    }
+}
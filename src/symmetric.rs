@@ -0,0 +1,137 @@
+//! Module for `±` displays that lock both numbers onto one [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) exponent.
+
+use crate::exact::round_at_place;
+use crate::format_eng;
+
+/// Returns `center ± half_width` with both numbers on the exponent and number of decimals of
+/// `center` formatted at `sf` significant figures, e.g. `"(2.350 ± 0.012)e-3"`, whatever
+/// exponent `half_width` would take on its own.  `half_width` is rounded half away from zero to
+/// the last decimal of `center`, so it may show fewer significant figures, show only zeros
+/// (including when `half_width` is 0), or have more digits left of the decimal point than
+/// `center`.  Parentheses and exponent are omitted when the exponent is 0.
+///
+/// # Arguments
+/// - `center` - value at the middle of the band
+/// - `half_width` - distance from `center` to either edge of the band; its sign is ignored
+/// - `sf` - number of significant figures of `center`, defaults to 3
+///
+/// # Panics
+/// If `center` or `half_width` is not finite.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::format_eng_symmetric;
+/// assert_eq!(format_eng_symmetric(2.35e-3, 1.2e-5, Some(4)), "(2.350 ± 0.012)e-3");
+/// assert_eq!(format_eng_symmetric(0.5e-3, 0.0004, None), "(500 ± 400)e-6");
+/// ```
+pub fn format_eng_symmetric(center: f64, half_width: f64, sf: Option<usize>) -> String {
+    assert!(
+        center.is_finite() && half_width.is_finite(),
+        "`format_eng_symmetric` args `center` and `half_width` must be finite."
+    );
+    let formatted = format_eng(center, sf);
+    let (mantissa, exp) = match formatted.split_once('e') {
+        Some((mantissa, exp)) => (mantissa, exp.parse().unwrap()),
+        None => (formatted.as_str(), 0),
+    };
+    let n_dec = mantissa.split_once('.').map_or(0, |(_, dec)| dec.len());
+
+    // `half_width / 10^exp` rounded to `n_dec` decimals, as an integer count of the last place
+    let count = round_at_place(half_width, exp - n_dec as i32);
+    let count = format!("{count:0>width$}", width = n_dec + 1);
+    let (int, dec) = count.split_at(count.len() - n_dec);
+    let half_width = match n_dec {
+        0 => int.to_string(),
+        _ => format!("{int}.{dec}"),
+    };
+
+    match exp {
+        0 => format!("{mantissa} ± {half_width}"),
+        _ => format!("({mantissa} ± {half_width})e{exp}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locked_exponent() {
+        assert_eq!(
+            format_eng_symmetric(2.35e-3, 1.2e-5, Some(4)),
+            String::from("(2.350 ± 0.012)e-3")
+        );
+    }
+    #[test]
+    fn test_half_width_smaller_natural_exponent() {
+        // 400e-6 would be 400e-6 on its own, but 0.0004 with a 1.00e-3 center is 0.40e-3
+        assert_eq!(
+            format_eng_symmetric(1e-3, 0.0004, None),
+            String::from("(1.00 ± 0.40)e-3")
+        );
+    }
+    #[test]
+    fn test_half_width_larger_than_center() {
+        assert_eq!(
+            format_eng_symmetric(1.5e3, 25e3, None),
+            String::from("(1.50 ± 25.00)e3")
+        );
+        assert_eq!(
+            format_eng_symmetric(1.5e3, 2e6, Some(2)),
+            String::from("(1.5 ± 2000.0)e3")
+        );
+    }
+    #[test]
+    fn test_zero_half_width() {
+        assert_eq!(
+            format_eng_symmetric(2.35e-3, 0., Some(4)),
+            String::from("(2.350 ± 0.000)e-3")
+        );
+    }
+    #[test]
+    fn test_half_width_rounds_to_zero() {
+        assert_eq!(
+            format_eng_symmetric(47e3, 4e-3, None),
+            String::from("(47.0 ± 0.0)e3")
+        );
+    }
+    #[test]
+    fn test_negative_center() {
+        assert_eq!(
+            format_eng_symmetric(-2.35e-3, 1.2e-5, Some(4)),
+            String::from("(-2.350 ± 0.012)e-3")
+        );
+    }
+    #[test]
+    fn test_negative_half_width() {
+        assert_eq!(
+            format_eng_symmetric(2.35e-3, -1.2e-5, Some(4)),
+            String::from("(2.350 ± 0.012)e-3")
+        );
+    }
+    #[test]
+    fn test_exponent_zero() {
+        assert_eq!(
+            format_eng_symmetric(2.35, 0.125, None),
+            String::from("2.35 ± 0.13")
+        );
+        assert_eq!(
+            format_eng_symmetric(0., 0.5, None),
+            String::from("0.00 ± 0.50")
+        );
+    }
+    #[test]
+    fn test_center_carry() {
+        // the exponent is chosen after rounding the center
+        assert_eq!(
+            format_eng_symmetric(999.7, 2.5, None),
+            String::from("(1.00 ± 0.00)e3")
+        );
+    }
+    #[test]
+    #[should_panic(expected = "must be finite")]
+    fn test_non_finite() {
+        format_eng_symmetric(1., f64::NAN, None);
+    }
+}
@@ -0,0 +1,311 @@
+//! Module for tracking a live value and summarizing its recent history with [SI
+//! prefixes](https://en.wikipedia.org/wiki/Metric_prefix), e.g. for monitoring displays.
+
+use crate::exact::with_eng_parts;
+use crate::fixed::format_eng_fixed;
+use crate::si::SiPrefix;
+use crate::MAX_SIG_FIGS;
+
+/// Options for [EngTracker]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrackerOptions {
+    /// Number of significant figures of the latest value, defaults to 3
+    pub sf: Option<usize>,
+    /// Unit symbol printed after the SI prefix, e.g. `"Ω"`
+    pub unit: String,
+}
+
+/// Number of significant figures of the change since the previous sample
+const DELTA_SF: usize = 2;
+/// Number of significant figures of the windowed standard deviation
+const SIGMA_SF: usize = 1;
+/// Factor by which the latest value may leave the `[1, 1000)` mantissa range of the shared prefix
+/// before the prefix follows it
+const PREFIX_HYSTERESIS: f64 = 2.;
+
+/// Fixed-capacity history of samples that summarizes the latest one, e.g.
+/// `"4.32 mΩ  (Δ +12 µΩ, 3-sample σ 4 µΩ)"`.  The change since the previous sample is shown
+/// with 2 significant figures and an explicit sign, and the sample standard deviation of the
+/// finite samples in the window with 1.  All three share the SI prefix of the latest value,
+/// which is sticky: it only follows the latest finite, nonzero value once its mantissa at that
+/// prefix leaves `[0.5, 2000)`, so a value hovering around a decade boundary such as 1 Ω does
+/// not flip between `"999 mΩ"` and `"1.00 Ω"`.  Pushing never allocates, since the window is a
+/// ring buffer allocated up front.
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{EngTracker, TrackerOptions};
+/// let opts = TrackerOptions { sf: None, unit: "Ω".into() };
+/// let mut tracker = EngTracker::new(3, opts);
+/// tracker.push(4.30e-3);
+/// assert_eq!(tracker.latest_line(), "4.30 mΩ");
+/// tracker.push(4.308e-3);
+/// tracker.push(4.320e-3);
+/// assert_eq!(tracker.latest_line(), "4.32 mΩ  (Δ +0.012 mΩ, 3-sample σ 0.01 mΩ)");
+/// ```
+#[derive(Clone, Debug)]
+pub struct EngTracker {
+    /// Ring buffer of the most recent samples
+    window: Vec<f64>,
+    /// Index in `window` of the next sample to be overwritten once it is full
+    next: usize,
+    /// Engineering exponent of the shared SI prefix, `None` until a finite, nonzero push
+    exponent: Option<i32>,
+    /// Resolved number of significant figures of the latest value
+    sf: usize,
+    opts: TrackerOptions,
+}
+
+impl EngTracker {
+    /// Returns an empty tracker keeping the latest `capacity` samples
+    ///
+    /// # Arguments
+    /// - `capacity` - number of samples in the standard deviation window, at least 2
+    /// - `opts` - significant figures and unit
+    ///
+    /// # Panics
+    /// If `capacity` is less than 2, or `opts.sf` is 0 or more than
+    /// [MAX_SIG_FIGS](crate::MAX_SIG_FIGS).
+    pub fn new(capacity: usize, opts: TrackerOptions) -> Self {
+        assert!(
+            capacity >= 2,
+            "`EngTracker::new` arg `capacity` must be at least 2."
+        );
+        let sf = opts.sf.unwrap_or(3);
+        assert!(
+            (1..=MAX_SIG_FIGS).contains(&sf),
+            "`EngTracker::new` arg `opts.sf` must be from 1 to {MAX_SIG_FIGS}."
+        );
+        Self {
+            window: Vec::with_capacity(capacity),
+            next: 0,
+            exponent: None,
+            sf,
+            opts,
+        }
+    }
+
+    /// Adds sample `x`, replacing the oldest one once the window is full
+    pub fn push(&mut self, x: f64) {
+        if self.window.len() < self.window.capacity() {
+            self.window.push(x);
+        } else {
+            self.window[self.next] = x;
+            self.next = (self.next + 1) % self.window.len();
+        }
+        self.update_exponent(x);
+    }
+
+    /// Moves the shared prefix to that of `x` once `x` leaves its hysteresis band
+    fn update_exponent(&mut self, x: f64) {
+        if !x.is_finite() || x == 0. {
+            return;
+        }
+        if let Some(exp) = self.exponent {
+            let mantissa = x.abs() / 10_f64.powi(exp);
+            if (1. / PREFIX_HYSTERESIS..1000. * PREFIX_HYSTERESIS).contains(&mantissa) {
+                return;
+            }
+        }
+        // the exponent after rounding, so 999.96 at 3 significant figures moves to kilo
+        self.exponent = Some(with_eng_parts(x, self.sf, |_, _, exp| exp));
+    }
+
+    /// Returns the `back`-th most recent sample, 0 being the latest
+    fn nth_latest(&self, back: usize) -> Option<f64> {
+        let len = self.window.len();
+        if back >= len {
+            return None;
+        }
+        // `next` is the oldest sample once full, and 0 before that
+        let latest = (self.next + len - 1) % len;
+        Some(self.window[(latest + len - back) % len])
+    }
+
+    /// Returns the latest sample, or `None` before the first push
+    pub fn latest(&self) -> Option<f64> {
+        self.nth_latest(0)
+    }
+
+    /// Returns the latest sample minus the previous one, or `None` if either is missing or not
+    /// finite
+    pub fn delta(&self) -> Option<f64> {
+        let (latest, previous) = (self.nth_latest(0)?, self.nth_latest(1)?);
+        let delta = latest - previous;
+        delta.is_finite().then_some(delta)
+    }
+
+    /// Returns the sample standard deviation of the finite samples in the window and how many
+    /// there are, or `None` if there are fewer than 2
+    pub fn sigma(&self) -> Option<(f64, usize)> {
+        let finite = || self.window.iter().filter(|x| x.is_finite());
+        let n = finite().count();
+        if n < 2 {
+            return None;
+        }
+        let mean = finite().sum::<f64>() / n as f64;
+        let sum_sq: f64 = finite().map(|x| (x - mean) * (x - mean)).sum();
+        Some(((sum_sq / (n - 1) as f64).sqrt(), n))
+    }
+
+    /// Returns the summary line of the latest sample, with the change and standard deviation
+    /// in parentheses when available, or an empty string before the first push
+    pub fn latest_line(&self) -> String {
+        let Some(latest) = self.latest() else {
+            return String::new();
+        };
+        let mut line = self.quantity(latest, self.sf);
+        let mut details = Vec::with_capacity(2);
+        if let Some(delta) = self.delta() {
+            let sign = if delta.is_sign_negative() { "" } else { "+" };
+            details.push(format!("Δ {sign}{}", self.quantity(delta, DELTA_SF)));
+        }
+        if let Some((sigma, n)) = self.sigma() {
+            details.push(format!("{n}-sample σ {}", self.quantity(sigma, SIGMA_SF)));
+        }
+        if !details.is_empty() {
+            line.push_str("  (");
+            line.push_str(&details.join(", "));
+            line.push(')');
+        }
+        line
+    }
+
+    /// Returns `x` scaled to the shared SI prefix, separated from the prefix and unit by a
+    /// space, or with its `e` exponent where the prefix has no symbol
+    fn quantity(&self, x: f64, sf: usize) -> String {
+        let exp = self.exponent.unwrap_or(0);
+        let text =
+            format_eng_fixed(x, exp, Some(sf)).expect("shared exponent is always a multiple of 3");
+        let unit = self.opts.unit.as_str();
+        match SiPrefix::from_exponent(exp) {
+            Some(prefix) if x.is_finite() => {
+                let mantissa = text.split_once('e').map_or(text.as_str(), |(m, _)| m);
+                format!("{mantissa} {}{unit}", prefix.symbol())
+            }
+            _ if unit.is_empty() => text,
+            _ => format!("{text} {unit}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ohms(capacity: usize) -> EngTracker {
+        EngTracker::new(
+            capacity,
+            TrackerOptions {
+                sf: None,
+                unit: String::from("Ω"),
+            },
+        )
+    }
+
+    #[test]
+    fn test_sequence() {
+        let mut tracker = ohms(3);
+        assert_eq!(tracker.latest_line(), String::new());
+        let expected = [
+            (4.30e-3, "4.30 mΩ"),
+            (4.308e-3, "4.31 mΩ  (Δ +0.0080 mΩ, 2-sample σ 0.006 mΩ)"),
+            (4.320e-3, "4.32 mΩ  (Δ +0.012 mΩ, 3-sample σ 0.01 mΩ)"),
+            (4.316e-3, "4.32 mΩ  (Δ -0.0040 mΩ, 3-sample σ 0.006 mΩ)"),
+            // still within the band of milli
+            (1.2, "1200 mΩ  (Δ +1200 mΩ, 3-sample σ 700 mΩ)"),
+            (2.5, "2.50 Ω  (Δ +1.3 Ω, 3-sample σ 1 Ω)"),
+        ];
+        for (x, line) in expected {
+            tracker.push(x);
+            assert_eq!(tracker.latest_line(), String::from(line), "x: {x}");
+        }
+    }
+    #[test]
+    fn test_nan_sample() {
+        let mut tracker = ohms(3);
+        tracker.push(1.0);
+        tracker.push(f64::NAN);
+        assert_eq!(tracker.latest_line(), String::from("NaN Ω"));
+        tracker.push(2.0);
+        assert_eq!(
+            tracker.latest_line(),
+            String::from("2.00 Ω  (2-sample σ 0.7 Ω)")
+        );
+        tracker.push(2.5);
+        assert_eq!(
+            tracker.latest_line(),
+            String::from("2.50 Ω  (Δ +0.50 Ω, 2-sample σ 0.4 Ω)")
+        );
+    }
+    #[test]
+    fn test_ring_buffer() {
+        let mut tracker = ohms(2);
+        for x in [1., 2., 3., 5.] {
+            tracker.push(x);
+        }
+        assert_eq!(tracker.latest(), Some(5.));
+        assert_eq!(tracker.delta(), Some(2.));
+        assert_eq!(tracker.sigma(), Some((2_f64.sqrt(), 2)));
+        // pushing reuses the window allocated up front
+        let before = tracker.window.as_ptr();
+        tracker.push(8.);
+        assert_eq!(tracker.window.as_ptr(), before);
+        assert_eq!(tracker.window.capacity(), 2);
+    }
+    #[test]
+    fn test_no_unit_and_zero_delta() {
+        let mut tracker = EngTracker::new(4, TrackerOptions::default());
+        tracker.push(1e30);
+        tracker.push(1e30);
+        assert_eq!(
+            tracker.latest_line(),
+            String::from("1.00e30  (Δ +0.0e30, 2-sample σ 0e30)")
+        );
+    }
+    #[test]
+    fn test_sticky_prefix() {
+        let mut tracker = ohms(2);
+        let expected = [
+            (0.999, "999 mΩ"),
+            (1.001, "1000 mΩ  (Δ +2.0 mΩ, 2-sample σ 1 mΩ)"),
+            (0.998, "998 mΩ  (Δ -3.0 mΩ, 2-sample σ 2 mΩ)"),
+            (1.9, "1900 mΩ  (Δ +900 mΩ, 2-sample σ 600 mΩ)"),
+            (2.1, "2.10 Ω  (Δ +0.20 Ω, 2-sample σ 0.1 Ω)"),
+            (0.9, "0.900 Ω  (Δ -1.2 Ω, 2-sample σ 0.8 Ω)"),
+            (0.4, "400 mΩ  (Δ -500 mΩ, 2-sample σ 400 mΩ)"),
+        ];
+        for (x, line) in expected {
+            tracker.push(x);
+            assert_eq!(tracker.latest_line(), String::from(line), "x: {x}");
+        }
+        // NaN and zero keep the prefix
+        tracker.push(f64::NAN);
+        tracker.push(0.);
+        assert_eq!(tracker.latest_line(), String::from("0.00 mΩ"));
+    }
+    #[test]
+    fn test_rounding_carry() {
+        let mut tracker = ohms(2);
+        tracker.push(999.96);
+        assert_eq!(tracker.latest_line(), String::from("1.00 kΩ"));
+    }
+    #[test]
+    #[should_panic(expected = "`EngTracker::new` arg `opts.sf` must be from 1 to 767.")]
+    fn test_sf_zero() {
+        let opts = TrackerOptions {
+            sf: Some(0),
+            unit: String::new(),
+        };
+        EngTracker::new(2, opts);
+    }
+    #[test]
+    #[should_panic(expected = "`EngTracker::new` arg `opts.sf` must be from 1 to 767.")]
+    fn test_sf_too_large() {
+        let opts = TrackerOptions {
+            sf: Some(MAX_SIG_FIGS + 1),
+            unit: String::new(),
+        };
+        EngTracker::new(2, opts);
+    }
+}
@@ -0,0 +1,260 @@
+//! Module for shortening already-formatted [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) strings.
+
+use std::borrow::Cow;
+
+use crate::exact::{try_round_digits, write_eng_into, RoundingMode};
+use crate::math::mod_floor_i32;
+
+/// Marker returned by [truncate_eng] when nothing meaningful fits
+pub const TRUNCATED: &str = "…";
+
+/// Shortens an already-formatted engineering notation string to at most `max_chars` characters
+/// by dropping mantissa digits, re-rounding the remaining digits to nearest.  The exponent or
+/// any trailing prefix/unit (e.g. `" µ"`) is always kept intact; length is counted in `char`s,
+/// not bytes.  The decimal digits are rounded as written, never through an f64, so e.g.
+/// `"1.15"`, `"1.15e-6"`, and `"1.15 µ"` all round up.  When the string is plain or has an `e`
+/// exponent, the result is in engineering notation as by [format_eng](crate::format_eng) so
+/// that a rounding carry renormalizes the exponent.  Returns [TRUNCATED]
+/// if even a single significant figure plus the suffix does not fit, or if `s` does not start
+/// with a number, and an empty string in those cases if `max_chars` is 0, so the result never
+/// exceeds `max_chars`.
+///
+/// # Arguments
+/// - `s` - formatted string to be shortened
+/// - `max_chars` - maximum number of `char`s in the result
+///
+/// # Examples
+/// ```
+/// use eng_fmt::truncate_eng;
+/// assert_eq!(truncate_eng("3.14159e-3", 8), "3.142e-3");
+/// assert_eq!(truncate_eng("4.70 µ", 5), "4.7 µ");
+/// assert_eq!(truncate_eng("-60.2e-24", 4), "…");
+/// ```
+pub fn truncate_eng(s: &str, max_chars: usize) -> Cow<'_, str> {
+    if s.chars().count() <= max_chars {
+        return Cow::Borrowed(s);
+    }
+
+    let n_sign = usize::from(s.starts_with(['-', '+']));
+    let n_mantissa = s[n_sign..]
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len() - n_sign);
+    let (sign, rest) = s.split_at(n_sign);
+    let (mantissa, suffix) = rest.split_at(n_mantissa);
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if !int_part
+        .chars()
+        .chain(frac_part.chars())
+        .any(|c| c.is_ascii_digit())
+    {
+        return Cow::Borrowed(truncated(max_chars));
+    }
+
+    let fits = |candidate: &str| candidate.chars().count() <= max_chars;
+
+    // exponent of a plain or `e` notation number, whose digits are renormalized
+    let exp = match suffix.strip_prefix(['e', 'E']) {
+        _ if suffix.is_empty() => Some(0),
+        Some(exp) => exp.parse::<i32>().ok(),
+        None => None,
+    };
+    let digits: Vec<u8> = int_part
+        .bytes()
+        .chain(frac_part.bytes())
+        .map(|b| b - b'0')
+        .collect();
+    let n_zeros = leading_zeros(mantissa);
+    if let (Some(exp), Some(significant)) = (exp, digits.get(n_zeros..).filter(|d| !d.is_empty())) {
+        let exp_sci = i64::from(exp) + int_part.len() as i64 - 1 - n_zeros as i64;
+        let exp_char = if suffix.starts_with('E') { 'E' } else { 'e' };
+        for sf in (1..significant.len()).rev() {
+            // exponents beyond the `i32` limits cannot be written, so never fit
+            let Some(rounded) = i32::try_from(exp_sci)
+                .ok()
+                .and_then(|exp_sci| {
+                    try_round_digits(
+                        significant,
+                        exp_sci,
+                        sf,
+                        RoundingMode::HalfAwayFromZero,
+                        false,
+                    )
+                })
+                .filter(|rounded| {
+                    rounded
+                        .exp_sci
+                        .checked_sub(mod_floor_i32(rounded.exp_sci, 3))
+                        .is_some()
+                })
+            else {
+                continue;
+            };
+            let ascii: Vec<u8> = rounded.digits.iter().map(|d| b'0' + d).collect();
+            let mut candidate = String::from(sign);
+            write_eng_into(&mut candidate, false, &ascii, rounded.exp_sci, exp_char).unwrap();
+            if fits(&candidate) {
+                return Cow::Owned(candidate);
+            }
+        }
+        return Cow::Borrowed(truncated(max_chars));
+    }
+
+    for n_dec in (0..frac_part.len()).rev() {
+        let candidate = format!(
+            "{sign}{}{suffix}",
+            round_decimals(int_part, frac_part, n_dec)
+        );
+        if fits(&candidate) {
+            return Cow::Owned(candidate);
+        }
+    }
+    Cow::Borrowed(truncated(max_chars))
+}
+
+/// Returns [TRUNCATED], or an empty string if even it does not fit in `max_chars` characters
+pub(crate) fn truncated(max_chars: usize) -> &'static str {
+    match TRUNCATED.chars().count() <= max_chars {
+        true => TRUNCATED,
+        false => "",
+    }
+}
+
+/// Returns number of non-significant leading zeros in `mantissa`, ignoring the decimal point
+fn leading_zeros(mantissa: &str) -> usize {
+    mantissa
+        .chars()
+        .filter(|c| *c != '.')
+        .take_while(|c| *c == '0')
+        .count()
+}
+
+/// Rounds the decimal digit string `int_part.frac_part` half away from zero to `n_dec`
+/// decimal places, where `n_dec < frac_part.len()`
+fn round_decimals(int_part: &str, frac_part: &str, n_dec: usize) -> String {
+    let mut digits: Vec<u8> = int_part
+        .bytes()
+        .chain(frac_part.bytes().take(n_dec))
+        .map(|b| b - b'0')
+        .collect();
+    let mut n_int = int_part.len();
+    if frac_part.as_bytes()[n_dec] >= b'5' {
+        let mut i = digits.len();
+        loop {
+            if i == 0 {
+                digits.insert(0, 1);
+                n_int += 1;
+                break;
+            }
+            i -= 1;
+            if digits[i] == 9 {
+                digits[i] = 0;
+            } else {
+                digits[i] += 1;
+                break;
+            }
+        }
+    }
+    let mut rounded: String = digits.iter().map(|d| char::from(b'0' + d)).collect();
+    if n_int == 0 {
+        rounded.insert(0, '0');
+        n_int = 1;
+    }
+    if n_dec > 0 {
+        rounded.insert(n_int, '.');
+    }
+    rounded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts `truncate_eng(s, budget)` for every budget from 3 to the full length of `s`
+    fn check_sweep(s: &str, expected: &[&str]) {
+        let n_chars = s.chars().count();
+        assert_eq!(expected.len(), n_chars - 2);
+        for (budget, expected) in (3..=n_chars).zip(expected) {
+            let truncated = truncate_eng(s, budget);
+            assert_eq!(truncated, *expected, "budget: {budget}");
+            assert!(truncated.chars().count() <= budget);
+        }
+    }
+
+    #[test]
+    fn test_sweep_plain() {
+        check_sweep("3.14159", &["3.1", "3.14", "3.142", "3.1416", "3.14159"]);
+    }
+    #[test]
+    fn test_sweep_exp() {
+        check_sweep(
+            "-314.159e-3",
+            &[
+                "…",
+                "…",
+                "…",
+                "…",
+                "-314e-3",
+                "-314e-3",
+                "-314.2e-3",
+                "-314.16e-3",
+                "-314.159e-3",
+            ],
+        );
+    }
+    #[test]
+    fn test_sweep_never_cuts_exponent() {
+        check_sweep("60.2e-24", &["…", "…", "…", "60e-24", "60e-24", "60.2e-24"]);
+    }
+    #[test]
+    fn test_sweep_prefix() {
+        // 'µ' is 2 bytes but 1 char
+        check_sweep("4.705 µ", &["5 µ", "5 µ", "4.7 µ", "4.71 µ", "4.705 µ"]);
+        check_sweep("4.705µ", &["5µ", "4.7µ", "4.71µ", "4.705µ"]);
+    }
+    #[test]
+    fn test_sweep_unit_carry() {
+        check_sweep(
+            "999.96 mV",
+            &["…", "…", "…", "…", "1000 mV", "1000 mV", "999.96 mV"],
+        );
+    }
+    #[test]
+    fn test_rounds_digits_as_written() {
+        // 1.15 is stored as 1.149999..., which must not decide the rounding
+        assert_eq!(truncate_eng("1.15", 3), "1.2");
+        assert_eq!(truncate_eng("1.15e-6", 6), "1.2e-6");
+        assert_eq!(truncate_eng("1.15 µ", 5), "1.2 µ");
+        assert_eq!(truncate_eng("9.995", 4), "10.0");
+        assert_eq!(truncate_eng("999.96", 5), "1.0e3");
+        assert_eq!(truncate_eng("1.005E3", 6), "1.01E3");
+    }
+    #[test]
+    fn test_exponent_limits() {
+        assert_eq!(truncate_eng("9.96e2147483646", 14), "10e2147483646");
+        assert_eq!(truncate_eng("9.96e2147483647", 14), TRUNCATED);
+        assert_eq!(truncate_eng("1.25e-2147483648", 15), TRUNCATED);
+    }
+    #[test]
+    fn test_zero_budget() {
+        for s in ["-60.2e-24", "4.70 µ", "abc"] {
+            assert_eq!(truncate_eng(s, 1), TRUNCATED, "{s}");
+            assert_eq!(truncate_eng(s, 0), "", "{s}");
+        }
+        assert_eq!(truncate_eng("3.14159", 1), "3");
+        assert_eq!(truncate_eng("3.14159", 0), "");
+        assert_eq!(truncate_eng("", 0), "");
+    }
+    #[test]
+    fn test_sub_unity_mantissa() {
+        assert_eq!(truncate_eng("0.0196 k", 6), "0.02 k");
+    }
+    #[test]
+    fn test_not_a_number() {
+        assert_eq!(truncate_eng("NaN units", 4), TRUNCATED);
+    }
+    #[test]
+    fn test_borrowed_when_fits() {
+        assert!(matches!(truncate_eng("1.00e3", 6), Cow::Borrowed(_)));
+    }
+}
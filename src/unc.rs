@@ -0,0 +1,157 @@
+//! Module for formatting a value with its uncertainty, the value's precision set by the
+//! uncertainty, on one [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation) exponent.
+
+use crate::exact::{round_at_place, round_exact, RoundingMode};
+use crate::format_eng;
+use crate::math::eng_exponent_from_sci;
+
+/// Notation of the uncertainty in [format_eng_unc]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UncertaintyStyle {
+    /// Both numbers with the exponent, e.g. `"48.23e3 ± 0.13e3"`
+    #[default]
+    PlusMinus,
+    /// Uncertainty in units of the last digit of the value, e.g. `"48.23(13)e3"`
+    Parenthetical,
+}
+
+/// Returns `x` with its uncertainty `u` in [engineering
+/// notation](https://en.wikipedia.org/wiki/Engineering_notation), e.g. `"48.23e3 ± 0.13e3"`,
+/// with `u` rounded to 2 significant figures and `x` rounded to the same place, both half away
+/// from zero from their exact values.  Both numbers share the engineering exponent of the larger
+/// of the two after rounding, so `u` larger than `x` works, e.g. `"0.5 ± 2.3"`, and `x` may
+/// round to zero.  The exponent is omitted when it is 0, and `x` keeps its sign.  The sign of
+/// `u` is ignored, and a zero or non-finite `u`, or a non-finite `x`, gives `x` alone as by
+/// [format_eng] with the default 3 significant figures.
+///
+/// # Arguments
+/// - `x` - value to be formatted
+/// - `u` - uncertainty of `x`
+/// - `style` - `±` or parenthetical notation of `u`
+///
+/// # Examples
+/// ```
+/// use eng_fmt::{format_eng_unc, UncertaintyStyle};
+/// assert_eq!(
+///     format_eng_unc(4.8231e4, 1.3e2, UncertaintyStyle::PlusMinus),
+///     "48.23e3 ± 0.13e3"
+/// );
+/// assert_eq!(
+///     format_eng_unc(4.8231e4, 1.3e2, UncertaintyStyle::Parenthetical),
+///     "48.23(13)e3"
+/// );
+/// assert_eq!(format_eng_unc(2.5, 0., UncertaintyStyle::PlusMinus), "2.50");
+/// ```
+pub fn format_eng_unc(x: f64, u: f64, style: UncertaintyStyle) -> String {
+    if u == 0. || !u.is_finite() || !x.is_finite() {
+        return format_eng(x, None);
+    }
+    let u = round_exact(u.abs(), 2, RoundingMode::HalfAwayFromZero);
+    // power of ten of the second significant figure of `u`, the last place shown
+    let place = u.exp_sci - 1;
+    let u_count: String = u.digits.iter().map(|d| char::from(b'0' + d)).collect();
+    let x_count = round_at_place(x, place);
+    let x_exp_sci = match x_count.as_str() {
+        "0" => i32::MIN,
+        _ => place + x_count.len() as i32 - 1,
+    };
+    let exp = eng_exponent_from_sci(x_exp_sci.max(u.exp_sci), 3);
+
+    let sign = if x.is_sign_negative() { "-" } else { "" };
+    let x_text = scaled(&x_count, place, exp);
+    let suffix = match exp {
+        0 => String::new(),
+        _ => format!("e{exp}"),
+    };
+    match style {
+        UncertaintyStyle::PlusMinus => {
+            let u_text = scaled(&u_count, place, exp);
+            format!("{sign}{x_text}{suffix} ± {u_text}{suffix}")
+        }
+        UncertaintyStyle::Parenthetical => {
+            // `u` in units of the last digit shown, which is the units digit if `place > exp`
+            let u_text = scaled(&u_count, place, place.min(exp));
+            format!("{sign}{x_text}({u_text}){suffix}")
+        }
+    }
+}
+
+/// Returns the integer with ASCII digits `count` times `10^place`, divided by `10^exp`, in
+/// positional notation with `exp - place` decimals when `place < exp`
+fn scaled(count: &str, place: i32, exp: i32) -> String {
+    if count == "0" && place >= exp {
+        return String::from("0");
+    }
+    if place >= exp {
+        return format!("{count}{}", "0".repeat((place - exp) as usize));
+    }
+    let n_dec = (exp - place) as usize;
+    let count = format!("{count:0>width$}", width = n_dec + 1);
+    let (int, dec) = count.split_at(count.len() - n_dec);
+    format!("{int}.{dec}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plus_minus() {
+        let table = [
+            (4.8231e4, 1.3e2, "48.23e3 ± 0.13e3"),
+            (2.35, 0.125, "2.35 ± 0.13"),
+            (1.23456e-3, 1.2e-5, "1.235e-3 ± 0.012e-3"),
+            (6.67430e-11, 1.5e-15, "66.7430e-12 ± 0.0015e-12"),
+            (-4.8231e4, -1.3e2, "-48.23e3 ± 0.13e3"),
+            (999.999_6, 0.13, "1.00000e3 ± 0.00013e3"),
+            (12.345, 0.0996, "12.35 ± 0.10"),
+        ];
+        for (x, u, expected) in table {
+            assert_eq!(format_eng_unc(x, u, UncertaintyStyle::PlusMinus), expected);
+        }
+    }
+    #[test]
+    fn test_parenthetical() {
+        let table = [
+            (4.8231e4, 1.3e2, "48.23(13)e3"),
+            (2.35, 0.125, "2.35(13)"),
+            (6.67430e-11, 1.5e-15, "66.7430(15)e-12"),
+            (-1.23456e-3, 1.2e-5, "-1.235(12)e-3"),
+            (12.345, 0.0996, "12.35(10)"),
+        ];
+        for (x, u, expected) in table {
+            assert_eq!(
+                format_eng_unc(x, u, UncertaintyStyle::Parenthetical),
+                expected
+            );
+        }
+    }
+    #[test]
+    fn test_uncertainty_larger_than_value() {
+        let table = [
+            (0.5, 2.3, "0.5 ± 2.3", "0.5(23)"),
+            (5., 1300., "0.0e3 ± 1.3e3", "0.0(13)e3"),
+            (1., 130., "0 ± 130", "0(130)"),
+            (-40., 1300., "-0.0e3 ± 1.3e3", "-0.0(13)e3"),
+        ];
+        for (x, u, plus_minus, parenthetical) in table {
+            assert_eq!(
+                format_eng_unc(x, u, UncertaintyStyle::PlusMinus),
+                plus_minus
+            );
+            assert_eq!(
+                format_eng_unc(x, u, UncertaintyStyle::Parenthetical),
+                parenthetical
+            );
+        }
+    }
+    #[test]
+    fn test_fallback() {
+        for style in [UncertaintyStyle::PlusMinus, UncertaintyStyle::Parenthetical] {
+            assert_eq!(format_eng_unc(4.8231e4, 0., style), "48.2e3");
+            assert_eq!(format_eng_unc(-2.5, f64::NAN, style), "-2.50");
+            assert_eq!(format_eng_unc(f64::INFINITY, 1., style), "inf");
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Module for terminal visualizations annotated in [engineering
+//! notation](https://en.wikipedia.org/wiki/Engineering_notation).
+
+use crate::format_eng;
+
+/// Block characters from lowest to highest level
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Character used when every value is equal
+const FLAT: char = '▄';
+/// Character used for columns with no finite value
+const GAP: char = ' ';
+
+/// Returns a Unicode block-character sparkline of `values` followed by the range of the
+/// finite values formatted with [format_eng], e.g. `"▁▂▅▇█  [210e-6 … 4.30e-3]"`.  When
+/// `values` is longer than `width`, consecutive values are averaged into `width` columns;
+/// otherwise there is one column per value.  Non-finite values are skipped, and columns with
+/// no finite value render as gaps.  The range annotation is omitted if there are no finite
+/// values, and an empty slice gives an empty string.
+///
+/// # Arguments
+/// - `values` - values to be plotted
+/// - `width` - maximum number of columns
+///
+/// # Examples
+/// ```
+/// use eng_fmt::viz::eng_sparkline;
+/// assert_eq!(
+///     eng_sparkline(&[210e-6, 1e-3, 4.3e-3], 8),
+///     "▁▂█  [210e-6 … 4.30e-3]"
+/// );
+/// ```
+pub fn eng_sparkline(values: &[f64], width: usize) -> String {
+    let columns = resample(values, width);
+    let finite = columns.iter().flatten();
+    let (min, max) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+        (min.min(*x), max.max(*x))
+    });
+    let mut line: String = columns
+        .iter()
+        .map(|column| match column {
+            None => GAP,
+            Some(_) if min == max => FLAT,
+            Some(x) => BARS[((x - min) / (max - min) * (BARS.len() - 1) as f64).round() as usize],
+        })
+        .collect();
+    if min <= max {
+        line.push_str(&format!(
+            "  [{} … {}]",
+            format_eng(min, None),
+            format_eng(max, None)
+        ));
+    }
+    line
+}
+
+/// Same as [eng_sparkline] with a second line showing the latest finite value, e.g.
+/// `"▁▂▅▇█  [210e-6 … 4.30e-3]\nlatest: 4.30e-3"`.
+///
+/// # Arguments
+/// - `values` - values to be plotted
+/// - `width` - maximum number of columns
+pub fn eng_sparkline_latest(values: &[f64], width: usize) -> String {
+    let latest = match values.iter().rev().find(|x| x.is_finite()) {
+        Some(x) => format_eng(*x, None),
+        None => String::from("-"),
+    };
+    format!("{}\nlatest: {latest}", eng_sparkline(values, width))
+}
+
+/// Averages the finite values of `values` into at most `width` columns
+fn resample(values: &[f64], width: usize) -> Vec<Option<f64>> {
+    let n_columns = values.len().min(width);
+    (0..n_columns)
+        .map(|i| {
+            let bucket = &values[i * values.len() / n_columns..(i + 1) * values.len() / n_columns];
+            let (sum, n) = bucket
+                .iter()
+                .filter(|x| x.is_finite())
+                .fold((0., 0), |(sum, n), x| (sum + x, n + 1));
+            (n > 0).then(|| sum / n as f64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_vector() {
+        let values = [0., 1., 2., 3., 4., 5., 6., 7.];
+        assert_eq!(
+            eng_sparkline(&values, 10),
+            String::from("▁▂▃▄▅▆▇█  [0.00 … 7.00]")
+        );
+    }
+    #[test]
+    fn test_resampled() {
+        let values = [1e-3, 3e-3, 2e-3, 2e-3, 5e-3, 7e-3];
+        assert_eq!(
+            eng_sparkline(&values, 3),
+            String::from("▁▁█  [2.00e-3 … 6.00e-3]")
+        );
+    }
+    #[test]
+    fn test_nan_gap() {
+        let values = [1., f64::NAN, 2., f64::INFINITY, 3.];
+        assert_eq!(
+            eng_sparkline(&values, 5),
+            String::from("▁ ▅ █  [1.00 … 3.00]")
+        );
+    }
+    #[test]
+    fn test_all_equal() {
+        assert_eq!(
+            eng_sparkline(&[42e3; 4], 4),
+            String::from("▄▄▄▄  [42.0e3 … 42.0e3]")
+        );
+    }
+    #[test]
+    fn test_empty() {
+        assert_eq!(eng_sparkline(&[], 10), String::new());
+        assert_eq!(eng_sparkline_latest(&[], 10), String::from("\nlatest: -"));
+    }
+    #[test]
+    fn test_all_nan() {
+        assert_eq!(eng_sparkline(&[f64::NAN; 3], 3), String::from("   "));
+    }
+    #[test]
+    fn test_latest() {
+        assert_eq!(
+            eng_sparkline_latest(&[210e-6, 1e-3, 4.3e-3, f64::NAN], 8),
+            String::from("▁▂█   [210e-6 … 4.30e-3]\nlatest: 4.30e-3")
+        );
+    }
+}
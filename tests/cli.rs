@@ -0,0 +1,163 @@
+//! Tests of the `eng_fmt` binary, run as a child process.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Runs `eng_fmt` with `args` and `stdin`
+fn eng_fmt(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_eng_fmt"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Returns stdout and stderr of `output` as text
+fn text(output: &Output) -> (String, String) {
+    (
+        String::from_utf8(output.stdout.clone()).unwrap(),
+        String::from_utf8(output.stderr.clone()).unwrap(),
+    )
+}
+
+#[test]
+fn test_args() {
+    let output = eng_fmt(&["0.010", "6.022e-23", "--sf", "4"], "");
+    assert!(output.status.success());
+    assert_eq!(
+        text(&output),
+        (String::from("10.00e-3\n60.22e-24\n"), String::new())
+    );
+
+    let output = eng_fmt(&["--si", "4700", "-0.000047"], "");
+    assert!(output.status.success());
+    assert_eq!(text(&output).0, "4.70k\n-47.0µ\n");
+}
+
+#[test]
+fn test_stdin() {
+    let output = eng_fmt(&["--sf", "5"], "3.14159265\n  1234567 \n\n-2.5e-9\t1e300\n");
+    assert!(output.status.success());
+    assert_eq!(
+        text(&output).0,
+        "3.1416\n1.2346e6\n-2.5000e-9\n1.0000e300\n"
+    );
+
+    let output = eng_fmt(&[], "");
+    assert!(output.status.success());
+    assert_eq!(text(&output), (String::new(), String::new()));
+}
+
+#[test]
+fn test_parse() {
+    let output = eng_fmt(&["--parse", "10.0e-3", "4.70k", "-47.0µ", "NaN"], "");
+    assert!(output.status.success());
+    assert_eq!(text(&output).0, "0.01\n4700.0\n-4.7e-5\nNaN\n");
+
+    let output = eng_fmt(&["--parse"], "60.2e-24 1.00e3");
+    assert_eq!(text(&output).0, "6.02e-23\n1000.0\n");
+}
+
+#[test]
+fn test_invalid_token() {
+    let output = eng_fmt(&["1.5", "abc", "2e3"], "");
+    assert_eq!(output.status.code(), Some(1));
+    let (stdout, stderr) = text(&output);
+    assert_eq!(stdout, "1.50\n2.00e3\n");
+    assert!(
+        stderr.starts_with("eng_fmt: invalid number \"abc\""),
+        "{stderr}"
+    );
+
+    let output = eng_fmt(&["--parse"], "1.0x");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(text(&output).1.contains("\"1.0x\""));
+}
+
+#[test]
+fn test_usage_errors() {
+    for args in [
+        &["--sf"][..],
+        &["--sf", "0", "1"],
+        &["--sf", "1000", "1"],
+        &["--sf", "three", "1"],
+        &["--bogus"],
+        &["diff", "only_one"],
+    ] {
+        let output = eng_fmt(args, "");
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        let (stdout, stderr) = text(&output);
+        assert!(stdout.is_empty());
+        assert!(stderr.contains("usage: eng_fmt"), "{stderr}");
+    }
+}
+
+#[cfg(feature = "io")]
+#[test]
+fn test_downsample() {
+    let csv = "time,current\nt0,1e-3\nt1,3e-3\nt2,2e-3\nt3,6e-3\nt4,NaN\n";
+    let output = eng_fmt(&["--downsample", "2", "--header", "--sf", "2"], csv);
+    assert!(output.status.success());
+    assert_eq!(
+        text(&output),
+        (
+            String::from("time,min,mean,max\nt0,1.0e-3,2.0e-3,3.0e-3\nt2,2.0e-3,4.0e-3,6.0e-3\n"),
+            String::new()
+        )
+    );
+
+    let output = eng_fmt(&["--downsample", "2"], "t0,1e-3\nt1,oops\n");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(text(&output).1.contains("line 2"), "{:?}", text(&output));
+
+    for args in [
+        &["--downsample"][..],
+        &["--downsample", "0"],
+        &["--downsample", "2", "1.5"],
+        &["--downsample", "2", "--si"],
+    ] {
+        let output = eng_fmt(args, "t0,1\n");
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(text(&output).1.contains("usage: eng_fmt"));
+    }
+}
+
+#[test]
+fn test_diff() {
+    let dir = std::env::temp_dir().join(format!("eng_fmt_cli_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let old = dir.join("old.txt");
+    let new = dir.join("new.txt");
+    let same = dir.join("same.txt");
+    std::fs::write(&old, "gain: 1.000e3  offset: 12.0e-3\n").unwrap();
+    std::fs::write(&new, "gain: 1000.1 offset: 12.5e-3\n").unwrap();
+    std::fs::write(&same, "gain: 1000.2 offset: 12.04e-3\n").unwrap();
+    let path = |p: &std::path::Path| p.to_str().unwrap().to_string();
+
+    let output = eng_fmt(&["diff", &path(&old), &path(&new)], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(text(&output).0, "1:22: 12.0e-3 -> 12.5e-3 (+4.17 %)\n");
+
+    let output = eng_fmt(&["diff", &path(&old), &path(&same)], "");
+    assert!(output.status.success());
+    assert_eq!(text(&output).0, "");
+
+    let output = eng_fmt(&["diff", "--tol-sf", "4", &path(&old), &path(&same)], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(text(&output).0, "1:22: 12.00e-3 -> 12.04e-3 (+0.33 %)\n");
+
+    let output = eng_fmt(&["diff", &path(&old), &path(&dir.join("missing.txt"))], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(text(&output).1.contains("missing.txt"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
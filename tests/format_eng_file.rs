@@ -0,0 +1,35 @@
+use std::fs;
+use std::path::PathBuf;
+
+use eng_fmt::format_eng;
+use eng_fmt::io::{format_eng_file, FileFormatOptions};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("eng_fmt_{}_{name}", std::process::id()))
+}
+
+#[test]
+fn test_format_eng_file_1m_values() {
+    let n = 1_000_000;
+    let values: Vec<f64> = (0..n)
+        .map(|i| (i as f64 - 500_000.) * 1.2345e-3 * 10_f64.powi(i % 13 - 6))
+        .collect();
+    let input = temp_path("in.bin");
+    let output = temp_path("out.txt");
+    let bytes: Vec<u8> = values.iter().flat_map(|x| x.to_le_bytes()).collect();
+    fs::write(&input, bytes).unwrap();
+
+    let stats = format_eng_file(&input, &output, FileFormatOptions::default()).unwrap();
+    let text = fs::read_to_string(&output).unwrap();
+    fs::remove_file(&input).unwrap();
+    fs::remove_file(&output).unwrap();
+
+    assert_eq!(stats.count, n as usize);
+    assert_eq!(stats.min, values.iter().copied().reduce(f64::min));
+    assert_eq!(stats.max, values.iter().copied().reduce(f64::max));
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), n as usize);
+    for i in [0, 1, 4_095, 4_096, 500_000, 777_777, n as usize - 1] {
+        assert_eq!(lines[i], format_eng(values[i], None));
+    }
+}
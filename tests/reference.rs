@@ -0,0 +1,63 @@
+//! Differential test of `format_eng` against `tests/reference/expected.json`, which
+//! `tools/gen_reference.py` generates with Python's `decimal` module as ground truth.  The
+//! canonical semantics both implementations follow are documented in that script.
+
+use eng_fmt::{eng_parts, format_eng, format_eng_into, Sign};
+
+/// Returns the text between `"<key>": ` and the next `,` or `}` of a one-line JSON object,
+/// without surrounding quotes
+fn field<'a>(line: &'a str, key: &str) -> &'a str {
+    let start = line.find(&format!("\"{key}\": ")).unwrap() + key.len() + 4;
+    let rest = &line[start..];
+    let end = rest.find([',', '}']).unwrap();
+    rest[..end].trim_matches('"')
+}
+
+#[test]
+fn test_reference() {
+    let json = include_str!("reference/expected.json");
+    let mut mismatches = Vec::new();
+    let mut n_cases = 0;
+    for line in json
+        .lines()
+        .filter(|line| line.trim_start().starts_with('{'))
+    {
+        let x: f64 = field(line, "x").parse().unwrap();
+        let sf: usize = field(line, "sf").parse().unwrap();
+        let expected = field(line, "expected");
+        let formatted = format_eng(x, Some(sf));
+        if formatted != expected {
+            mismatches.push(format!("x: {x:e}, sf: {sf}: {formatted} != {expected}"));
+        }
+        let mut written = String::new();
+        format_eng_into(&mut written, x, Some(sf)).unwrap();
+        if written != expected {
+            mismatches.push(format!("into x: {x:e}, sf: {sf}: {written} != {expected}"));
+        }
+        let parts = eng_parts(x, Some(sf));
+        let rebuilt = format!(
+            "{}{}{}",
+            if parts.sign == Sign::Negative {
+                "-"
+            } else {
+                ""
+            },
+            parts.mantissa,
+            match parts.exponent {
+                0 => String::new(),
+                exp => format!("e{exp}"),
+            }
+        );
+        if rebuilt != expected || parts.to_string() != expected || parts.exponent % 3 != 0 {
+            mismatches.push(format!("parts x: {x:e}, sf: {sf}: {parts:?} != {expected}"));
+        }
+        n_cases += 1;
+    }
+    assert_eq!(n_cases, 5000);
+    assert!(
+        mismatches.is_empty(),
+        "{} mismatches:\n{}",
+        mismatches.len(),
+        mismatches.join("\n")
+    );
+}
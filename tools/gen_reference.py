@@ -0,0 +1,123 @@
+#!/usr/bin/env python3
+"""Generates the expected outputs for `tests/reference.rs` with Python's `decimal` module.
+
+Canonical semantics that every implementation of `format_eng` must follow:
+
+- Zero formats as `0` with `sf - 1` decimals, keeping the sign of negative zero (`-0.00`).
+- Any other finite value is rounded from its *exact* binary value (not its shortest repr) to
+  `sf` significant figures, with ties rounded half away from zero.
+- The exponent is the largest multiple of 3 not above the decade of the *rounded* value, so
+  999.7 at 3 sf is `1.00e3`, never `1000`.
+- The mantissa shows `max(sf, digits left of the decimal point)` digits, padding with zeros
+  (e.g. `30e-3` at 1 sf), and has a decimal point only if digits follow it.
+- The exponent is written as `e<exp>` and omitted when it is 0.
+
+Usage:
+    tools/gen_reference.py                      # inputs.txt -> expected.json
+    tools/gen_reference.py --regenerate-inputs  # rewrite inputs.txt first
+"""
+
+import argparse
+import math
+import random
+import struct
+from decimal import ROUND_HALF_UP, Decimal, localcontext
+from pathlib import Path
+
+REFERENCE_DIR = Path(__file__).resolve().parent.parent / "tests" / "reference"
+INPUTS = REFERENCE_DIR / "inputs.txt"
+EXPECTED = REFERENCE_DIR / "expected.json"
+MAX_SF = 17
+
+
+def format_eng(x: float, sf: int) -> str:
+    """Returns `x` in engineering notation at `sf` significant figures per the rules above."""
+    if x == 0:
+        return ("-" if math.copysign(1, x) < 0 else "") + format(0, f".{sf - 1}f")
+    with localcontext() as ctx:
+        ctx.prec = sf
+        ctx.rounding = ROUND_HALF_UP
+        ctx.Emin, ctx.Emax = -999_999, 999_999
+        rounded = +abs(Decimal(x))  # Decimal(float) is exact; unary plus rounds
+    exp_sci = rounded.adjusted()
+    digits = "".join(map(str, rounded.as_tuple().digits)).ljust(sf, "0")
+    exp_eng = exp_sci - exp_sci % 3
+    n_left_of_dec = exp_sci - exp_eng + 1
+    digits = digits.ljust(n_left_of_dec, "0")
+    mantissa = digits[:n_left_of_dec]
+    if len(digits) > n_left_of_dec:
+        mantissa += "." + digits[n_left_of_dec:]
+    sign = "-" if x < 0 else ""
+    return sign + mantissa + (f"e{exp_eng}" if exp_eng else "")
+
+
+def from_bits(bits: int) -> float:
+    return struct.unpack("<d", struct.pack("<Q", bits))[0]
+
+
+def tricky_inputs() -> list[float]:
+    """Returns ~5000 deterministic inputs around rounding, decade, and range boundaries."""
+    rng = random.Random(20_221)
+    values = [
+        0.0,
+        -0.0,
+        5e-324,
+        2.2250738585072014e-308,
+        from_bits(0x000F_FFFF_FFFF_FFFF),
+        1.7976931348623157e308,
+        -1.7976931348623157e308,
+    ]
+    # neighbors of every power of ten
+    for k in range(-323, 309):
+        p = float(f"1e{k}")
+        values += [math.nextafter(p, 0), p, math.nextafter(p, math.inf)]
+    # decimal ties and near-ties at 1 to 6 significant figures
+    for _ in range(800):
+        sf = rng.randint(1, 6)
+        digits = rng.randint(10 ** (sf - 1), 10**sf - 1)
+        tie = float(f"{digits}5e{rng.randint(-30, 30)}")
+        values.append(rng.choice([tie, math.nextafter(tie, 0), math.nextafter(tie, math.inf)]))
+    # all-nines values that carry into the next decade or exponent
+    for _ in range(400):
+        nines = "9" * rng.randint(1, 16)
+        values.append(float(f"{nines}{rng.choice(['', '5', '4'])}e{rng.randint(-40, 40)}"))
+    # subnormals
+    for _ in range(200):
+        values.append(from_bits(rng.getrandbits(52)))
+    # random finite bit patterns
+    while len(values) < 4700:
+        x = from_bits(rng.getrandbits(64))
+        if math.isfinite(x):
+            values.append(x)
+    # random short decimals like sensor readings
+    while len(values) < 5000:
+        values.append(float(f"{rng.randint(1, 99_999)}e{rng.randint(-15, 15)}"))
+    return [-x if rng.random() < 0.3 else x for x in values]
+
+
+def write_inputs() -> None:
+    rng = random.Random(233)
+    lines = [f"{x!r} {rng.randint(1, MAX_SF)}\n" for x in tricky_inputs()]
+    INPUTS.write_text("".join(lines))
+
+
+def write_expected() -> None:
+    cases = []
+    for line in INPUTS.read_text().splitlines():
+        x, sf = line.split()
+        expected = format_eng(float(x), int(sf))
+        cases.append(f'  {{"x": "{x}", "sf": {sf}, "expected": "{expected}"}}')
+    EXPECTED.write_text("[\n" + ",\n".join(cases) + "\n]\n")
+
+
+def main() -> None:
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--regenerate-inputs", action="store_true")
+    args = parser.parse_args()
+    if args.regenerate_inputs:
+        write_inputs()
+    write_expected()
+
+
+if __name__ == "__main__":
+    main()